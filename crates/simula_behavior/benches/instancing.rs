@@ -0,0 +1,162 @@
+use bevy::{ecs::system::CommandQueue, prelude::*};
+use criterion::{criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion};
+use simula_behavior::{
+    prelude::*,
+    test::{test_app, TestBehavior},
+};
+use std::sync::Arc;
+
+/// A tree of 32 nodes, 5 sequences of 5 actions under a selector, repeated forever
+fn agent_document() -> BehaviorDocument<TestBehavior> {
+    let sequences = (0..5)
+        .map(|sequence| {
+            let actions = (0..5)
+                .map(|action| format!("(\"Action {}\", Debug(()))", action))
+                .collect::<Vec<_>>()
+                .join(",");
+            format!("(\"Sequence {}\", Sequencer(()), [{}])", sequence, actions)
+        })
+        .collect::<Vec<_>>()
+        .join(",");
+    let behavior = format!(
+        "(root: (\"Agent\", Repeater((repeat: Forever)), [(\"Choose\", Selector(()), [{}])]))",
+        sequences
+    );
+    ron::from_str(&behavior).unwrap()
+}
+
+fn apply(world: &mut World, f: impl FnOnce(&mut Commands)) {
+    let mut command_queue = CommandQueue::default();
+    let mut commands = Commands::new(&mut command_queue, world);
+    f(&mut commands);
+    command_queue.apply(world);
+}
+
+/// Spawned from the document for every agent, one entity per node
+fn spawn_documents(world: &mut World, document: &BehaviorDocument<TestBehavior>, agents: usize) {
+    apply(world, |commands| {
+        for _ in 0..agents {
+            let root = BehaviorTree::spawn_tree(None, commands, &document.root);
+            commands.entity(root).insert(BehaviorCursor);
+            commands
+                .spawn()
+                .insert(BehaviorTree { root: Some(root) })
+                .insert(BehaviorBlackboard::from_document(document));
+        }
+    });
+}
+
+/// Spawned from a shared definition, one entity per tree, reusing released trees
+fn spawn_pooled(
+    world: &mut World,
+    pool: &mut BehaviorTreePool,
+    definition: &Arc<BehaviorDefinition<TestBehavior>>,
+    agents: usize,
+) -> Vec<Entity> {
+    let mut trees = vec![];
+    apply(world, |commands| {
+        for _ in 0..agents {
+            trees.push(pool.spawn(commands, definition));
+        }
+    });
+    trees
+}
+
+fn release_all(
+    world: &mut World,
+    pool: &mut BehaviorTreePool,
+    definition: &Arc<BehaviorDefinition<TestBehavior>>,
+    trees: &[Entity],
+) {
+    apply(world, |commands| {
+        for tree in trees {
+            pool.release(commands, *tree, definition.id(), None);
+        }
+    });
+}
+
+fn instancing(c: &mut Criterion) {
+    let document = agent_document();
+    let definition = BehaviorDefinition::from_document(agent_document());
+    let mut group = c.benchmark_group("instancing");
+    for agents in [100, 1000] {
+        group.bench_with_input(
+            BenchmarkId::new("entity_per_node", agents),
+            &agents,
+            |b, agents| {
+                b.iter_batched(
+                    World::new,
+                    |mut world| {
+                        spawn_documents(&mut world, &document, *agents);
+                        world
+                    },
+                    BatchSize::LargeInput,
+                )
+            },
+        );
+        group.bench_with_input(
+            BenchmarkId::new("compact", agents),
+            &agents,
+            |b, agents| {
+                b.iter_batched(
+                    || (World::new(), BehaviorTreePool::default()),
+                    |(mut world, mut pool)| {
+                        spawn_pooled(&mut world, &mut pool, &definition, *agents);
+                        (world, pool)
+                    },
+                    BatchSize::LargeInput,
+                )
+            },
+        );
+        group.bench_with_input(BenchmarkId::new("pooled", agents), &agents, |b, agents| {
+            b.iter_batched(
+                || {
+                    // Agents that came and went, leaving their trees in the pool
+                    let mut world = World::new();
+                    let mut pool = BehaviorTreePool::new(*agents);
+                    let trees = spawn_pooled(&mut world, &mut pool, &definition, *agents);
+                    release_all(&mut world, &mut pool, &definition, &trees);
+                    (world, pool)
+                },
+                |(mut world, mut pool)| {
+                    spawn_pooled(&mut world, &mut pool, &definition, *agents);
+                    (world, pool)
+                },
+                BatchSize::LargeInput,
+            )
+        });
+    }
+    group.finish();
+}
+
+/// Frames of running agents, once every tree is started
+fn running(c: &mut Criterion) {
+    let document = agent_document();
+    let definition = BehaviorDefinition::from_document(agent_document());
+    let mut group = c.benchmark_group("running");
+    for agents in [100, 1000] {
+        group.bench_with_input(
+            BenchmarkId::new("entity_per_node", agents),
+            &agents,
+            |b, agents| {
+                let mut app = App::new();
+                test_app(&mut app);
+                spawn_documents(&mut app.world, &document, *agents);
+                app.update();
+                b.iter(|| app.update())
+            },
+        );
+        group.bench_with_input(BenchmarkId::new("compact", agents), &agents, |b, agents| {
+            let mut app = App::new();
+            test_app(&mut app);
+            let mut pool = BehaviorTreePool::default();
+            spawn_pooled(&mut app.world, &mut pool, &definition, *agents);
+            app.update();
+            b.iter(|| app.update())
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, instancing, running);
+criterion_main!(benches);
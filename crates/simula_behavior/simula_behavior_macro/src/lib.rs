@@ -0,0 +1,277 @@
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::{
+    parse_macro_input, parse_quote, Data, DeriveInput, Fields, ItemEnum, Lit, Meta, NestedMeta,
+};
+
+/// Built-in behavior nodes, as variant name and type path
+const BUILTINS: &[(&str, &str)] = &[
+    ("Debug", "::simula_behavior::actions::Debug"),
+    ("Delay", "::simula_behavior::actions::Delay"),
+    ("Condition", "::simula_behavior::actions::Condition"),
+    ("SetBlackboard", "::simula_behavior::actions::SetBlackboard"),
+    ("DriveSignal", "::simula_behavior::actions::DriveSignal"),
+    ("SampleSignal", "::simula_behavior::actions::SampleSignal"),
+    ("Selector", "::simula_behavior::composites::Selector"),
+    ("Sequencer", "::simula_behavior::composites::Sequencer"),
+    ("All", "::simula_behavior::composites::All"),
+    ("Any", "::simula_behavior::composites::Any"),
+    ("Parallel", "::simula_behavior::composites::Parallel"),
+    (
+        "ReactiveSequence",
+        "::simula_behavior::composites::ReactiveSequence",
+    ),
+    (
+        "ReactiveSelector",
+        "::simula_behavior::composites::ReactiveSelector",
+    ),
+    (
+        "UtilitySelector",
+        "::simula_behavior::composites::UtilitySelector",
+    ),
+    (
+        "WeightedSelector",
+        "::simula_behavior::composites::WeightedSelector",
+    ),
+    ("Repeater", "::simula_behavior::decorators::Repeater"),
+    ("Inverter", "::simula_behavior::decorators::Inverter"),
+    ("Succeeder", "::simula_behavior::decorators::Succeeder"),
+    ("Subtree", "::simula_behavior::decorators::Subtree"),
+    ("Observer", "::simula_behavior::decorators::Observer"),
+    ("Timeout", "::simula_behavior::decorators::Timeout"),
+    ("Cooldown", "::simula_behavior::decorators::Cooldown"),
+    ("Retry", "::simula_behavior::decorators::Retry"),
+    (
+        "ForceFailure",
+        "::simula_behavior::decorators::ForceFailure",
+    ),
+    ("Limit", "::simula_behavior::decorators::Limit"),
+];
+
+/// Derive `BehaviorSpawner` for an enum of behavior nodes. Every variant must hold a
+/// single `BehaviorInfo` node. Custom nodes can name their system with
+/// `#[behavior(run = "path::to::run")]` so it gets added on registration.
+#[proc_macro_derive(BehaviorSpawner, attributes(behavior))]
+pub fn behavior_spawner_derive(input: TokenStream) -> TokenStream {
+    let ast = parse_macro_input!(input as DeriveInput);
+    match impl_behavior_spawner(&ast) {
+        Ok(gen) => gen.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+fn impl_behavior_spawner(ast: &DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+    let name = &ast.ident;
+    let data = match &ast.data {
+        Data::Enum(data) => data,
+        _ => {
+            return Err(syn::Error::new_spanned(
+                name,
+                "BehaviorSpawner can only be derived for enums",
+            ))
+        }
+    };
+
+    let mut inserts = vec![];
+    let mut extracts = vec![];
+    let mut registers = vec![];
+    let mut removes = vec![];
+    let mut types = vec![];
+    let mut names = vec![];
+    let mut defaults = vec![];
+    let mut inspects = vec![];
+    let mut ticks = vec![];
+    let mut registrations = vec![];
+    for variant in data.variants.iter() {
+        let ident = &variant.ident;
+        let typ = match &variant.fields {
+            Fields::Unnamed(fields) if fields.unnamed.len() == 1 => &fields.unnamed[0].ty,
+            _ => {
+                return Err(syn::Error::new_spanned(
+                    variant,
+                    "BehaviorSpawner variants must hold a single behavior node",
+                ))
+            }
+        };
+        inserts.push(quote! {
+            #name::#ident(data) => ::simula_behavior::BehaviorInfo::insert_with(commands, data),
+        });
+        extracts.push(quote! {
+            if let Some(data) = world.get::<#typ>(entity) {
+                return Some(#name::#ident(data.clone()));
+            }
+        });
+        registers.push(quote! {
+            ::simula_behavior::register_behavior::<#typ>(app);
+        });
+        registrations.push(quote! {
+            registry.register::<#typ>();
+        });
+        removes.push(quote! {
+            commands.remove::<#typ>();
+        });
+        types.push(quote! {
+            #name::#ident(_) => <#typ as ::simula_behavior::BehaviorInfo>::TYPE,
+        });
+        names.push(quote! {
+            #name::#ident(_) => <#typ as ::simula_behavior::BehaviorInfo>::NAME,
+        });
+        defaults.push(quote! {
+            #name::#ident(<#typ as ::std::default::Default>::default()),
+        });
+        inspects.push(quote! {
+            #name::#ident(data) => ::bevy_inspector_egui::Inspectable::ui(
+                data,
+                ui,
+                ::std::default::Default::default(),
+                context,
+            ),
+        });
+        ticks.push(quote! {
+            #name::#ident(data) => ::simula_behavior::BehaviorInfo::tick_compact(data, context),
+        });
+        if let Some(run) = run_attribute(&variant.attrs)? {
+            registers.push(quote! {
+                ::simula_behavior::BehaviorAppExt::add_behavior_system(app, #run);
+            });
+        }
+    }
+
+    Ok(quote! {
+        impl ::simula_behavior::BehaviorSpawner for #name {
+            fn insert(&self, commands: &mut ::bevy::ecs::system::EntityCommands) {
+                match self {
+                    #(#inserts)*
+                }
+            }
+
+            fn from_entity(
+                world: &::bevy::prelude::World,
+                entity: ::bevy::prelude::Entity,
+            ) -> Option<Self> {
+                #(#extracts)*
+                None
+            }
+
+            fn register(app: &mut ::bevy::prelude::App) {
+                #(#registers)*
+            }
+
+            fn register_behaviors(registry: &mut ::simula_behavior::registry::BehaviorRegistry) {
+                #(#registrations)*
+            }
+
+            fn behavior_type(&self) -> Option<::simula_behavior::prelude::BehaviorType> {
+                Some(match self {
+                    #(#types)*
+                })
+            }
+
+            fn remove(commands: &mut ::bevy::ecs::system::EntityCommands) {
+                #(#removes)*
+            }
+
+            fn behavior_name(&self) -> Option<&'static str> {
+                Some(match self {
+                    #(#names)*
+                })
+            }
+
+            fn defaults() -> Vec<Self> {
+                vec![
+                    #(#defaults)*
+                ]
+            }
+
+            fn inspect(
+                &mut self,
+                ui: &mut ::bevy_inspector_egui::egui::Ui,
+                context: &mut ::bevy_inspector_egui::Context,
+            ) -> bool {
+                match self {
+                    #(#inspects)*
+                }
+            }
+
+            fn tick_compact(
+                &self,
+                context: &mut ::simula_behavior::instance::BehaviorTickContext,
+            ) -> Option<::simula_behavior::instance::BehaviorTick> {
+                match self {
+                    #(#ticks)*
+                }
+            }
+        }
+    })
+}
+
+fn run_attribute(attrs: &[syn::Attribute]) -> syn::Result<Option<syn::Path>> {
+    let mut run = None;
+    let mut errors: Option<syn::Error> = None;
+    let mut report = |error: syn::Error| match &mut errors {
+        Some(errors) => errors.combine(error),
+        None => errors = Some(error),
+    };
+    for attr in attrs.iter().filter(|attr| attr.path.is_ident("behavior")) {
+        let list = match attr.parse_meta()? {
+            Meta::List(list) => list,
+            meta => {
+                report(syn::Error::new_spanned(meta, "Expected #[behavior(...)]"));
+                continue;
+            }
+        };
+        for nested in list.nested.iter() {
+            match nested {
+                NestedMeta::Meta(Meta::NameValue(value)) if value.path.is_ident("run") => {
+                    match &value.lit {
+                        Lit::Str(_) if run.is_some() => {
+                            report(syn::Error::new_spanned(value, "Duplicate run attribute"))
+                        }
+                        Lit::Str(lit) => match lit.parse() {
+                            Ok(path) => run = Some(path),
+                            Err(error) => report(error),
+                        },
+                        lit => report(syn::Error::new_spanned(lit, "Expected a string")),
+                    }
+                }
+                _ => report(syn::Error::new_spanned(
+                    nested,
+                    "Unknown behavior attribute",
+                )),
+            }
+        }
+    }
+    match errors {
+        Some(errors) => Err(errors),
+        None => Ok(run),
+    }
+}
+
+/// Add all built-in behavior nodes as variants of a behavior enum, and make `Debug`
+/// its default. Must be placed before the derives of the enum.
+#[proc_macro_attribute]
+pub fn behavior_builtins(_args: TokenStream, input: TokenStream) -> TokenStream {
+    let mut item = parse_macro_input!(input as ItemEnum);
+    let name = item.ident.clone();
+    let builtins = BUILTINS
+        .iter()
+        .map(|(variant, typ)| {
+            let variant = syn::Ident::new(variant, proc_macro2::Span::call_site());
+            let typ: syn::Type = syn::parse_str(typ).unwrap();
+            parse_quote! { #variant(#typ) }
+        })
+        .collect::<Vec<syn::Variant>>();
+    for (index, variant) in builtins.into_iter().enumerate() {
+        item.variants.insert(index, variant);
+    }
+    let gen = quote! {
+        #item
+
+        impl Default for #name {
+            fn default() -> Self {
+                Self::Debug(Default::default())
+            }
+        }
+    };
+    gen.into()
+}
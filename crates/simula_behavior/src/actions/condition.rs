@@ -0,0 +1,104 @@
+use crate::prelude::*;
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// A test on a blackboard value
+#[derive(Debug, Default, Reflect, Clone, Deserialize, Serialize, Inspectable)]
+pub enum ConditionTest {
+    #[default]
+    IsSet,
+    IsNotSet,
+    IsTrue,
+    IsFalse,
+    Equals(BlackboardValue),
+    NotEquals(BlackboardValue),
+    Greater(f64),
+    Less(f64),
+}
+
+impl ConditionTest {
+    /// Check a blackboard value, None if the key is not set
+    pub fn check(&self, value: Option<&dyn Reflect>) -> bool {
+        match self {
+            ConditionTest::IsSet => value.is_some(),
+            ConditionTest::IsNotSet => value.is_none(),
+            ConditionTest::IsTrue => is_true(value),
+            ConditionTest::IsFalse => !is_true(value),
+            ConditionTest::Equals(expected) => is_equal(value, expected),
+            ConditionTest::NotEquals(expected) => !is_equal(value, expected),
+            ConditionTest::Greater(limit) => as_number(value).map_or(false, |v| v > *limit),
+            ConditionTest::Less(limit) => as_number(value).map_or(false, |v| v < *limit),
+        }
+    }
+
+    /// Check a key of a tree blackboard
+    pub fn check_key(&self, blackboard: Option<&BehaviorBlackboard>, key: &str) -> bool {
+        self.check(blackboard.and_then(|blackboard| blackboard.get_reflect(key)))
+    }
+}
+
+fn is_true(value: Option<&dyn Reflect>) -> bool {
+    value
+        .and_then(|value| value.downcast_ref::<bool>())
+        .copied()
+        .unwrap_or(false)
+}
+
+fn is_equal(value: Option<&dyn Reflect>, expected: &BlackboardValue) -> bool {
+    value
+        .and_then(|value| value.reflect_partial_eq(expected.to_reflect().as_ref()))
+        .unwrap_or(false)
+}
+
+pub(crate) fn as_number(value: Option<&dyn Reflect>) -> Option<f64> {
+    let value = value?;
+    if let Some(value) = value.downcast_ref::<f64>() {
+        Some(*value)
+    } else if let Some(value) = value.downcast_ref::<f32>() {
+        Some(*value as f64)
+    } else if let Some(value) = value.downcast_ref::<i64>() {
+        Some(*value as f64)
+    } else if let Some(value) = value.downcast_ref::<i32>() {
+        Some(*value as f64)
+    } else if let Some(value) = value.downcast_ref::<u64>() {
+        Some(*value as f64)
+    } else if let Some(value) = value.downcast_ref::<u32>() {
+        Some(*value as f64)
+    } else {
+        value.downcast_ref::<usize>().map(|value| *value as f64)
+    }
+}
+
+/// A condition succeeds if a blackboard value passes a test, and fails otherwise.
+#[derive(Debug, Default, Component, Reflect, Clone, Deserialize, Serialize, Inspectable)]
+pub struct Condition {
+    pub key: String,
+    #[serde(default)]
+    pub test: ConditionTest,
+}
+
+impl BehaviorInfo for Condition {
+    const TYPE: BehaviorType = BehaviorType::Action;
+    const NAME: &'static str = "Condition";
+    const DESC: &'static str = "Succeed if a blackboard value passes a test";
+
+    fn tick_compact(&self, context: &mut BehaviorTickContext) -> Option<BehaviorTick> {
+        let success = self.test.check_key(Some(&*context.blackboard), &self.key);
+        Some(BehaviorTick::complete(success))
+    }
+}
+
+pub fn run(
+    mut commands: Commands,
+    conditions: Query<(Entity, &Condition, &BehaviorNode), BehaviorRunQuery>,
+    blackboards: Query<&BehaviorBlackboard>,
+) {
+    for (entity, condition, node) in &conditions {
+        let blackboard = node.tree.and_then(|tree| blackboards.get(tree).ok());
+        if condition.test.check_key(blackboard, &condition.key) {
+            commands.entity(entity).insert(BehaviorSuccess);
+        } else {
+            commands.entity(entity).insert(BehaviorFailure);
+        }
+    }
+}
@@ -18,21 +18,37 @@ impl BehaviorInfo for Debug {
     const TYPE: BehaviorType = BehaviorType::Action;
     const NAME: &'static str = "Debug";
     const DESC: &'static str = "Display a debug message and complete with success or failure";
+
+    fn tick_compact(&self, context: &mut BehaviorTickContext) -> Option<BehaviorTick> {
+        if context.entered {
+            context.local.time = context.time;
+            debug!("RUNNING {}", self.message);
+        }
+        if context.time - context.local.time >= self.duration {
+            Some(BehaviorTick::complete(!self.fail))
+        } else {
+            Some(BehaviorTick::Running)
+        }
+    }
 }
 
 pub fn run(
-    time: Res<Time>,
+    time: BehaviorTime,
     mut commands: Commands,
-    mut debug_actions: Query<(Entity, &mut Debug, &mut BehaviorRunning), BehaviorRunQuery>,
+    mut debug_actions: Query<
+        (Entity, &mut Debug, &mut BehaviorRunning, &BehaviorNode),
+        BehaviorRunQuery,
+    >,
 ) {
-    for (entity, mut debug_action, mut running) in &mut debug_actions {
+    for (entity, mut debug_action, mut running, node) in &mut debug_actions {
+        let now = time.elapsed(node);
         if !running.on_enter_handled {
             running.on_enter_handled = true;
-            debug_action.start = time.seconds_since_startup();
+            debug_action.start = now;
             debug!("[{}] RUNNING {}", entity.id(), debug_action.message);
         }
-        let duration = time.seconds_since_startup() - debug_action.start;
-        if duration > debug_action.duration {
+        let duration = now - debug_action.start;
+        if duration >= debug_action.duration {
             if debug_action.fail {
                 commands.entity(entity).insert(BehaviorFailure);
             } else {
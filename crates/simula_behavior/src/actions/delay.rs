@@ -15,20 +15,32 @@ impl BehaviorInfo for Delay {
     const TYPE: BehaviorType = BehaviorType::Action;
     const NAME: &'static str = "Delay";
     const DESC: &'static str = "Delay for a specified amount of time";
+
+    fn tick_compact(&self, context: &mut BehaviorTickContext) -> Option<BehaviorTick> {
+        if context.entered {
+            context.local.time = context.time;
+        }
+        if context.time - context.local.time >= self.duration {
+            Some(BehaviorTick::Success)
+        } else {
+            Some(BehaviorTick::Running)
+        }
+    }
 }
 
 pub fn run(
-    time: Res<Time>,
+    time: BehaviorTime,
     mut commands: Commands,
-    mut delays: Query<(Entity, &mut Delay, &mut BehaviorRunning), BehaviorRunQuery>,
+    mut delays: Query<(Entity, &mut Delay, &mut BehaviorRunning, &BehaviorNode), BehaviorRunQuery>,
 ) {
-    for (entity, mut delay, mut running) in &mut delays {
+    for (entity, mut delay, mut running, node) in &mut delays {
+        let now = time.elapsed(node);
         if !running.on_enter_handled {
             running.on_enter_handled = true;
-            delay.start = time.seconds_since_startup();
+            delay.start = now;
         }
-        let duration = time.seconds_since_startup() - delay.start;
-        if duration > delay.duration {
+        let duration = now - delay.start;
+        if duration >= delay.duration {
             commands.entity(entity).insert(BehaviorSuccess);
         }
     }
@@ -0,0 +1,131 @@
+use crate::prelude::*;
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use simula_core::signal::SignalController;
+use std::time::Duration;
+
+/// Drive a `SignalController<f32>` toward a setpoint, succeeds once the process variable is
+/// within tolerance of the setpoint, fails on timeout. The controller is looked up on the
+/// tree entity, then on its ancestors, like the agent owning the tree.
+#[derive(Debug, Component, Reflect, Clone, Deserialize, Serialize, Inspectable)]
+pub struct DriveSignal {
+    pub setpoint: BehaviorProp<f64>,
+    /// Process variable, usually bound to a blackboard key the game keeps up to date
+    pub pv: BehaviorProp<f64>,
+    #[serde(default = "default_tolerance")]
+    pub tolerance: f64,
+    /// Seconds before failing, never fails when zero
+    #[serde(default)]
+    pub timeout: f64,
+    /// Blackboard key the controller output is written to, not written when empty
+    #[serde(default)]
+    pub output: String,
+    #[serde(default)]
+    pub start: f64,
+    #[serde(default)]
+    pub last: f64,
+}
+
+fn default_tolerance() -> f64 {
+    0.01
+}
+
+impl Default for DriveSignal {
+    fn default() -> Self {
+        Self {
+            setpoint: default(),
+            pv: default(),
+            tolerance: default_tolerance(),
+            timeout: 0.0,
+            output: String::new(),
+            start: 0.0,
+            last: 0.0,
+        }
+    }
+}
+
+impl BehaviorInfo for DriveSignal {
+    const TYPE: BehaviorType = BehaviorType::Action;
+    const NAME: &'static str = "DriveSignal";
+    const DESC: &'static str = "Drive a signal controller toward a setpoint";
+}
+
+/// First of the tree entity and its ancestors that passes `has`
+pub(crate) fn find_owner(
+    tree: Option<Entity>,
+    parents: &Query<&Parent>,
+    has: impl Fn(Entity) -> bool,
+) -> Option<Entity> {
+    std::iter::successors(tree, |entity| {
+        parents.get(*entity).ok().map(|parent| parent.get())
+    })
+    .find(|entity| has(*entity))
+}
+
+pub fn run(
+    time: BehaviorTime,
+    mut commands: Commands,
+    mut drives: Query<
+        (
+            Entity,
+            &mut DriveSignal,
+            &mut BehaviorRunning,
+            &BehaviorNode,
+        ),
+        BehaviorRunQuery,
+    >,
+    mut controllers: Query<&mut SignalController<f32>>,
+    mut blackboards: Query<&mut BehaviorBlackboard>,
+    parents: Query<&Parent>,
+) {
+    for (entity, mut drive, mut running, node) in &mut drives {
+        let now = time.elapsed(node);
+        let owner = find_owner(node.tree, &parents, |entity| controllers.contains(entity));
+        let mut controller = match owner.and_then(|owner| controllers.get_mut(owner).ok()) {
+            Some(controller) => controller,
+            None => {
+                warn!("No signal controller to drive");
+                commands.entity(entity).insert(BehaviorFailure);
+                continue;
+            }
+        };
+        let mut blackboard = node.tree.and_then(|tree| blackboards.get_mut(tree).ok());
+        let setpoint = drive.setpoint.get(blackboard.as_deref());
+        let pv = drive.pv.get(blackboard.as_deref());
+        let (setpoint, pv) = match (setpoint, pv) {
+            (Some(setpoint), Some(pv)) => (setpoint, pv),
+            _ => {
+                warn!("Missing setpoint or process variable to drive signal");
+                commands.entity(entity).insert(BehaviorFailure);
+                continue;
+            }
+        };
+
+        let error = setpoint - pv;
+        if !running.on_enter_handled {
+            running.on_enter_handled = true;
+            drive.start = now;
+            drive.last = now;
+            // Start from a clean state, without a kick from an older error
+            controller.reset();
+        }
+
+        let output = if error.abs() <= drive.tolerance {
+            commands.entity(entity).insert(BehaviorSuccess);
+            0.0
+        } else if drive.timeout > 0.0 && now - drive.start >= drive.timeout {
+            commands.entity(entity).insert(BehaviorFailure);
+            0.0
+        } else {
+            let dt = Duration::from_secs_f64((now - drive.last).max(0.0));
+            drive.last = now;
+            controller.control(setpoint as f32, pv as f32, dt)
+        };
+        // Once done, stop driving instead of leaving the last output in place
+        if !drive.output.is_empty() {
+            if let Some(blackboard) = blackboard.as_mut() {
+                blackboard.set(drive.output.clone(), output as f64);
+            }
+        }
+    }
+}
@@ -0,0 +1,64 @@
+use crate::{event::BehaviorEventInfo, prelude::*};
+use bevy::prelude::*;
+use bevy_inspector_egui::{egui, Context};
+use serde::{Deserialize, Serialize};
+
+/// Send an event created from the payload and the tree blackboard, and succeed. Fails if
+/// the event can't be created.
+#[derive(Debug, Clone, Component, Reflect, Deserialize, Serialize)]
+pub struct EmitEvent<E>
+where
+    E: BehaviorEventInfo,
+{
+    #[serde(default)]
+    pub payload: E::Payload,
+}
+
+impl<E> Default for EmitEvent<E>
+where
+    E: BehaviorEventInfo,
+{
+    fn default() -> Self {
+        Self { payload: default() }
+    }
+}
+
+impl<E> Inspectable for EmitEvent<E>
+where
+    E: BehaviorEventInfo,
+{
+    type Attributes = ();
+
+    fn ui(&mut self, ui: &mut egui::Ui, _options: Self::Attributes, context: &mut Context) -> bool {
+        self.payload.ui(ui, default(), context)
+    }
+}
+
+impl<E> BehaviorInfo for EmitEvent<E>
+where
+    E: BehaviorEventInfo,
+{
+    const TYPE: BehaviorType = BehaviorType::Action;
+    const NAME: &'static str = E::EMIT_NAME;
+    const DESC: &'static str = "Send an event created from the payload";
+}
+
+pub fn run<E>(
+    mut commands: Commands,
+    mut events: EventWriter<E>,
+    emits: Query<(Entity, &EmitEvent<E>, &BehaviorNode), BehaviorRunQuery>,
+    blackboards: Query<&BehaviorBlackboard>,
+) where
+    E: BehaviorEventInfo,
+{
+    for (entity, emit, node) in &emits {
+        let blackboard = node.tree.and_then(|tree| blackboards.get(tree).ok());
+        if let Some(event) = E::from_payload(&emit.payload, blackboard) {
+            events.send(event);
+            commands.entity(entity).insert(BehaviorSuccess);
+        } else {
+            warn!("Could not create event for {}", E::EMIT_NAME);
+            commands.entity(entity).insert(BehaviorFailure);
+        }
+    }
+}
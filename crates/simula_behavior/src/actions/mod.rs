@@ -1,5 +1,17 @@
+pub mod condition;
 pub mod debug;
 pub mod delay;
+pub mod drive_signal;
+pub mod emit_event;
+pub mod sample_signal;
+pub mod set_blackboard;
+pub mod wait_for_event;
 
+pub use condition::*;
 pub use debug::*;
 pub use delay::*;
+pub use drive_signal::*;
+pub use emit_event::*;
+pub use sample_signal::*;
+pub use set_blackboard::*;
+pub use wait_for_event::*;
@@ -0,0 +1,51 @@
+use super::drive_signal::find_owner;
+use crate::prelude::*;
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use simula_core::signal::SignalGenerator;
+use std::time::Duration;
+
+/// Write a sample of a `SignalGenerator` to the tree blackboard and succeed, at the tree
+/// time. The generator is looked up on the tree entity, then on its ancestors. Fails if
+/// there is no generator or no blackboard.
+#[derive(Debug, Default, Component, Reflect, Clone, Deserialize, Serialize, Inspectable)]
+pub struct SampleSignal {
+    pub key: String,
+}
+
+impl BehaviorInfo for SampleSignal {
+    const TYPE: BehaviorType = BehaviorType::Action;
+    const NAME: &'static str = "SampleSignal";
+    const DESC: &'static str = "Write a signal generator sample to the tree blackboard";
+}
+
+pub fn run(
+    time: BehaviorTime,
+    mut commands: Commands,
+    samplers: Query<(Entity, &SampleSignal, &BehaviorNode), BehaviorRunQuery>,
+    mut generators: Query<&mut SignalGenerator>,
+    mut blackboards: Query<&mut BehaviorBlackboard>,
+    parents: Query<&Parent>,
+) {
+    for (entity, sampler, node) in &samplers {
+        let owner = find_owner(node.tree, &parents, |entity| generators.contains(entity));
+        let generator = owner.and_then(|owner| generators.get_mut(owner).ok());
+        let blackboard = node.tree.and_then(|tree| blackboards.get_mut(tree).ok());
+        match (generator, blackboard) {
+            (Some(mut generator), Some(mut blackboard)) => {
+                let now = Duration::from_secs_f64(time.elapsed(node).max(0.0));
+                let sample = generator.sample(now);
+                blackboard.set(sampler.key.clone(), sample as f64);
+                commands.entity(entity).insert(BehaviorSuccess);
+            }
+            (None, _) => {
+                warn!("No signal generator to sample for key: {}", sampler.key);
+                commands.entity(entity).insert(BehaviorFailure);
+            }
+            (_, None) => {
+                warn!("No blackboard to write key: {}", sampler.key);
+                commands.entity(entity).insert(BehaviorFailure);
+            }
+        }
+    }
+}
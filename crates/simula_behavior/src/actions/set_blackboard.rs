@@ -0,0 +1,41 @@
+use crate::prelude::*;
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Write a value to the tree blackboard and succeed, fails if the tree has no blackboard.
+#[derive(Debug, Default, Component, Reflect, Clone, Deserialize, Serialize, Inspectable)]
+pub struct SetBlackboard {
+    pub key: String,
+    #[serde(default)]
+    pub value: BlackboardValue,
+}
+
+impl BehaviorInfo for SetBlackboard {
+    const TYPE: BehaviorType = BehaviorType::Action;
+    const NAME: &'static str = "SetBlackboard";
+    const DESC: &'static str = "Write a value to the tree blackboard";
+
+    fn tick_compact(&self, context: &mut BehaviorTickContext) -> Option<BehaviorTick> {
+        context
+            .blackboard
+            .set_reflect(self.key.clone(), self.value.to_reflect());
+        Some(BehaviorTick::Success)
+    }
+}
+
+pub fn run(
+    mut commands: Commands,
+    setters: Query<(Entity, &SetBlackboard, &BehaviorNode), BehaviorRunQuery>,
+    mut blackboards: Query<&mut BehaviorBlackboard>,
+) {
+    for (entity, setter, node) in &setters {
+        let blackboard = node.tree.and_then(|tree| blackboards.get_mut(tree).ok());
+        if let Some(mut blackboard) = blackboard {
+            blackboard.set_reflect(setter.key.clone(), setter.value.to_reflect());
+            commands.entity(entity).insert(BehaviorSuccess);
+        } else {
+            warn!("No blackboard to write key: {}", setter.key);
+            commands.entity(entity).insert(BehaviorFailure);
+        }
+    }
+}
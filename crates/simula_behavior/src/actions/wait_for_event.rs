@@ -0,0 +1,88 @@
+use crate::{event::BehaviorEventInfo, prelude::*};
+use bevy::{ecs::event::ManualEventReader, prelude::*, utils::HashMap};
+use bevy_inspector_egui::{egui, Context};
+use serde::{Deserialize, Serialize};
+
+/// Wait for an event that passes the filter, and succeed. Only events sent from the first
+/// step the node runs are seen, events sent before are missed.
+#[derive(Debug, Clone, Component, Reflect, Deserialize, Serialize)]
+pub struct WaitForEvent<E>
+where
+    E: BehaviorEventInfo,
+{
+    #[serde(default)]
+    pub filter: E::Filter,
+}
+
+impl<E> Default for WaitForEvent<E>
+where
+    E: BehaviorEventInfo,
+{
+    fn default() -> Self {
+        Self { filter: default() }
+    }
+}
+
+impl<E> Inspectable for WaitForEvent<E>
+where
+    E: BehaviorEventInfo,
+{
+    type Attributes = ();
+
+    fn ui(&mut self, ui: &mut egui::Ui, _options: Self::Attributes, context: &mut Context) -> bool {
+        self.filter.ui(ui, default(), context)
+    }
+}
+
+impl<E> BehaviorInfo for WaitForEvent<E>
+where
+    E: BehaviorEventInfo,
+{
+    const TYPE: BehaviorType = BehaviorType::Action;
+    const NAME: &'static str = E::WAIT_NAME;
+    const DESC: &'static str = "Wait for an event that passes the filter";
+}
+
+pub fn run<E>(
+    mut commands: Commands,
+    events: Res<Events<E>>,
+    mut readers: Local<HashMap<Entity, ManualEventReader<E>>>,
+    mut waits: Query<
+        (
+            Entity,
+            &WaitForEvent<E>,
+            &BehaviorNode,
+            &mut BehaviorRunning,
+        ),
+        BehaviorRunQuery,
+    >,
+    mut blackboards: Query<&mut BehaviorBlackboard>,
+) where
+    E: BehaviorEventInfo,
+{
+    // Every wait reads on its own from when it starts, so none sees events sent before
+    let mut waiting = HashMap::default();
+    for (entity, wait, node, mut running) in &mut waits {
+        let mut reader = match readers.remove(&entity) {
+            Some(reader) if running.on_enter_handled => reader,
+            _ => {
+                running.on_enter_handled = true;
+                events.get_reader_current()
+            }
+        };
+        let mut blackboard = node.tree.and_then(|tree| blackboards.get_mut(tree).ok());
+        let event = reader
+            .iter(&events)
+            .find(|event| event.matches(&wait.filter, blackboard.as_deref()));
+        if let Some(event) = event {
+            if let Some(blackboard) = blackboard.as_mut() {
+                event.store(&wait.filter, blackboard);
+            }
+            commands.entity(entity).insert(BehaviorSuccess);
+        } else {
+            waiting.insert(entity, reader);
+        }
+    }
+    // Readers of waits that are done or stopped go away
+    *readers = waiting;
+}
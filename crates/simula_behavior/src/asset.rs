@@ -1,12 +1,22 @@
-use crate::{BehaviorSpawner, BehaviorTree};
+use crate::{
+    blackboard::BlackboardValue, BehaviorBlackboard, BehaviorChildren, BehaviorCursor,
+    BehaviorFailure, BehaviorNode, BehaviorRunning, BehaviorSpawner, BehaviorStopped,
+    BehaviorSuccess, BehaviorTree, BehaviorType,
+};
 use bevy::{
-    asset::{AssetLoader, LoadContext, LoadedAsset},
+    asset::{AssetLoader, LoadContext, LoadState, LoadedAsset},
+    ecs::system::{Command, EntityCommands},
     prelude::*,
     reflect::TypeUuid,
-    utils::BoxedFuture,
+    utils::{BoxedFuture, HashMap},
 };
 use serde::{Deserialize, Serialize};
-use std::fmt::Debug;
+use std::{
+    any::{Any, TypeId},
+    collections::BTreeMap,
+    fmt::Debug,
+    sync::{Arc, RwLock},
+};
 
 #[derive(Default, Debug, Serialize, Deserialize)]
 pub struct BTNode<T: Default>(pub String, pub T, #[serde(default)] pub Vec<BTNode<T>>);
@@ -14,15 +24,222 @@ pub struct BTNode<T: Default>(pub String, pub T, #[serde(default)] pub Vec<BTNod
 #[derive(Default, Debug, Serialize, Deserialize)]
 pub struct BehaviorDocument<T: Default> {
     pub root: BTNode<T>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub blackboard: BTreeMap<String, BlackboardValue>,
+}
+
+impl<T> BehaviorDocument<T>
+where
+    T: Default + Serialize,
+{
+    /// Serialize to the text of a .bht.ron file
+    pub fn to_ron(&self) -> Result<String, ron::Error> {
+        ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default())
+    }
 }
 
+impl<T> BehaviorDocument<T>
+where
+    T: Default + BehaviorSpawner + for<'de> Deserialize<'de>,
+{
+    /// Parse and validate the text of a .bht.ron file, `path` is only used for errors
+    pub fn from_ron(path: &str, text: &str) -> Result<Self, BehaviorDocumentError> {
+        let document: Self =
+            ron::de::from_str(text).map_err(|err| BehaviorDocumentError::from_ron(path, err))?;
+        document.root.validate(path, None)?;
+        Ok(document)
+    }
+}
+
+impl<T> BTNode<T>
+where
+    T: Default + BehaviorSpawner,
+{
+    /// Check that the node and its children have a valid number of children
+    fn validate(&self, path: &str, parent: Option<&str>) -> Result<(), BehaviorDocumentError> {
+        let BTNode(name, node_type, nodes) = self;
+        let node = match parent {
+            Some(parent) => format!("{}/{}", parent, name),
+            None => name.clone(),
+        };
+        match node_type.behavior_type() {
+            Some(BehaviorType::Action) if !nodes.is_empty() => {
+                return Err(BehaviorDocumentError::ActionWithChildren {
+                    path: path.to_string(),
+                    node,
+                });
+            }
+            Some(BehaviorType::Decorator) if nodes.len() > 1 => {
+                return Err(BehaviorDocumentError::DecoratorChildren {
+                    path: path.to_string(),
+                    node,
+                    count: nodes.len(),
+                });
+            }
+            _ => {}
+        }
+        for child in nodes.iter() {
+            child.validate(path, Some(&node))?;
+        }
+        Ok(())
+    }
+}
+
+/// Why a behavior document could not be loaded
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+pub enum BehaviorDocumentError {
+    #[error("{path}:{line}:{col}: {message}")]
+    Parse {
+        path: String,
+        line: usize,
+        col: usize,
+        message: String,
+    },
+    #[error("{path}:{line}:{col}: unknown behavior `{found}`{}", did_you_mean(.suggestion))]
+    UnknownBehavior {
+        path: String,
+        line: usize,
+        col: usize,
+        found: String,
+        suggestion: Option<String>,
+    },
+    #[error("{path}: action \"{node}\" can't have children")]
+    ActionWithChildren { path: String, node: String },
+    #[error("{path}: decorator \"{node}\" has {count} children, it can only have one")]
+    DecoratorChildren {
+        path: String,
+        node: String,
+        count: usize,
+    },
+    #[error("{path}: failed to load")]
+    LoadFailed { path: String },
+    #[error("{path}: invalid UTF-8 at byte {offset}")]
+    Encoding { path: String, offset: usize },
+}
+
+fn did_you_mean(suggestion: &Option<String>) -> String {
+    suggestion
+        .as_ref()
+        .map(|suggestion| format!(", did you mean `{}`?", suggestion))
+        .unwrap_or_default()
+}
+
+impl BehaviorDocumentError {
+    fn from_ron(path: &str, err: ron::Error) -> Self {
+        let position = err.position;
+        let message = err.code.to_string();
+        // Serde reports unknown variants as "unknown variant `x`, expected one of `a`, `b`"
+        if let Some(rest) = message.strip_prefix("unknown variant `") {
+            if let Some((found, expected)) = rest.split_once('`') {
+                let suggestion = expected
+                    .split('`')
+                    .skip(1)
+                    .step_by(2)
+                    .map(|variant| (edit_distance(found, variant), variant))
+                    .filter(|(distance, _)| *distance <= usize::max(2, found.len() / 3))
+                    .min_by_key(|(distance, _)| *distance)
+                    .map(|(_, variant)| variant.to_string());
+                return Self::UnknownBehavior {
+                    path: path.to_string(),
+                    line: position.line,
+                    col: position.col,
+                    found: found.to_string(),
+                    suggestion,
+                };
+            }
+        }
+        Self::Parse {
+            path: path.to_string(),
+            line: position.line,
+            col: position.col,
+            message,
+        }
+    }
+}
+
+/// Levenshtein distance, to suggest a behavior name close to a misspelled one
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b = b.chars().collect::<Vec<char>>();
+    let mut row = (0..=b.len()).collect::<Vec<usize>>();
+    for (i, a) in a.chars().enumerate() {
+        let mut previous = row[0];
+        row[0] = i + 1;
+        for (j, b) in b.iter().enumerate() {
+            let current = row[j + 1];
+            row[j + 1] = if a == *b {
+                previous
+            } else {
+                1 + previous.min(row[j]).min(row[j + 1])
+            };
+            previous = current;
+        }
+    }
+    row[b.len()]
+}
+
+/// A .bht.ron file. Its document is parsed and validated the first time a behavior enum
+/// asks for it, and kept in the asset for the next trees spawned from it.
 #[derive(Default, Debug, TypeUuid, Deserialize)]
 #[uuid = "7f117190-5353-11ed-ae42-02a179e5df2b"]
 pub struct BehaviorAsset {
     pub path: String,
-    pub document: String,
+    pub text: String,
+    /// Set by the loader when the file could not be read as a document
+    #[serde(skip)]
+    pub error: Option<BehaviorDocumentError>,
+    /// Parsed documents, by behavior enum
+    #[serde(skip)]
+    documents: RwLock<HashMap<TypeId, Arc<dyn Any + Send + Sync>>>,
+}
+
+type ParsedDocument<T> = Result<Arc<BehaviorDocument<T>>, BehaviorDocumentError>;
+
+impl BehaviorAsset {
+    pub fn new(path: impl Into<String>, text: impl Into<String>) -> Self {
+        Self {
+            path: path.into(),
+            text: text.into(),
+            ..default()
+        }
+    }
+
+    /// The document for behavior enum `T`, parsed and validated only the first time
+    pub fn document<T>(&self) -> ParsedDocument<T>
+    where
+        T: BehaviorSpawner + Send + Sync + 'static + Default + for<'de> Deserialize<'de>,
+    {
+        if let Some(error) = &self.error {
+            return Err(error.clone());
+        }
+        let type_id = TypeId::of::<T>();
+        let parsed = self
+            .documents
+            .read()
+            .ok()
+            .and_then(|documents| documents.get(&type_id).cloned());
+        if let Some(document) = parsed
+            .as_ref()
+            .and_then(|parsed| parsed.downcast_ref::<ParsedDocument<T>>())
+        {
+            return document.clone();
+        }
+        let document = BehaviorDocument::<T>::from_ron(&self.path, &self.text).map(Arc::new);
+        if let Ok(mut documents) = self.documents.write() {
+            documents.insert(type_id, Arc::new(document.clone()));
+        }
+        document
+    }
 }
 
+/// Sent, and added to the root entity, when a behavior asset fails to load
+#[derive(Debug, Clone, Component)]
+pub struct BehaviorLoadFailed {
+    pub entity: Entity,
+    pub error: BehaviorDocumentError,
+}
+
+/// Loads .bht.ron files for all behavior enums, documents are checked against each enum
+/// when a tree of it is spawned
 #[derive(Default)]
 pub struct BehaviorAssetLoader;
 
@@ -33,9 +250,21 @@ impl AssetLoader for BehaviorAssetLoader {
         load_context: &'a mut LoadContext,
     ) -> BoxedFuture<'a, Result<(), bevy::asset::Error>> {
         Box::pin(async move {
-            let asset = BehaviorAsset {
-                path: load_context.path().display().to_string(),
-                document: std::str::from_utf8(bytes).unwrap_or_default().to_string(),
+            let path = load_context.path().display().to_string();
+            // Broken documents still load, so their error can be reported to their users
+            let asset = match std::str::from_utf8(bytes) {
+                Ok(text) => BehaviorAsset::new(path, text),
+                Err(err) => {
+                    let error = BehaviorDocumentError::Encoding {
+                        path: path.clone(),
+                        offset: err.valid_up_to(),
+                    };
+                    warn!("{}", error);
+                    BehaviorAsset {
+                        error: Some(error),
+                        ..BehaviorAsset::new(path, "")
+                    }
+                }
             };
             load_context.set_default_asset(LoadedAsset::new(asset));
             Ok(())
@@ -47,6 +276,18 @@ impl AssetLoader for BehaviorAssetLoader {
     }
 }
 
+/// How a tree spawned from an asset follows changes to that asset
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum BehaviorReload {
+    /// Keep the tree as it was spawned
+    Never,
+    /// Rebuild the tree, and restart it from its root if it was running
+    #[default]
+    Restart,
+    /// Rebuild the tree, keeping the blackboard and the state of nodes whose names match
+    Preserve,
+}
+
 #[derive(Component, Default)]
 pub struct BehaviorAssetLoading<T>
 where
@@ -54,13 +295,35 @@ where
 {
     pub document: Handle<BehaviorAsset>,
     pub parent: Option<Entity>,
+    pub reload: BehaviorReload,
+    pub phantom: std::marker::PhantomData<T>,
+}
+
+/// Added to the root of a tree spawned from an asset, to know where its nodes come from
+#[derive(Component, Clone)]
+pub struct BehaviorSource {
+    pub document: Handle<BehaviorAsset>,
+}
+
+/// Added to the root of a tree spawned from an asset, to rebuild it when the asset changes
+#[derive(Component)]
+pub struct BehaviorAssetLoaded<T>
+where
+    T: TypeUuid + Send + Sync + 'static + Default + Debug,
+{
+    pub document: Handle<BehaviorAsset>,
+    pub parent: Option<Entity>,
+    pub reload: BehaviorReload,
     pub phantom: std::marker::PhantomData<T>,
 }
 
 pub fn behavior_loader<T>(
     mut commands: Commands,
+    asset_server: Res<AssetServer>,
     loaded_assets: Res<Assets<BehaviorAsset>>,
     queued_assets: Query<(Entity, &BehaviorAssetLoading<T>)>,
+    mut trees: Query<(Entity, &BehaviorTree, Option<&mut BehaviorBlackboard>)>,
+    mut failures: EventWriter<BehaviorLoadFailed>,
 ) where
     T: BehaviorSpawner
         + TypeUuid
@@ -73,11 +336,287 @@ pub fn behavior_loader<T>(
 {
     for (entity, queued_asset) in queued_assets.iter() {
         if let Some(loaded_asset) = loaded_assets.get(&queued_asset.document) {
-            let BehaviorAsset { document, .. } = loaded_asset;
-            let document: BehaviorDocument<T> = ron::de::from_str(&document).unwrap();
-            let BehaviorDocument { root } = document;
-            BehaviorTree::insert_tree::<T>(entity, queued_asset.parent, &mut commands, &root);
-            commands.entity(entity).remove::<BehaviorAssetLoading<T>>();
+            let document = match loaded_asset.document::<T>() {
+                Ok(document) => document,
+                Err(error) => {
+                    warn!("{}", error);
+                    fail_loading::<T>(&mut commands, &mut failures, entity, error);
+                    continue;
+                }
+            };
+            BehaviorTree::insert_tree::<T>(
+                entity,
+                queued_asset.parent,
+                &mut commands,
+                &document.root,
+            );
+            // Seed the blackboard of the tree owning this root
+            for (tree_entity, tree, blackboard) in &mut trees {
+                if tree.root == Some(entity) {
+                    if let Some(mut blackboard) = blackboard {
+                        blackboard.merge_defaults(document.as_ref());
+                    } else {
+                        commands
+                            .entity(tree_entity)
+                            .insert(BehaviorBlackboard::from_document(document.as_ref()));
+                    }
+                }
+            }
+            commands
+                .entity(entity)
+                .remove::<BehaviorAssetLoading<T>>()
+                .insert(BehaviorSource {
+                    document: queued_asset.document.clone(),
+                });
+            if queued_asset.reload != BehaviorReload::Never {
+                commands.entity(entity).insert(BehaviorAssetLoaded::<T> {
+                    document: queued_asset.document.clone(),
+                    parent: queued_asset.parent,
+                    reload: queued_asset.reload,
+                    phantom: default(),
+                });
+            }
+        } else if asset_server.get_load_state(&queued_asset.document) == LoadState::Failed {
+            let path = asset_server
+                .get_handle_path(&queued_asset.document)
+                .map(|path| path.path().display().to_string())
+                .unwrap_or_default();
+            let error = BehaviorDocumentError::LoadFailed { path };
+            warn!("{}", error);
+            fail_loading::<T>(&mut commands, &mut failures, entity, error);
+        }
+    }
+}
+
+fn fail_loading<T>(
+    commands: &mut Commands,
+    failures: &mut EventWriter<BehaviorLoadFailed>,
+    entity: Entity,
+    error: BehaviorDocumentError,
+) where
+    T: TypeUuid + Send + Sync + 'static + Default + Debug,
+{
+    let failed = BehaviorLoadFailed { entity, error };
+    commands
+        .entity(entity)
+        .remove::<BehaviorAssetLoading<T>>()
+        .insert(failed.clone());
+    failures.send(failed);
+}
+
+/// Execution state of a node, kept across reloads
+#[derive(Default, Clone, Copy)]
+struct NodeState {
+    cursor: bool,
+    running: bool,
+    success: bool,
+    failure: bool,
+}
+
+impl NodeState {
+    fn insert(&self, commands: &mut EntityCommands) {
+        if self.cursor {
+            commands.insert(BehaviorCursor);
+        }
+        if self.running {
+            // Let running nodes enter again, their data comes from the new document
+            commands.insert(BehaviorRunning::default());
+        }
+        if self.success {
+            commands.insert(BehaviorSuccess);
+        }
+        if self.failure {
+            commands.insert(BehaviorFailure);
+        }
+    }
+}
+
+type NodeStateQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        &'static Name,
+        &'static BehaviorChildren,
+        Option<&'static BehaviorCursor>,
+        Option<&'static BehaviorRunning>,
+        Option<&'static BehaviorSuccess>,
+        Option<&'static BehaviorFailure>,
+    ),
+>;
+
+/// Rebuild trees spawned from an asset when the asset is modified
+pub fn behavior_reloader<T>(
+    mut commands: Commands,
+    mut events: EventReader<AssetEvent<BehaviorAsset>>,
+    loaded_assets: Res<Assets<BehaviorAsset>>,
+    roots: Query<(
+        Entity,
+        &BehaviorAssetLoaded<T>,
+        &BehaviorNode,
+        &BehaviorChildren,
+    )>,
+    nodes: NodeStateQuery,
+    mut trees: Query<(&BehaviorTree, &mut BehaviorBlackboard)>,
+    mut failures: EventWriter<BehaviorLoadFailed>,
+) where
+    T: BehaviorSpawner
+        + TypeUuid
+        + Send
+        + Sync
+        + 'static
+        + Default
+        + Debug
+        + for<'de> Deserialize<'de>,
+{
+    for event in events.iter() {
+        let handle = if let AssetEvent::Modified { handle } = event {
+            handle
+        } else {
+            continue;
+        };
+        if !roots
+            .iter()
+            .any(|(_, loaded, ..)| loaded.document == *handle)
+        {
+            continue;
+        }
+        let asset = if let Some(asset) = loaded_assets.get(handle) {
+            asset
+        } else {
+            continue;
+        };
+        let document = match asset.document::<T>() {
+            Ok(document) => document,
+            Err(error) => {
+                // Keep running the old trees
+                warn!("{}", error);
+                for (entity, loaded, ..) in &roots {
+                    if loaded.document == *handle {
+                        failures.send(BehaviorLoadFailed {
+                            entity,
+                            error: error.clone(),
+                        });
+                    }
+                }
+                continue;
+            }
+        };
+
+        for (entity, loaded, node, children) in &roots {
+            if loaded.document != *handle || loaded.reload == BehaviorReload::Never {
+                continue;
+            }
+            debug!("[{}] RELOADED {}", entity.id(), asset.path);
+
+            // Remember the state of the old nodes by their name path
+            let mut states = HashMap::default();
+            collect_states(entity, None, &nodes, &mut states);
+            let active = states.values().any(|state| state.cursor || state.running);
+
+            // Replace the old nodes, the root entity is kept so the tree still points to it
+            let mut root_commands = commands.entity(entity);
+            T::remove(&mut root_commands);
+            root_commands
+                .remove::<BehaviorCursor>()
+                .remove::<BehaviorRunning>()
+                .remove::<BehaviorSuccess>()
+                .remove::<BehaviorFailure>()
+                .remove::<BehaviorStopped>();
+            for child in children.iter() {
+                commands.entity(*child).despawn_recursive();
+            }
+            let mut paths: HashMap<Entity, String> = HashMap::default();
+            let mut inserted = vec![];
+            BehaviorTree::insert_tree_with::<T, _>(
+                entity,
+                loaded.parent,
+                &mut commands,
+                &document.root,
+                &mut |node_entity, node_parent, BTNode(name, ..)| {
+                    let path = match node_parent.and_then(|parent| paths.get(&parent)) {
+                        Some(parent_path) => format!("{}/{}", parent_path, name),
+                        None => name.clone(),
+                    };
+                    paths.insert(node_entity, path.clone());
+                    inserted.push((node_entity, path));
+                },
+            );
+            if let Some(tree) = node.tree {
+                commands.add(SetBehaviorTree {
+                    tree,
+                    nodes: inserted
+                        .iter()
+                        .map(|(node_entity, _)| *node_entity)
+                        .collect(),
+                });
+            }
+
+            // State can only be kept if the cursor is still somewhere in the tree
+            let preserve = loaded.reload == BehaviorReload::Preserve
+                && states.iter().all(|(path, state)| {
+                    !state.cursor || inserted.iter().any(|(_, inserted)| inserted == path)
+                });
+            if preserve {
+                for (node_entity, path) in inserted.iter() {
+                    if let Some(state) = states.get(path) {
+                        state.insert(&mut commands.entity(*node_entity));
+                    }
+                }
+            } else if active {
+                commands.entity(entity).insert(BehaviorCursor);
+            }
+
+            if let Some((tree, mut blackboard)) =
+                node.tree.and_then(|tree| trees.get_mut(tree).ok())
+            {
+                // A restarted tree starts over from the document values
+                if !preserve && tree.root == Some(entity) {
+                    blackboard.clear();
+                }
+                blackboard.merge_defaults(document.as_ref());
+            }
+        }
+    }
+}
+
+fn collect_states(
+    entity: Entity,
+    parent_path: Option<&str>,
+    nodes: &NodeStateQuery,
+    states: &mut HashMap<String, NodeState>,
+) {
+    if let Ok((name, children, cursor, running, success, failure)) = nodes.get(entity) {
+        let path = match parent_path {
+            Some(parent_path) => format!("{}/{}", parent_path, name),
+            None => name.to_string(),
+        };
+        for child in children.iter() {
+            collect_states(*child, Some(&path), nodes, states);
+        }
+        states.insert(
+            path,
+            NodeState {
+                cursor: cursor.is_some(),
+                running: running.is_some(),
+                success: success.is_some(),
+                failure: failure.is_some(),
+            },
+        );
+    }
+}
+
+/// Make rebuilt nodes part of the tree their old nodes belonged to
+pub(crate) struct SetBehaviorTree {
+    pub tree: Entity,
+    pub nodes: Vec<Entity>,
+}
+
+impl Command for SetBehaviorTree {
+    fn write(self, world: &mut World) {
+        for entity in self.nodes {
+            if let Some(mut node) = world.get_mut::<BehaviorNode>(entity) {
+                node.tree = Some(self.tree);
+            }
         }
     }
 }
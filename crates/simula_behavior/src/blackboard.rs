@@ -0,0 +1,251 @@
+use crate::asset::BehaviorDocument;
+use bevy::{prelude::*, utils::HashMap};
+use bevy_inspector_egui::{egui, Context, Inspectable};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// A value declared in a behavior document, used to seed a tree blackboard
+#[derive(Debug, Clone, PartialEq, Reflect, Serialize, Deserialize, Inspectable)]
+pub enum BlackboardValue {
+    Bool(bool),
+    Int(i64),
+    Float(f64),
+    String(String),
+}
+
+impl Default for BlackboardValue {
+    fn default() -> Self {
+        Self::Bool(false)
+    }
+}
+
+impl BlackboardValue {
+    pub fn to_reflect(&self) -> Box<dyn Reflect> {
+        match self {
+            BlackboardValue::Bool(value) => Box::new(*value),
+            BlackboardValue::Int(value) => Box::new(*value),
+            BlackboardValue::Float(value) => Box::new(*value),
+            BlackboardValue::String(value) => Box::new(value.clone()),
+        }
+    }
+
+    /// Convert back a reflected value, None if it has no document representation
+    pub fn try_from_reflect(value: &dyn Reflect) -> Option<Self> {
+        if let Some(value) = value.downcast_ref::<bool>() {
+            Some(BlackboardValue::Bool(*value))
+        } else if let Some(value) = value.downcast_ref::<i64>() {
+            Some(BlackboardValue::Int(*value))
+        } else if let Some(value) = value.downcast_ref::<f64>() {
+            Some(BlackboardValue::Float(*value))
+        } else {
+            value
+                .downcast_ref::<String>()
+                .map(|value| BlackboardValue::String(value.clone()))
+        }
+    }
+}
+
+/// Keyed values shared by all nodes of a behavior tree, lives on the tree entity
+#[derive(Debug, Default, Component)]
+pub struct BehaviorBlackboard {
+    entries: HashMap<String, Box<dyn Reflect>>,
+}
+
+impl BehaviorBlackboard {
+    /// Create a blackboard seeded with the defaults declared in a document
+    pub fn from_document<T>(document: &BehaviorDocument<T>) -> Self
+    where
+        T: Default,
+    {
+        let mut blackboard = Self::default();
+        blackboard.merge_defaults(document);
+        blackboard
+    }
+
+    /// Create a blackboard from values, like the ones returned by `to_values`
+    pub fn from_values(values: &BTreeMap<String, BlackboardValue>) -> Self {
+        Self {
+            entries: values
+                .iter()
+                .map(|(key, value)| (key.clone(), value.to_reflect()))
+                .collect(),
+        }
+    }
+
+    /// Add document defaults, keeping any entry that is already set
+    pub fn merge_defaults<T>(&mut self, document: &BehaviorDocument<T>)
+    where
+        T: Default,
+    {
+        for (key, value) in document.blackboard.iter() {
+            if !self.entries.contains_key(key) {
+                self.entries.insert(key.clone(), value.to_reflect());
+            }
+        }
+    }
+
+    /// Get a typed value, None if missing or of another type
+    pub fn get<T>(&self, key: &str) -> Option<&T>
+    where
+        T: Reflect,
+    {
+        self.entries.get(key)?.downcast_ref::<T>()
+    }
+
+    /// Get a typed mutable value, None if missing or of another type
+    pub fn get_mut<T>(&mut self, key: &str) -> Option<&mut T>
+    where
+        T: Reflect,
+    {
+        self.entries.get_mut(key)?.downcast_mut::<T>()
+    }
+
+    pub fn get_reflect(&self, key: &str) -> Option<&dyn Reflect> {
+        self.entries.get(key).map(|value| value.as_ref())
+    }
+
+    /// Set a value, replacing any previous value regardless of its type
+    pub fn set<T>(&mut self, key: impl Into<String>, value: T)
+    where
+        T: Reflect,
+    {
+        self.entries.insert(key.into(), Box::new(value));
+    }
+
+    pub fn set_reflect(&mut self, key: impl Into<String>, value: Box<dyn Reflect>) {
+        self.entries.insert(key.into(), value);
+    }
+
+    pub fn remove(&mut self, key: &str) -> Option<Box<dyn Reflect>> {
+        self.entries.remove(key)
+    }
+
+    pub fn contains(&self, key: &str) -> bool {
+        self.entries.contains_key(key)
+    }
+
+    pub fn keys(&self) -> impl Iterator<Item = &String> {
+        self.entries.keys()
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    /// Values that can be written to a document, others are skipped
+    pub fn to_values(&self) -> BTreeMap<String, BlackboardValue> {
+        self.entries
+            .iter()
+            .filter_map(|(key, value)| {
+                BlackboardValue::try_from_reflect(value.as_ref()).map(|value| (key.clone(), value))
+            })
+            .collect()
+    }
+}
+
+impl Inspectable for BehaviorBlackboard {
+    type Attributes = ();
+
+    fn ui(
+        &mut self,
+        ui: &mut egui::Ui,
+        _options: Self::Attributes,
+        _context: &mut Context,
+    ) -> bool {
+        let mut keys = self.entries.keys().cloned().collect::<Vec<_>>();
+        keys.sort();
+        egui::Grid::new("behavior_blackboard").show(ui, |ui| {
+            for key in keys {
+                if let Some(value) = self.entries.get(&key) {
+                    ui.label(&key);
+                    ui.label(format!("{:?}", value));
+                    ui.end_row();
+                }
+            }
+        });
+        false
+    }
+}
+
+/// A node field that is either a literal value or bound to a blackboard key
+#[derive(Debug, Clone, Reflect, Serialize, Deserialize)]
+pub enum BehaviorProp<T>
+where
+    T: Reflect + Clone,
+{
+    Value(T),
+    Blackboard(String),
+}
+
+impl<T> Default for BehaviorProp<T>
+where
+    T: Reflect + Clone + Default,
+{
+    fn default() -> Self {
+        Self::Value(T::default())
+    }
+}
+
+impl<T> BehaviorProp<T>
+where
+    T: Reflect + Clone,
+{
+    /// Resolve the literal value, or look up the bound key in the blackboard
+    pub fn get(&self, blackboard: Option<&BehaviorBlackboard>) -> Option<T> {
+        match self {
+            BehaviorProp::Value(value) => Some(value.clone()),
+            BehaviorProp::Blackboard(key) => blackboard?.get::<T>(key).cloned(),
+        }
+    }
+
+    /// Write a value to the bound key, literals are updated in place
+    pub fn set(&mut self, blackboard: Option<&mut BehaviorBlackboard>, value: T) {
+        match self {
+            BehaviorProp::Value(current) => *current = value,
+            BehaviorProp::Blackboard(key) => {
+                if let Some(blackboard) = blackboard {
+                    blackboard.set(key.clone(), value);
+                } else {
+                    warn!("No blackboard to write key: {}", key);
+                }
+            }
+        }
+    }
+}
+
+impl<T> Inspectable for BehaviorProp<T>
+where
+    T: Inspectable + Reflect + Clone + Default,
+{
+    type Attributes = T::Attributes;
+
+    fn ui(&mut self, ui: &mut egui::Ui, options: Self::Attributes, context: &mut Context) -> bool {
+        let mut changed = false;
+        ui.vertical(|ui| {
+            ui.horizontal(|ui| {
+                let is_value = matches!(self, BehaviorProp::Value(_));
+                if ui.selectable_label(is_value, "Value").clicked() && !is_value {
+                    *self = BehaviorProp::Value(T::default());
+                    changed = true;
+                }
+                if ui.selectable_label(!is_value, "Blackboard").clicked() && is_value {
+                    *self = BehaviorProp::Blackboard(String::new());
+                    changed = true;
+                }
+            });
+            match self {
+                BehaviorProp::Value(value) => changed |= value.ui(ui, options, context),
+                BehaviorProp::Blackboard(key) => changed |= key.ui(ui, default(), context),
+            }
+        });
+        changed
+    }
+}
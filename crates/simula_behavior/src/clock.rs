@@ -0,0 +1,64 @@
+use bevy::prelude::*;
+
+/// How a behavior clock advances every frame
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub enum BehaviorClockMode {
+    /// Follow `Time`, the wall clock
+    #[default]
+    Real,
+    /// Advance a fixed number of seconds every frame
+    Fixed(f64),
+    /// Only advance when told to, with `BehaviorClock::advance`
+    Manual,
+}
+
+/// Time seen by behavior nodes, in seconds. Nodes read it instead of `Time` so trees
+/// can run headless and deterministic, independent of the wall clock.
+#[derive(Debug, Default, Clone)]
+pub struct BehaviorClock {
+    pub mode: BehaviorClockMode,
+    elapsed: f64,
+}
+
+impl BehaviorClock {
+    pub fn real() -> Self {
+        Self::default()
+    }
+
+    pub fn fixed(step: f64) -> Self {
+        Self {
+            mode: BehaviorClockMode::Fixed(step),
+            ..default()
+        }
+    }
+
+    pub fn manual() -> Self {
+        Self {
+            mode: BehaviorClockMode::Manual,
+            ..default()
+        }
+    }
+
+    /// Seconds since the clock started
+    pub fn elapsed(&self) -> f64 {
+        self.elapsed
+    }
+
+    /// Move the clock forward, whatever its mode
+    pub fn advance(&mut self, seconds: f64) {
+        self.elapsed += seconds;
+    }
+}
+
+/// Advance the behavior clock, before behaviors run
+pub fn update_clock(time: Option<Res<Time>>, mut clock: ResMut<BehaviorClock>) {
+    match clock.mode {
+        BehaviorClockMode::Real => {
+            if let Some(time) = time {
+                clock.elapsed = time.seconds_since_startup();
+            }
+        }
+        BehaviorClockMode::Fixed(step) => clock.advance(step),
+        BehaviorClockMode::Manual => {}
+    }
+}
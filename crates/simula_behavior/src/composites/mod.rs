@@ -1,9 +1,19 @@
 pub mod all;
 pub mod any;
+pub mod parallel;
+pub mod reactive_selector;
+pub mod reactive_sequence;
 pub mod selector;
 pub mod sequencer;
+pub mod utility_selector;
+pub mod weighted_selector;
 
 pub use all::*;
 pub use any::*;
+pub use parallel::*;
+pub use reactive_selector::*;
+pub use reactive_sequence::*;
 pub use selector::*;
 pub use sequencer::*;
+pub use utility_selector::*;
+pub use weighted_selector::*;
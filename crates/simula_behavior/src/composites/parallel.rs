@@ -0,0 +1,141 @@
+use crate::prelude::*;
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// How many children of a parallel must complete the same way
+#[derive(Debug, Default, Reflect, Clone, Copy, PartialEq, Deserialize, Serialize, Inspectable)]
+pub enum Threshold {
+    RequireOne,
+    #[default]
+    RequireAll,
+    Count(u64),
+}
+
+impl Threshold {
+    /// Number of children required, out of `children`
+    pub fn required(&self, children: usize) -> usize {
+        match self {
+            Threshold::RequireOne => 1,
+            Threshold::RequireAll => children,
+            Threshold::Count(count) => (*count as usize).clamp(1, children),
+        }
+    }
+}
+
+/// Parallel will run all of its children in parallel, until enough of them succeed or
+/// fail. Children still running when it completes are stopped.
+#[derive(Debug, Component, Reflect, Clone, Deserialize, Serialize, Inspectable)]
+pub struct Parallel {
+    #[serde(default)]
+    pub success_threshold: Threshold,
+    #[serde(default = "default_failure_threshold")]
+    pub failure_threshold: Threshold,
+}
+
+fn default_failure_threshold() -> Threshold {
+    Threshold::RequireOne
+}
+
+impl Default for Parallel {
+    fn default() -> Self {
+        Self {
+            success_threshold: Threshold::RequireAll,
+            failure_threshold: default_failure_threshold(),
+        }
+    }
+}
+
+impl BehaviorInfo for Parallel {
+    const TYPE: BehaviorType = BehaviorType::Composite;
+    const NAME: &'static str = "Parallel";
+    const DESC: &'static str = "Run children in parallel until enough succeed or fail";
+    const FLOW: BehaviorFlow = BehaviorFlow {
+        aborts: true,
+        ..BehaviorFlow::NONE
+    };
+}
+
+pub fn run(
+    mut commands: Commands,
+    parallels: Query<(Entity, &Parallel, &BehaviorChildren), BehaviorRunQuery>,
+    nodes: Query<BehaviorChildQuery, With<BehaviorNode>>,
+) {
+    for (entity, parallel, children) in &parallels {
+        if children.is_empty() {
+            commands.entity(entity).insert(BehaviorSuccess);
+            continue;
+        }
+        let mut successes = 0;
+        let mut failures = 0;
+        let mut running = vec![];
+        let mut ready = vec![];
+        let mut valid = true;
+        for BehaviorChildQueryItem {
+            child_entity,
+            child_parent,
+            child_failure,
+            child_success,
+            child_running,
+        } in nodes.iter_many(children.iter())
+        {
+            if let Some(child_parent) = **child_parent {
+                if entity == child_parent {
+                    if child_failure.is_some() {
+                        failures += 1;
+                    } else if child_success.is_some() {
+                        successes += 1;
+                    } else if child_running.is_some() {
+                        running.push(child_entity);
+                    } else {
+                        ready.push(child_entity);
+                    }
+                } else {
+                    // Child is not ours, so we fail
+                    warn!("Child is not ours");
+                    valid = false;
+                    break;
+                }
+            } else {
+                // Child has no parent, so we fail
+                warn!("Child has no parent");
+                valid = false;
+                break;
+            }
+        }
+
+        let success_required = parallel.success_threshold.required(children.len());
+        let failure_required = parallel.failure_threshold.required(children.len());
+        let pending = running.len() + ready.len();
+        let result = if !valid {
+            Some(false)
+        } else if successes >= success_required {
+            Some(true)
+        } else if failures >= failure_required || successes + pending < success_required {
+            // Enough children failed, or too few are left to succeed
+            Some(false)
+        } else {
+            None
+        };
+
+        match result {
+            Some(success) => {
+                // Policy resolved, stop children still running
+                for child in running {
+                    commands.entity(child).insert(BehaviorStopped);
+                }
+                if success {
+                    commands.entity(entity).insert(BehaviorSuccess);
+                } else {
+                    commands.entity(entity).insert(BehaviorFailure);
+                }
+            }
+            None => {
+                // Pass on cursor to all ready children, wait for running ones
+                commands.entity(entity).remove::<BehaviorCursor>();
+                for child in ready {
+                    commands.entity(child).insert(BehaviorCursor);
+                }
+            }
+        }
+    }
+}
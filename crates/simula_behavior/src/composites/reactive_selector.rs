@@ -0,0 +1,118 @@
+use crate::prelude::*;
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// A reactive selector visits its children in order like a `Selector`, but while a child
+/// runs it keeps checking the `Condition` children that failed before it, every frame. If
+/// one of them passes, the running child is stopped and the selector succeeds.
+/// Other children are not run again, so checks to react to go in `Condition` nodes.
+#[derive(Debug, Default, Component, Reflect, Clone, Deserialize, Serialize, Inspectable)]
+pub struct ReactiveSelector;
+
+impl BehaviorInfo for ReactiveSelector {
+    const TYPE: BehaviorType = BehaviorType::Composite;
+    const NAME: &'static str = "ReactiveSelector";
+    const DESC: &'static str = "Selector that checks earlier conditions again";
+    const FLOW: BehaviorFlow = BehaviorFlow {
+        selects: true,
+        reactive: true,
+        aborts: true,
+        ..BehaviorFlow::NONE
+    };
+}
+
+pub fn run(
+    mut commands: Commands,
+    selectors: Query<
+        (
+            Entity,
+            &BehaviorChildren,
+            &BehaviorNode,
+            Option<&BehaviorCursor>,
+        ),
+        (
+            With<ReactiveSelector>,
+            With<BehaviorRunning>,
+            Without<BehaviorSuccess>,
+            Without<BehaviorFailure>,
+            Without<BehaviorStopped>,
+            Without<BehaviorPaused>,
+        ),
+    >,
+    nodes: Query<BehaviorChildQuery, With<BehaviorNode>>,
+    conditions: Query<&Condition>,
+    blackboards: Query<&BehaviorBlackboard>,
+) {
+    for (entity, children, node, cursor) in &selectors {
+        if children.is_empty() {
+            commands.entity(entity).insert(BehaviorSuccess);
+            continue;
+        }
+
+        if cursor.is_none() {
+            // A child is running, check the conditions that failed before it
+            let blackboard = node.tree.and_then(|tree| blackboards.get(tree).ok());
+            let mut running = None;
+            let mut changed = false;
+            for BehaviorChildQueryItem {
+                child_entity,
+                child_parent: _,
+                child_failure,
+                child_success,
+                child_running,
+            } in nodes.iter_many(children.iter())
+            {
+                if child_failure.is_none() {
+                    if child_running.is_some() && child_success.is_none() {
+                        running = Some(child_entity);
+                    }
+                    break;
+                }
+                if let Ok(condition) = conditions.get(child_entity) {
+                    if condition.test.check_key(blackboard, &condition.key) {
+                        changed = true;
+                    }
+                }
+            }
+            if let (Some(running), true) = (running, changed) {
+                // An earlier condition passes now, stop the running child and succeed
+                commands.entity(running).insert(BehaviorStopped);
+                commands.entity(entity).insert(BehaviorCursor);
+                commands.entity(entity).insert(BehaviorSuccess);
+            }
+            continue;
+        }
+
+        let mut should_fail = true;
+        for BehaviorChildQueryItem {
+            child_entity,
+            child_parent,
+            child_failure,
+            child_success,
+            child_running: _,
+        } in nodes.iter_many(children.iter())
+        {
+            if **child_parent != Some(entity) {
+                // Child is not ours, so we fail
+                warn!("Child is not ours");
+                break;
+            }
+            if child_success.is_some() {
+                // Child succeeded, so we succeed
+                commands.entity(entity).insert(BehaviorSuccess);
+                should_fail = false;
+                break;
+            } else if child_failure.is_none() {
+                // Child is ready, pass on cursor
+                commands.entity(entity).remove::<BehaviorCursor>();
+                commands.entity(child_entity).insert(BehaviorCursor);
+                should_fail = false;
+                break;
+            }
+        }
+        // If all children failed, complete with failure
+        if should_fail {
+            commands.entity(entity).insert(BehaviorFailure);
+        }
+    }
+}
@@ -0,0 +1,120 @@
+use crate::prelude::*;
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// A reactive sequence visits its children in order like a `Sequencer`, but while a child
+/// runs it keeps checking the `Condition` children before it, every frame. If one of them
+/// no longer passes, the running child is stopped and the sequence fails.
+/// Other children are not run again, so checks to react to go in `Condition` nodes.
+#[derive(Debug, Default, Component, Reflect, Clone, Deserialize, Serialize, Inspectable)]
+pub struct ReactiveSequence;
+
+impl BehaviorInfo for ReactiveSequence {
+    const TYPE: BehaviorType = BehaviorType::Composite;
+    const NAME: &'static str = "ReactiveSequence";
+    const DESC: &'static str = "Sequence that checks earlier conditions again";
+    const FLOW: BehaviorFlow = BehaviorFlow {
+        sequences: true,
+        reactive: true,
+        aborts: true,
+        ..BehaviorFlow::NONE
+    };
+}
+
+pub fn run(
+    mut commands: Commands,
+    sequences: Query<
+        (
+            Entity,
+            &BehaviorChildren,
+            &BehaviorNode,
+            Option<&BehaviorCursor>,
+        ),
+        (
+            With<ReactiveSequence>,
+            With<BehaviorRunning>,
+            Without<BehaviorSuccess>,
+            Without<BehaviorFailure>,
+            Without<BehaviorStopped>,
+            Without<BehaviorPaused>,
+        ),
+    >,
+    nodes: Query<BehaviorChildQuery, With<BehaviorNode>>,
+    conditions: Query<&Condition>,
+    blackboards: Query<&BehaviorBlackboard>,
+) {
+    for (entity, children, node, cursor) in &sequences {
+        if children.is_empty() {
+            commands.entity(entity).insert(BehaviorSuccess);
+            continue;
+        }
+
+        if cursor.is_none() {
+            // A child is running, check the conditions that passed before it
+            let blackboard = node.tree.and_then(|tree| blackboards.get(tree).ok());
+            let mut running = None;
+            let mut changed = false;
+            for BehaviorChildQueryItem {
+                child_entity,
+                child_parent: _,
+                child_failure,
+                child_success,
+                child_running,
+            } in nodes.iter_many(children.iter())
+            {
+                if child_success.is_none() {
+                    if child_running.is_some() && child_failure.is_none() {
+                        running = Some(child_entity);
+                    }
+                    break;
+                }
+                if let Ok(condition) = conditions.get(child_entity) {
+                    if !condition.test.check_key(blackboard, &condition.key) {
+                        changed = true;
+                    }
+                }
+            }
+            if let (Some(running), true) = (running, changed) {
+                // An earlier condition no longer passes, stop the running child and fail
+                commands.entity(running).insert(BehaviorStopped);
+                commands.entity(entity).insert(BehaviorCursor);
+                commands.entity(entity).insert(BehaviorFailure);
+            }
+            continue;
+        }
+
+        let mut should_succeed = true;
+        for BehaviorChildQueryItem {
+            child_entity,
+            child_parent,
+            child_failure,
+            child_success,
+            child_running: _,
+        } in nodes.iter_many(children.iter())
+        {
+            if **child_parent != Some(entity) {
+                // Child is not ours, so we fail
+                warn!("Child is not ours");
+                commands.entity(entity).insert(BehaviorFailure);
+                should_succeed = false;
+                break;
+            }
+            if child_failure.is_some() {
+                // Child failed, so we fail
+                commands.entity(entity).insert(BehaviorFailure);
+                should_succeed = false;
+                break;
+            } else if child_success.is_none() {
+                // Child is ready, pass on cursor
+                commands.entity(entity).remove::<BehaviorCursor>();
+                commands.entity(child_entity).insert(BehaviorCursor);
+                should_succeed = false;
+                break;
+            }
+        }
+        // If all children succeed, complete with success
+        if should_succeed {
+            commands.entity(entity).insert(BehaviorSuccess);
+        }
+    }
+}
@@ -28,6 +28,29 @@ impl BehaviorInfo for Selector {
     const TYPE: BehaviorType = BehaviorType::Composite;
     const NAME: &'static str = "Selector";
     const DESC: &'static str = "Selector behavior node";
+    const FLOW: BehaviorFlow = BehaviorFlow {
+        selects: true,
+        ..BehaviorFlow::NONE
+    };
+
+    fn tick_compact(&self, context: &mut BehaviorTickContext) -> Option<BehaviorTick> {
+        // The seed of a random run is kept in the node count
+        if context.entered {
+            context.local.count = rand::random();
+        }
+        let mut order = (0..context.children()).collect::<Vec<_>>();
+        if self.random {
+            order.shuffle(&mut StdRng::seed_from_u64(context.local.count));
+        }
+        for position in order {
+            match context.child(position) {
+                BehaviorNodeState::Failure => {}
+                BehaviorNodeState::Success => return Some(BehaviorTick::Success),
+                _ => return Some(BehaviorTick::Child(position)),
+            }
+        }
+        Some(BehaviorTick::Failure)
+    }
 }
 
 pub fn run(
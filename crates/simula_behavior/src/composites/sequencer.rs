@@ -28,6 +28,29 @@ impl BehaviorInfo for Sequencer {
     const TYPE: BehaviorType = BehaviorType::Composite;
     const NAME: &'static str = "Sequencer";
     const DESC: &'static str = "Sequencer behavior node";
+    const FLOW: BehaviorFlow = BehaviorFlow {
+        sequences: true,
+        ..BehaviorFlow::NONE
+    };
+
+    fn tick_compact(&self, context: &mut BehaviorTickContext) -> Option<BehaviorTick> {
+        // The seed of a random run is kept in the node count
+        if context.entered {
+            context.local.count = rand::random();
+        }
+        let mut order = (0..context.children()).collect::<Vec<_>>();
+        if self.random {
+            order.shuffle(&mut StdRng::seed_from_u64(context.local.count));
+        }
+        for position in order {
+            match context.child(position) {
+                BehaviorNodeState::Success => {}
+                BehaviorNodeState::Failure => return Some(BehaviorTick::Failure),
+                _ => return Some(BehaviorTick::Child(position)),
+            }
+        }
+        Some(BehaviorTick::Success)
+    }
 }
 
 pub fn run(
@@ -0,0 +1,216 @@
+use crate::{actions::condition::as_number, prelude::*};
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use simula_core::{ease::EaseFunction, map_range::map_range_eased};
+
+/// A score set on a child node by game systems, read by the `Component` scorer
+#[derive(Debug, Default, Clone, Copy, Component, Reflect, Inspectable)]
+#[reflect(Component)]
+pub struct UtilityScore(pub f32);
+
+/// Maps a blackboard value to a score, through an ease function
+#[derive(Debug, Default, Reflect, Clone, Deserialize, Serialize, Inspectable)]
+pub struct ScoreCurve {
+    pub key: String,
+    /// Range of the blackboard value
+    pub from: Vec2,
+    /// Range of the resulting score
+    pub to: Vec2,
+    #[serde(default)]
+    pub ease: EaseFunction,
+}
+
+/// How the utility of a child is scored
+#[derive(Debug, Default, Reflect, Clone, Deserialize, Serialize, Inspectable)]
+pub enum Scorer {
+    /// The `UtilityScore` component of the child, zero if missing
+    #[default]
+    Component,
+    /// A number in the blackboard, zero if missing
+    Blackboard(String),
+    /// A number in the blackboard mapped through a curve
+    Curve(ScoreCurve),
+    Fixed(f32),
+}
+
+impl Scorer {
+    pub fn score(
+        &self,
+        child: Entity,
+        blackboard: Option<&BehaviorBlackboard>,
+        scores: &Query<&UtilityScore>,
+    ) -> f32 {
+        match self {
+            Scorer::Component => scores.get(child).map_or(0.0, |score| score.0),
+            Scorer::Blackboard(key) => blackboard_number(blackboard, key).unwrap_or(0.0),
+            Scorer::Curve(curve) => {
+                let value = blackboard_number(blackboard, &curve.key).unwrap_or(curve.from.x);
+                map_range_eased(
+                    value,
+                    (curve.from.x, curve.from.y),
+                    (curve.to.x, curve.to.y),
+                    curve.ease,
+                )
+            }
+            Scorer::Fixed(score) => *score,
+        }
+    }
+}
+
+fn blackboard_number(blackboard: Option<&BehaviorBlackboard>, key: &str) -> Option<f32> {
+    as_number(blackboard?.get_reflect(key)).map(|value| value as f32)
+}
+
+/// A utility selector runs its highest scoring child. If the child fails, the next best
+/// child that has not failed yet is tried, and it succeeds as soon as one child does.
+/// While a child runs, scores can be re-evaluated to switch to a better child, once it
+/// beats the running child by more than the hysteresis.
+#[derive(Debug, Default, Component, Reflect, Clone, Deserialize, Serialize, Inspectable)]
+pub struct UtilitySelector {
+    /// Scorer of each child, in order, children without one use `Component`
+    #[serde(default)]
+    pub scorers: Vec<Scorer>,
+    /// Seconds between re-evaluations while a child runs, zero to never re-evaluate
+    #[serde(default)]
+    pub interval: f64,
+    #[serde(default)]
+    pub hysteresis: f32,
+    #[serde(default)]
+    pub evaluated: f64,
+}
+
+impl BehaviorInfo for UtilitySelector {
+    const TYPE: BehaviorType = BehaviorType::Composite;
+    const NAME: &'static str = "UtilitySelector";
+    const DESC: &'static str = "Run the child with the highest score";
+    const FLOW: BehaviorFlow = BehaviorFlow {
+        aborts: true,
+        ..BehaviorFlow::NONE
+    };
+}
+
+impl UtilitySelector {
+    fn scores(
+        &self,
+        children: &BehaviorChildren,
+        blackboard: Option<&BehaviorBlackboard>,
+        scores: &Query<&UtilityScore>,
+    ) -> Vec<f32> {
+        children
+            .iter()
+            .enumerate()
+            .map(|(index, child)| {
+                self.scorers
+                    .get(index)
+                    .cloned()
+                    .unwrap_or_default()
+                    .score(*child, blackboard, scores)
+            })
+            .collect()
+    }
+}
+
+/// Index of the highest score among candidates, the first one on ties
+fn best(scores: &[f32], candidates: &[usize]) -> Option<usize> {
+    candidates
+        .iter()
+        .copied()
+        .fold(None, |best, index| match best {
+            Some(best) if scores[best] >= scores[index] => Some(best),
+            _ => Some(index),
+        })
+}
+
+pub fn run(
+    time: BehaviorTime,
+    mut commands: Commands,
+    mut selectors: Query<
+        (
+            Entity,
+            &mut UtilitySelector,
+            &BehaviorNode,
+            &BehaviorChildren,
+            Option<&BehaviorCursor>,
+        ),
+        (
+            With<BehaviorRunning>,
+            Without<BehaviorSuccess>,
+            Without<BehaviorFailure>,
+            Without<BehaviorStopped>,
+            Without<BehaviorPaused>,
+        ),
+    >,
+    nodes: Query<BehaviorChildQuery, With<BehaviorNode>>,
+    utility_scores: Query<&UtilityScore>,
+    blackboards: Query<&BehaviorBlackboard>,
+) {
+    for (entity, mut selector, node, children, cursor) in &mut selectors {
+        if children.is_empty() {
+            commands.entity(entity).insert(BehaviorSuccess);
+            continue;
+        }
+        let blackboard = node.tree.and_then(|tree| blackboards.get(tree).ok());
+
+        let mut candidates = vec![];
+        let mut running = None;
+        let mut succeeded = false;
+        let mut valid = true;
+        for (index, child) in children.iter().enumerate() {
+            if let Ok(BehaviorChildQueryItem {
+                child_entity: _,
+                child_parent,
+                child_failure,
+                child_success,
+                child_running,
+            }) = nodes.get(*child)
+            {
+                if **child_parent != Some(entity) {
+                    // Child is not ours, so we fail
+                    warn!("Child is not ours");
+                    valid = false;
+                    break;
+                }
+                if child_success.is_some() {
+                    succeeded = true;
+                } else if child_running.is_some() && child_failure.is_none() {
+                    running = Some(index);
+                } else if child_failure.is_none() {
+                    candidates.push(index);
+                }
+            }
+        }
+
+        if cursor.is_some() {
+            if !valid {
+                commands.entity(entity).insert(BehaviorFailure);
+            } else if succeeded {
+                // Child succeeded, so we succeed
+                commands.entity(entity).insert(BehaviorSuccess);
+            } else if running.is_none() {
+                // Pass on cursor to the best child that hasn't failed, or fail
+                let scores = selector.scores(children, blackboard, &utility_scores);
+                if let Some(index) = best(&scores, &candidates) {
+                    selector.evaluated = time.elapsed(node);
+                    commands.entity(entity).remove::<BehaviorCursor>();
+                    commands.entity(children[index]).insert(BehaviorCursor);
+                } else {
+                    commands.entity(entity).insert(BehaviorFailure);
+                }
+            }
+        } else if let Some(current) = running {
+            // Re-evaluate while our child runs, and switch to a better child
+            let now = time.elapsed(node);
+            if selector.interval <= 0.0 || now - selector.evaluated < selector.interval {
+                continue;
+            }
+            selector.evaluated = now;
+            let scores = selector.scores(children, blackboard, &utility_scores);
+            if let Some(index) = best(&scores, &candidates) {
+                if scores[index] > scores[current] + selector.hysteresis {
+                    commands.entity(children[current]).insert(BehaviorStopped);
+                    commands.entity(entity).insert(BehaviorCursor);
+                }
+            }
+        }
+    }
+}
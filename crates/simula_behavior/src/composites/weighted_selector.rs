@@ -0,0 +1,164 @@
+use crate::prelude::*;
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use simula_core::prng::Prng;
+
+/// A weighted selector tries its children in a random order, where children with a higher
+/// weight are more likely to come first. It succeeds as soon as one child does, and fails
+/// if all children fail. The order comes from `seed`, so runs are reproducible.
+#[derive(Debug, Component, Reflect, Clone, Deserialize, Serialize, Inspectable)]
+pub struct WeightedSelector {
+    /// Weight of each child, in order. Children past the end of `weights` weigh 1, children
+    /// weighted 0 are tried last.
+    #[serde(default)]
+    pub weights: Vec<f32>,
+    /// Seed of the order, documents without one start from 0
+    #[serde(default)]
+    pub seed: u64,
+    /// Pick a new seed from the current one every run, otherwise every run tries children
+    /// in the same order
+    #[serde(default)]
+    pub reseed: bool,
+    /// Don't try a child again until all children have been tried
+    #[serde(default)]
+    pub shuffle_bag: bool,
+    /// Children left in the bag, by index
+    #[serde(default)]
+    pub bag: Vec<usize>,
+    /// Order of children in the current run, by index
+    #[serde(default)]
+    pub order: Vec<usize>,
+}
+
+impl Default for WeightedSelector {
+    fn default() -> Self {
+        Self {
+            weights: vec![],
+            seed: rand::random(),
+            reseed: false,
+            shuffle_bag: false,
+            bag: vec![],
+            order: vec![],
+        }
+    }
+}
+
+impl BehaviorInfo for WeightedSelector {
+    const TYPE: BehaviorType = BehaviorType::Composite;
+    const NAME: &'static str = "WeightedSelector";
+    const DESC: &'static str = "Try children in a random order, weighted per child";
+}
+
+impl WeightedSelector {
+    pub fn weight(&self, index: usize) -> f32 {
+        self.weights.get(index).copied().unwrap_or(1.0).max(0.0)
+    }
+
+    /// Order of children for a run, children still in the bag come first
+    pub fn shuffle(&mut self, children: usize) -> Vec<usize> {
+        let mut prng = Prng::new(self.seed);
+        if self.reseed {
+            self.seed = (prng.rand_u32() as u64) << 32 | prng.rand_u32() as u64;
+        }
+        self.bag.retain(|index| *index < children);
+        if !self.shuffle_bag || self.bag.is_empty() {
+            self.bag = (0..children).collect();
+        }
+        let rest = (0..children)
+            .filter(|index| !self.bag.contains(index))
+            .collect::<Vec<_>>();
+        let mut order = self.weighted_order(&mut prng, self.bag.clone());
+        order.extend(self.weighted_order(&mut prng, rest));
+        order
+    }
+
+    /// Draw all candidates without replacement, in proportion to their weights
+    fn weighted_order(&self, prng: &mut Prng, mut candidates: Vec<usize>) -> Vec<usize> {
+        let mut order = Vec::with_capacity(candidates.len());
+        while !candidates.is_empty() {
+            let total = candidates
+                .iter()
+                .map(|index| self.weight(*index))
+                .sum::<f32>();
+            if total <= 0.0 {
+                // Only children with no weight are left, in their order
+                order.append(&mut candidates);
+                break;
+            }
+            let mut pick = prng.rand_float() * total;
+            let mut position = candidates.len() - 1;
+            for (candidate, index) in candidates.iter().enumerate() {
+                let weight = self.weight(*index);
+                if weight > 0.0 && pick < weight {
+                    position = candidate;
+                    break;
+                }
+                pick -= weight;
+            }
+            order.push(candidates.remove(position));
+        }
+        order
+    }
+}
+
+pub fn run(
+    mut commands: Commands,
+    mut selectors: Query<
+        (
+            Entity,
+            &BehaviorChildren,
+            &mut WeightedSelector,
+            &mut BehaviorRunning,
+        ),
+        (With<WeightedSelector>, BehaviorRunQuery),
+    >,
+    nodes: Query<BehaviorChildQuery, BehaviorChildQueryFilter>,
+) {
+    for (entity, children, mut selector, mut running) in &mut selectors {
+        if children.is_empty() {
+            commands.entity(entity).insert(BehaviorSuccess);
+            continue;
+        }
+        if !running.on_enter_handled || selector.order.len() != children.len() {
+            running.on_enter_handled = true;
+            selector.order = selector.shuffle(children.len());
+        }
+
+        let mut should_fail = true;
+        for index in selector.order.clone() {
+            if let Ok(BehaviorChildQueryItem {
+                child_entity,
+                child_parent,
+                child_failure,
+                child_success,
+                child_running: _,
+            }) = nodes.get(children[index])
+            {
+                if **child_parent != Some(entity) {
+                    // Child is not ours, so we fail
+                    warn!("Child is not ours");
+                    break;
+                }
+                if child_failure.is_some() {
+                    // Child failed, so we move to next child
+                } else if child_success.is_some() {
+                    // Child succeeded, so we succeed
+                    commands.entity(entity).insert(BehaviorSuccess);
+                    should_fail = false;
+                    break;
+                } else {
+                    // Child is ready, take it out of the bag and pass on cursor
+                    selector.bag.retain(|bagged| *bagged != index);
+                    commands.entity(entity).remove::<BehaviorCursor>();
+                    commands.entity(child_entity).insert(BehaviorCursor);
+                    should_fail = false;
+                    break;
+                }
+            }
+        }
+        // If all children failed, complete with failure
+        if should_fail {
+            commands.entity(entity).insert(BehaviorFailure);
+        }
+    }
+}
@@ -0,0 +1,170 @@
+use crate::{
+    clock::BehaviorClock, net::BehaviorProxyNode, BehaviorCursor, BehaviorFailure, BehaviorNode,
+    BehaviorRunning, BehaviorStopped, BehaviorSuccess, BehaviorTree,
+};
+use bevy::{ecs::system::SystemParam, prelude::*, utils::HashMap};
+
+/// Pause, resume, step and restart a behavior tree, lives on the tree entity. While
+/// paused, nodes of the tree don't run and their clock time stands still.
+#[derive(Debug, Default, Clone, Component, Reflect)]
+#[reflect(Component)]
+pub struct BehaviorTreeControl {
+    paused: bool,
+    /// Frames to run while paused
+    steps: usize,
+    restart: bool,
+    /// Seconds the tree spent paused, hidden from its nodes
+    paused_time: f64,
+    /// Clock time of the last update
+    last: Option<f64>,
+}
+
+impl BehaviorTreeControl {
+    pub fn paused() -> Self {
+        Self {
+            paused: true,
+            ..default()
+        }
+    }
+
+    pub fn pause(&mut self) {
+        self.paused = true;
+        self.steps = 0;
+    }
+
+    pub fn resume(&mut self) {
+        self.paused = false;
+        self.steps = 0;
+    }
+
+    /// Run the tree for one frame, pausing it if it was running
+    pub fn step(&mut self) {
+        self.paused = true;
+        self.steps += 1;
+    }
+
+    /// Stop the tree, then start it over from its root
+    pub fn restart(&mut self) {
+        self.restart = true;
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    pub fn is_restarting(&self) -> bool {
+        self.restart
+    }
+
+    /// Seconds the tree spent paused
+    pub fn paused_time(&self) -> f64 {
+        self.paused_time
+    }
+
+    /// Move on to clock time `now`, returns true if the tree stands still this frame
+    pub(crate) fn advance(&mut self, now: f64) -> bool {
+        let delta = now - self.last.unwrap_or(now);
+        self.last = Some(now);
+
+        // A pending step runs this frame, and time moves on for it
+        let stepping = self.paused && self.steps > 0;
+        if stepping {
+            self.steps -= 1;
+        } else if self.paused {
+            self.paused_time += delta;
+        }
+        self.paused && !stepping
+    }
+
+    /// Take a pending restart, for trees that restart at once
+    pub(crate) fn take_restart(&mut self) -> bool {
+        std::mem::take(&mut self.restart)
+    }
+}
+
+/// A marker added to nodes of paused trees, they are left out of behavior queries
+#[derive(Debug, Default, Clone, Copy, Component, Reflect)]
+#[reflect(Component)]
+#[component(storage = "SparseSet")]
+pub struct BehaviorPaused;
+
+/// Clock time as seen by the nodes of a tree, which stands still while the tree is paused.
+/// Time based nodes read it instead of `BehaviorClock`. Apps without a `BehaviorClock`
+/// run on `Time`.
+#[derive(SystemParam)]
+pub struct BehaviorTime<'w, 's> {
+    clock: Option<Res<'w, BehaviorClock>>,
+    time: Option<Res<'w, Time>>,
+    controls: Query<'w, 's, &'static BehaviorTreeControl>,
+}
+
+impl<'w, 's> BehaviorTime<'w, 's> {
+    /// Seconds elapsed on the clock, whatever the tree
+    pub fn now(&self) -> f64 {
+        match (&self.clock, &self.time) {
+            (Some(clock), _) => clock.elapsed(),
+            (None, Some(time)) => time.seconds_since_startup(),
+            (None, None) => 0.0,
+        }
+    }
+
+    /// Seconds elapsed for the tree of a node
+    pub fn elapsed(&self, node: &BehaviorNode) -> f64 {
+        let paused_time = node
+            .tree
+            .and_then(|tree| self.controls.get(tree).ok())
+            .map_or(0.0, |control| control.paused_time);
+        self.now() - paused_time
+    }
+}
+
+/// Apply tree controls, pausing or resuming their nodes and restarting trees
+pub fn update_tree_control(
+    mut commands: Commands,
+    clock: Res<BehaviorClock>,
+    mut trees: Query<(Entity, &BehaviorTree, &mut BehaviorTreeControl)>,
+    nodes: Query<(Entity, &BehaviorNode, Option<&BehaviorPaused>), Without<BehaviorProxyNode>>,
+    roots: Query<
+        Entity,
+        Or<(
+            With<BehaviorCursor>,
+            With<BehaviorRunning>,
+            With<BehaviorSuccess>,
+            With<BehaviorFailure>,
+        )>,
+    >,
+) {
+    let now = clock.elapsed();
+    let mut frozen = HashMap::default();
+    for (entity, tree, mut control) in &mut trees {
+        let freeze = control.advance(now);
+        frozen.insert(entity, freeze);
+
+        if control.restart {
+            if let Some(root) = tree.root {
+                if roots.get(root).is_ok() {
+                    // Stop the tree first, it starts over once stopped
+                    commands.entity(root).insert(BehaviorStopped);
+                } else {
+                    commands.entity(root).insert(BehaviorCursor);
+                    control.restart = false;
+                }
+            } else {
+                control.restart = false;
+            }
+        }
+    }
+
+    for (entity, node, paused) in &nodes {
+        let freeze = node
+            .tree
+            .and_then(|tree| frozen.get(&tree))
+            .copied()
+            .unwrap_or(false);
+        if freeze && paused.is_none() {
+            commands.entity(entity).insert(BehaviorPaused);
+        } else if !freeze && paused.is_some() {
+            commands.entity(entity).remove::<BehaviorPaused>();
+        }
+    }
+}
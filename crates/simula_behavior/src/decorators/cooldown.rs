@@ -0,0 +1,107 @@
+use crate::prelude::*;
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// A cooldown runs its child, then fails right away if entered again before a duration
+/// has passed since the child completed.
+#[derive(Debug, Default, Component, Reflect, Clone, Deserialize, Serialize, Inspectable)]
+pub struct Cooldown {
+    pub duration: f64,
+    /// Time from which the child can run again
+    #[serde(default)]
+    pub ready_at: f64,
+}
+
+impl BehaviorInfo for Cooldown {
+    const TYPE: BehaviorType = BehaviorType::Decorator;
+    const NAME: &'static str = "Cooldown";
+    const DESC: &'static str = "Fail if entered again too soon after child completed";
+
+    fn tick_compact(&self, context: &mut BehaviorTickContext) -> Option<BehaviorTick> {
+        // The time the child can run again is kept in the node time
+        if context.entered && context.time < context.local.time {
+            return Some(BehaviorTick::Failure);
+        }
+        let tick = match context.child(0) {
+            _ if context.children() == 0 => BehaviorTick::Success,
+            BehaviorNodeState::Success => BehaviorTick::Success,
+            BehaviorNodeState::Failure => BehaviorTick::Failure,
+            _ => return Some(BehaviorTick::Child(0)),
+        };
+        context.local.time = context.time + self.duration;
+        Some(tick)
+    }
+}
+
+pub fn run(
+    time: BehaviorTime,
+    mut commands: Commands,
+    mut cooldowns: Query<
+        (
+            Entity,
+            &BehaviorChildren,
+            &mut Cooldown,
+            &mut BehaviorRunning,
+            &BehaviorNode,
+        ),
+        (With<Cooldown>, BehaviorRunQuery),
+    >,
+    nodes: Query<BehaviorChildQuery, BehaviorChildQueryFilter>,
+) {
+    for (entity, children, mut cooldown, mut running, node) in &mut cooldowns {
+        let now = time.elapsed(node);
+        // Still cooling down, so we fail fast
+        if !running.on_enter_handled {
+            running.on_enter_handled = true;
+            if now < cooldown.ready_at {
+                commands.entity(entity).insert(BehaviorFailure);
+                continue;
+            }
+        }
+        if children.is_empty() {
+            cooldown.ready_at = now + cooldown.duration;
+            commands.entity(entity).insert(BehaviorSuccess);
+        } else {
+            if children.len() > 1 {
+                warn!("Has more than one child, only the first will be used");
+            }
+            let child_entity = children[0]; // Safe because we checked for empty
+            if let Ok(BehaviorChildQueryItem {
+                child_entity,
+                child_parent,
+                child_failure,
+                child_success,
+                child_running: _,
+            }) = nodes.get(child_entity)
+            {
+                if let Some(child_parent) = **child_parent {
+                    if entity == child_parent {
+                        // Child failed, so we fail and start cooling down
+                        if child_failure.is_some() {
+                            cooldown.ready_at = now + cooldown.duration;
+                            commands.entity(entity).insert(BehaviorFailure);
+                        }
+                        // Child succeeded, so we succeed and start cooling down
+                        else if child_success.is_some() {
+                            cooldown.ready_at = now + cooldown.duration;
+                            commands.entity(entity).insert(BehaviorSuccess);
+                        }
+                        // Child is ready, pass on cursor
+                        else {
+                            commands.entity(entity).remove::<BehaviorCursor>();
+                            commands.entity(child_entity).insert(BehaviorCursor);
+                        }
+                    } else {
+                        // Child is not ours, so we fail
+                        warn!("Child is not ours");
+                        commands.entity(entity).insert(BehaviorFailure);
+                    }
+                } else {
+                    // Child has no parent, so we fail
+                    warn!("Child has no parent");
+                    commands.entity(entity).insert(BehaviorFailure);
+                }
+            }
+        }
+    }
+}
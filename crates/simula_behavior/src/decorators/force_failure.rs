@@ -0,0 +1,73 @@
+use crate::prelude::*;
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// A force failure will always return failure, irrespective of what the child node
+/// actually returned. The opposite of a succeeder.
+#[derive(Debug, Default, Component, Reflect, Clone, Deserialize, Serialize, Inspectable)]
+pub struct ForceFailure;
+
+impl BehaviorInfo for ForceFailure {
+    const TYPE: BehaviorType = BehaviorType::Decorator;
+    const NAME: &'static str = "ForceFailure";
+    const DESC: &'static str = "A force failure will always return failure";
+
+    fn tick_compact(&self, context: &mut BehaviorTickContext) -> Option<BehaviorTick> {
+        Some(match context.child(0) {
+            _ if context.children() == 0 => BehaviorTick::Failure,
+            BehaviorNodeState::Success | BehaviorNodeState::Failure => BehaviorTick::Failure,
+            _ => BehaviorTick::Child(0),
+        })
+    }
+}
+
+pub fn run(
+    mut commands: Commands,
+    mut force_failures: Query<(Entity, &BehaviorChildren), (With<ForceFailure>, BehaviorRunQuery)>,
+    nodes: Query<BehaviorChildQuery, BehaviorChildQueryFilter>,
+) {
+    for (entity, children) in &mut force_failures {
+        if children.is_empty() {
+            commands.entity(entity).insert(BehaviorFailure);
+        } else {
+            if children.len() > 1 {
+                warn!("Has more than one child, only the first will be used");
+            }
+            let child_entity = children[0]; // Safe because we checked for empty
+            if let Ok(BehaviorChildQueryItem {
+                child_entity,
+                child_parent,
+                child_failure,
+                child_success,
+                child_running: _,
+            }) = nodes.get(child_entity)
+            {
+                if let Some(child_parent) = **child_parent {
+                    if entity == child_parent {
+                        // Child failed, so we fail
+                        if child_failure.is_some() {
+                            commands.entity(entity).insert(BehaviorFailure);
+                        }
+                        // Child succeeded, so we fail
+                        else if child_success.is_some() {
+                            commands.entity(entity).insert(BehaviorFailure);
+                        }
+                        // Child is ready, pass on cursor
+                        else {
+                            commands.entity(entity).remove::<BehaviorCursor>();
+                            commands.entity(child_entity).insert(BehaviorCursor);
+                        }
+                    } else {
+                        // Child is not ours, so we fail
+                        warn!("Child is not ours");
+                        commands.entity(entity).insert(BehaviorFailure);
+                    }
+                } else {
+                    // Child has no parent, so we fail
+                    warn!("Child has no parent");
+                    commands.entity(entity).insert(BehaviorFailure);
+                }
+            }
+        }
+    }
+}
@@ -10,6 +10,15 @@ impl BehaviorInfo for Inverter {
     const TYPE: BehaviorType = BehaviorType::Decorator;
     const NAME: &'static str = "Inverter";
     const DESC: &'static str = "Inverts result of child node";
+
+    fn tick_compact(&self, context: &mut BehaviorTickContext) -> Option<BehaviorTick> {
+        Some(match context.child(0) {
+            _ if context.children() == 0 => BehaviorTick::Success,
+            BehaviorNodeState::Success => BehaviorTick::Failure,
+            BehaviorNodeState::Failure => BehaviorTick::Success,
+            _ => BehaviorTick::Child(0),
+        })
+    }
 }
 
 pub fn run(
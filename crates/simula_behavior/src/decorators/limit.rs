@@ -0,0 +1,97 @@
+use crate::prelude::*;
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// A limit runs its child at most a number of times over the lifetime of its tree.
+/// Once the limit is reached, it fails without running the child.
+#[derive(Debug, Default, Component, Reflect, Clone, Deserialize, Serialize, Inspectable)]
+pub struct Limit {
+    pub max: u64,
+    #[serde(default)]
+    pub count: u64,
+}
+
+impl BehaviorInfo for Limit {
+    const TYPE: BehaviorType = BehaviorType::Decorator;
+    const NAME: &'static str = "Limit";
+    const DESC: &'static str = "Run child at most a number of times";
+
+    fn tick_compact(&self, context: &mut BehaviorTickContext) -> Option<BehaviorTick> {
+        // Count activations over the lifetime of the tree
+        if context.entered {
+            if context.local.count >= self.max {
+                return Some(BehaviorTick::Failure);
+            }
+            context.local.count += 1;
+        }
+        Some(match context.child(0) {
+            _ if context.children() == 0 => BehaviorTick::Success,
+            BehaviorNodeState::Success => BehaviorTick::Success,
+            BehaviorNodeState::Failure => BehaviorTick::Failure,
+            _ => BehaviorTick::Child(0),
+        })
+    }
+}
+
+pub fn run(
+    mut commands: Commands,
+    mut limits: Query<
+        (Entity, &BehaviorChildren, &mut Limit, &mut BehaviorRunning),
+        (With<Limit>, BehaviorRunQuery),
+    >,
+    nodes: Query<BehaviorChildQuery, BehaviorChildQueryFilter>,
+) {
+    for (entity, children, mut limit, mut running) in &mut limits {
+        // Count activations, and refuse the ones over the limit
+        if !running.on_enter_handled {
+            running.on_enter_handled = true;
+            if limit.count >= limit.max {
+                commands.entity(entity).insert(BehaviorFailure);
+                continue;
+            }
+            limit.count += 1;
+        }
+        if children.is_empty() {
+            commands.entity(entity).insert(BehaviorSuccess);
+        } else {
+            if children.len() > 1 {
+                warn!("Has more than one child, only the first will be used");
+            }
+            let child_entity = children[0]; // Safe because we checked for empty
+            if let Ok(BehaviorChildQueryItem {
+                child_entity,
+                child_parent,
+                child_failure,
+                child_success,
+                child_running: _,
+            }) = nodes.get(child_entity)
+            {
+                if let Some(child_parent) = **child_parent {
+                    if entity == child_parent {
+                        // Child failed, so we fail
+                        if child_failure.is_some() {
+                            commands.entity(entity).insert(BehaviorFailure);
+                        }
+                        // Child succeeded, so we succeed
+                        else if child_success.is_some() {
+                            commands.entity(entity).insert(BehaviorSuccess);
+                        }
+                        // Child is ready, pass on cursor
+                        else {
+                            commands.entity(entity).remove::<BehaviorCursor>();
+                            commands.entity(child_entity).insert(BehaviorCursor);
+                        }
+                    } else {
+                        // Child is not ours, so we fail
+                        warn!("Child is not ours");
+                        commands.entity(entity).insert(BehaviorFailure);
+                    }
+                } else {
+                    // Child has no parent, so we fail
+                    warn!("Child has no parent");
+                    commands.entity(entity).insert(BehaviorFailure);
+                }
+            }
+        }
+    }
+}
@@ -1,7 +1,21 @@
+pub mod cooldown;
+pub mod force_failure;
 pub mod inverter;
+pub mod limit;
+pub mod observer;
 pub mod repeater;
+pub mod retry;
+pub mod subtree;
 pub mod succeeder;
+pub mod timeout;
 
+pub use cooldown::*;
+pub use force_failure::*;
 pub use inverter::*;
+pub use limit::*;
+pub use observer::*;
 pub use repeater::*;
+pub use retry::*;
+pub use subtree::*;
 pub use succeeder::*;
+pub use timeout::*;
@@ -0,0 +1,165 @@
+use crate::prelude::*;
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// What an observer aborts when its condition changes
+#[derive(Debug, Default, Reflect, Clone, Copy, PartialEq, Deserialize, Serialize, Inspectable)]
+pub enum Abort {
+    #[default]
+    Never,
+    /// Stop our running child when the condition stops passing
+    SelfOnly,
+    /// Stop running lower priority siblings when the condition starts passing
+    LowerPriority,
+    /// Both of the above
+    Both,
+}
+
+/// An observer only runs its child while a blackboard condition passes. The condition
+/// is re-evaluated every frame, so a running branch can be aborted when it changes.
+#[derive(Debug, Default, Component, Reflect, Clone, Deserialize, Serialize, Inspectable)]
+pub struct Observer {
+    pub key: String,
+    #[serde(default)]
+    pub test: ConditionTest,
+    #[serde(default)]
+    pub abort: Abort,
+}
+
+impl BehaviorInfo for Observer {
+    const TYPE: BehaviorType = BehaviorType::Decorator;
+    const NAME: &'static str = "Observer";
+    const DESC: &'static str = "Run child while a condition passes, abort on change";
+    const FLOW: BehaviorFlow = BehaviorFlow {
+        aborts: true,
+        ..BehaviorFlow::NONE
+    };
+}
+
+pub fn run(
+    mut commands: Commands,
+    observers: Query<
+        (
+            Entity,
+            &Observer,
+            &BehaviorNode,
+            &BehaviorParent,
+            &BehaviorChildren,
+            Option<&BehaviorCursor>,
+            Option<&BehaviorRunning>,
+            Option<&BehaviorFailure>,
+            Option<&BehaviorSuccess>,
+        ),
+        (Without<BehaviorStopped>, Without<BehaviorPaused>),
+    >,
+    nodes: Query<BehaviorChildQuery, BehaviorChildQueryFilter>,
+    parents: Query<&BehaviorChildren, With<BehaviorRunning>>,
+    active: Query<Entity, (With<BehaviorNode>, With<BehaviorRunning>)>,
+    blackboards: Query<&BehaviorBlackboard>,
+) {
+    for (entity, observer, node, parent, children, cursor, running, failure, success) in &observers
+    {
+        let blackboard = node.tree.and_then(|tree| blackboards.get(tree).ok());
+        let passed = observer.test.check_key(blackboard, &observer.key);
+
+        if running.is_some() && failure.is_none() && success.is_none() {
+            if cursor.is_some() {
+                run_observer(&mut commands, entity, passed, children, &nodes);
+            } else if !passed && matches!(observer.abort, Abort::SelfOnly | Abort::Both) {
+                // Condition no longer passes, stop our running child and fail
+                for child in children.iter() {
+                    commands.entity(*child).insert(BehaviorStopped);
+                }
+                commands.entity(entity).insert(BehaviorCursor);
+                commands.entity(entity).insert(BehaviorFailure);
+            }
+        } else if running.is_none()
+            && failure.is_some()
+            && passed
+            && matches!(observer.abort, Abort::LowerPriority | Abort::Both)
+        {
+            // Condition passes now, stop lower priority siblings and let parent retry us
+            let parent = if let Some(parent) = **parent {
+                parent
+            } else {
+                continue;
+            };
+            let siblings = if let Ok(siblings) = parents.get(parent) {
+                siblings
+            } else {
+                continue;
+            };
+            let lower = siblings
+                .iter()
+                .skip_while(|sibling| **sibling != entity)
+                .skip(1)
+                .filter(|sibling| active.get(**sibling).is_ok())
+                .copied()
+                .collect::<Vec<Entity>>();
+            if !lower.is_empty() {
+                for sibling in lower {
+                    commands.entity(sibling).insert(BehaviorStopped);
+                }
+                commands.entity(entity).remove::<BehaviorFailure>();
+                commands.entity(parent).insert(BehaviorCursor);
+            }
+        }
+    }
+}
+
+fn run_observer(
+    commands: &mut Commands,
+    entity: Entity,
+    passed: bool,
+    children: &BehaviorChildren,
+    nodes: &Query<BehaviorChildQuery, BehaviorChildQueryFilter>,
+) {
+    if children.is_empty() {
+        if passed {
+            commands.entity(entity).insert(BehaviorSuccess);
+        } else {
+            commands.entity(entity).insert(BehaviorFailure);
+        }
+        return;
+    }
+    if children.len() > 1 {
+        warn!("Has more than one child, only the first will be used");
+    }
+    let child_entity = children[0]; // Safe because we checked for empty
+    if let Ok(BehaviorChildQueryItem {
+        child_entity,
+        child_parent,
+        child_failure,
+        child_success,
+        child_running: _,
+    }) = nodes.get(child_entity)
+    {
+        if let Some(child_parent) = **child_parent {
+            if entity == child_parent {
+                // Child failed, so we fail
+                if child_failure.is_some() {
+                    commands.entity(entity).insert(BehaviorFailure);
+                }
+                // Child succeeded, so we succeed
+                else if child_success.is_some() {
+                    commands.entity(entity).insert(BehaviorSuccess);
+                }
+                // Child is ready, pass on cursor only if condition passes
+                else if passed {
+                    commands.entity(entity).remove::<BehaviorCursor>();
+                    commands.entity(child_entity).insert(BehaviorCursor);
+                } else {
+                    commands.entity(entity).insert(BehaviorFailure);
+                }
+            } else {
+                // Child is not ours, so we fail
+                warn!("Child is not ours");
+                commands.entity(entity).insert(BehaviorFailure);
+            }
+        } else {
+            // Child has no parent, so we fail
+            warn!("Child has no parent");
+            commands.entity(entity).insert(BehaviorFailure);
+        }
+    }
+}
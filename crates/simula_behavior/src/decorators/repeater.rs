@@ -22,6 +22,30 @@ impl BehaviorInfo for Repeater {
     const TYPE: BehaviorType = BehaviorType::Decorator;
     const NAME: &'static str = "Repeater";
     const DESC: &'static str = "Repeat a child until condition is met";
+
+    fn tick_compact(&self, context: &mut BehaviorTickContext) -> Option<BehaviorTick> {
+        if context.children() == 0 {
+            return Some(BehaviorTick::Success);
+        }
+        let state = context.child(0);
+        let repeat = match (&self.repeat, state) {
+            // Child is ready, count the run
+            (_, BehaviorNodeState::Idle) => {
+                context.local.count += 1;
+                true
+            }
+            (_, BehaviorNodeState::Running) => true,
+            (Repeat::Forever, _) => true,
+            (Repeat::Times(times), _) => *times > 0 && context.local.count < *times,
+            (Repeat::UntilFail, state) => state == BehaviorNodeState::Success,
+        };
+        if repeat {
+            Some(BehaviorTick::Child(0))
+        } else {
+            context.local.count = 0;
+            Some(BehaviorTick::Success)
+        }
+    }
 }
 
 pub fn run(
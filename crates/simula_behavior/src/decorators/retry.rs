@@ -0,0 +1,100 @@
+use crate::prelude::*;
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// A retry runs its child again when it fails, up to a number of attempts. It succeeds
+/// as soon as the child succeeds, and fails when the child failed every attempt.
+#[derive(Debug, Default, Component, Reflect, Clone, Deserialize, Serialize, Inspectable)]
+pub struct Retry {
+    /// Number of retries after the first failure
+    pub attempts: u64,
+    #[serde(default)]
+    pub count: u64,
+}
+
+impl BehaviorInfo for Retry {
+    const TYPE: BehaviorType = BehaviorType::Decorator;
+    const NAME: &'static str = "Retry";
+    const DESC: &'static str = "Run child again when it fails, up to a number of attempts";
+
+    fn tick_compact(&self, context: &mut BehaviorTickContext) -> Option<BehaviorTick> {
+        if context.entered {
+            context.local.count = 0;
+        }
+        Some(match context.child(0) {
+            _ if context.children() == 0 => BehaviorTick::Success,
+            BehaviorNodeState::Success => BehaviorTick::Success,
+            BehaviorNodeState::Failure if context.local.count < self.attempts => {
+                context.local.count += 1;
+                BehaviorTick::Child(0)
+            }
+            BehaviorNodeState::Failure => BehaviorTick::Failure,
+            _ => BehaviorTick::Child(0),
+        })
+    }
+}
+
+pub fn run(
+    mut commands: Commands,
+    mut retries: Query<
+        (Entity, &BehaviorChildren, &mut Retry, &mut BehaviorRunning),
+        (With<Retry>, BehaviorRunQuery),
+    >,
+    nodes: Query<BehaviorChildQuery, BehaviorChildQueryFilter>,
+) {
+    for (entity, children, mut retry, mut running) in &mut retries {
+        if !running.on_enter_handled {
+            running.on_enter_handled = true;
+            retry.count = 0;
+        }
+        if children.is_empty() {
+            commands.entity(entity).insert(BehaviorSuccess);
+        } else {
+            if children.len() > 1 {
+                warn!("Has more than one child, only the first will be used");
+            }
+            let child_entity = children[0]; // Safe because we checked for empty
+            if let Ok(BehaviorChildQueryItem {
+                child_entity,
+                child_parent,
+                child_failure,
+                child_success,
+                child_running: _,
+            }) = nodes.get(child_entity)
+            {
+                if let Some(child_parent) = **child_parent {
+                    if entity == child_parent {
+                        // Child failed, so we retry until out of attempts
+                        if child_failure.is_some() {
+                            if retry.count < retry.attempts {
+                                retry.count += 1;
+                                commands.entity(entity).remove::<BehaviorCursor>();
+                                commands.entity(child_entity).remove::<BehaviorFailure>();
+                                commands.entity(child_entity).insert(BehaviorCursor);
+                            } else {
+                                commands.entity(entity).insert(BehaviorFailure);
+                            }
+                        }
+                        // Child succeeded, so we succeed
+                        else if child_success.is_some() {
+                            commands.entity(entity).insert(BehaviorSuccess);
+                        }
+                        // Child is ready, pass on cursor
+                        else {
+                            commands.entity(entity).remove::<BehaviorCursor>();
+                            commands.entity(child_entity).insert(BehaviorCursor);
+                        }
+                    } else {
+                        // Child is not ours, so we fail
+                        warn!("Child is not ours");
+                        commands.entity(entity).insert(BehaviorFailure);
+                    }
+                } else {
+                    // Child has no parent, so we fail
+                    warn!("Child has no parent");
+                    commands.entity(entity).insert(BehaviorFailure);
+                }
+            }
+        }
+    }
+}
@@ -1 +1,182 @@
-// TODO: Create a subtree decorator node for linking trees together
\ No newline at end of file
+use crate::{add_children, prelude::*};
+use bevy::{asset::AssetPath, prelude::*, reflect::TypeUuid};
+use serde::{Deserialize, Serialize};
+use std::path::{Component as PathComponent, PathBuf};
+
+/// A subtree links another behavior asset as its only child. The asset is loaded and
+/// spawned the first time the subtree runs, and its result is passed through as is.
+#[derive(Debug, Default, Component, Reflect, Clone, Deserialize, Serialize, Inspectable)]
+pub struct Subtree {
+    pub path: String,
+}
+
+impl BehaviorInfo for Subtree {
+    const TYPE: BehaviorType = BehaviorType::Decorator;
+    const NAME: &'static str = "Subtree";
+    const DESC: &'static str = "Run another behavior asset as child";
+}
+
+pub fn run<T>(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    subtrees: Query<(Entity, &Subtree, &BehaviorChildren), BehaviorRunQuery>,
+    ancestors: Query<(
+        Option<&BehaviorParent>,
+        Option<&Subtree>,
+        Option<&BehaviorSource>,
+    )>,
+    nodes: Query<BehaviorChildQuery, BehaviorChildQueryFilter>,
+    failed: Query<&BehaviorLoadFailed>,
+    mut trees: Query<(&BehaviorChildren, &mut BehaviorNode)>,
+) where
+    T: BehaviorSpawner
+        + TypeUuid
+        + Send
+        + Sync
+        + 'static
+        + Default
+        + std::fmt::Debug
+        + for<'de> Deserialize<'de>,
+{
+    for (entity, subtree, children) in &subtrees {
+        if children.is_empty() {
+            // Refuse to include an asset already included above us
+            if is_recursive(entity, &subtree.path, &ancestors, &asset_server) {
+                warn!("Subtree is recursive: {}", subtree.path);
+                commands.entity(entity).insert(BehaviorFailure);
+                continue;
+            }
+            // Lazily load subtree, it becomes our only child
+            let document: Handle<BehaviorAsset> = asset_server.load(&subtree.path);
+            let child_entity = commands
+                .spawn()
+                .insert(BehaviorAssetLoading::<T> {
+                    document,
+                    parent: Some(entity),
+                    ..default()
+                })
+                .id();
+            add_children(&mut commands, entity, &[child_entity]);
+            continue;
+        }
+        if children.len() > 1 {
+            warn!("Has more than one child, only the first will be used");
+        }
+        let child_entity = children[0]; // Safe because we checked for empty
+
+        // Asset could not be loaded, so we fail
+        if failed.get(child_entity).is_ok() {
+            commands.entity(entity).insert(BehaviorFailure);
+            continue;
+        }
+        if let Ok(BehaviorChildQueryItem {
+            child_entity,
+            child_parent,
+            child_failure,
+            child_success,
+            child_running: _,
+        }) = nodes.get(child_entity)
+        {
+            if let Some(child_parent) = **child_parent {
+                if entity == child_parent {
+                    // Child failed, so we fail
+                    if child_failure.is_some() {
+                        commands.entity(entity).insert(BehaviorFailure);
+                    }
+                    // Child succeeded, so we succeed
+                    else if child_success.is_some() {
+                        commands.entity(entity).insert(BehaviorSuccess);
+                    }
+                    // Child is ready, pass on cursor
+                    else {
+                        // Loaded nodes join the tree we belong to
+                        if let Ok((_, node)) = trees.get(entity) {
+                            if let Some(tree) = node.tree {
+                                set_tree_recursively(&mut trees, tree, child_entity);
+                            }
+                        }
+                        commands.entity(entity).remove::<BehaviorCursor>();
+                        commands.entity(child_entity).insert(BehaviorCursor);
+                    }
+                } else {
+                    // Child is not ours, so we fail
+                    warn!("Child is not ours");
+                    commands.entity(entity).insert(BehaviorFailure);
+                }
+            } else {
+                // Child has no parent, so we fail
+                warn!("Child has no parent");
+                commands.entity(entity).insert(BehaviorFailure);
+            }
+        }
+    }
+}
+
+/// Check the subtree path against subtrees above us, and assets the tree was loaded from
+fn is_recursive(
+    entity: Entity,
+    path: &str,
+    ancestors: &Query<(
+        Option<&BehaviorParent>,
+        Option<&Subtree>,
+        Option<&BehaviorSource>,
+    )>,
+    asset_server: &AssetServer,
+) -> bool {
+    let path = asset_path(path);
+    let mut current = Some(entity);
+    while let Some(node) = current {
+        let (parent, subtree, source) = match ancestors.get(node) {
+            Ok(ancestor) => ancestor,
+            Err(_) => break,
+        };
+        let included = subtree
+            .filter(|_| node != entity)
+            .map(|subtree| asset_path(&subtree.path));
+        let loaded = source
+            .and_then(|source| asset_server.get_handle_path(&source.document))
+            .map(|source| asset_path(&source.path().to_string_lossy()));
+        if included.as_ref() == Some(&path) || loaded.as_ref() == Some(&path) {
+            return true;
+        }
+        current = parent.and_then(|parent| **parent);
+    }
+    false
+}
+
+/// Path of an asset by its components, so that `./a/b.bht.ron`, `a/b.bht.ron` and
+/// `a\b.bht.ron` are the same asset
+fn asset_path(path: &str) -> PathBuf {
+    let path = path.replace('\\', "/");
+    let asset_path = AssetPath::from(path.as_str());
+    let mut normalized = PathBuf::new();
+    for component in asset_path.path().components() {
+        match component {
+            PathComponent::CurDir => {}
+            PathComponent::ParentDir => {
+                normalized.pop();
+            }
+            component => normalized.push(component),
+        }
+    }
+    normalized
+}
+
+fn set_tree_recursively(
+    trees: &mut Query<(&BehaviorChildren, &mut BehaviorNode)>,
+    tree: Entity,
+    entity: Entity,
+) {
+    let children = if let Ok((children, mut node)) = trees.get_mut(entity) {
+        if node.tree == Some(tree) {
+            return;
+        }
+        node.tree = Some(tree);
+        children.iter().copied().collect::<Vec<Entity>>()
+    } else {
+        vec![]
+    };
+    for child in children {
+        set_tree_recursively(trees, tree, child);
+    }
+}
@@ -13,6 +13,14 @@ impl BehaviorInfo for Succeeder {
     const TYPE: BehaviorType = BehaviorType::Decorator;
     const NAME: &'static str = "Succeeder";
     const DESC: &'static str = "A succeeder will always return success";
+
+    fn tick_compact(&self, context: &mut BehaviorTickContext) -> Option<BehaviorTick> {
+        Some(match context.child(0) {
+            _ if context.children() == 0 => BehaviorTick::Success,
+            BehaviorNodeState::Success | BehaviorNodeState::Failure => BehaviorTick::Success,
+            _ => BehaviorTick::Child(0),
+        })
+    }
 }
 
 pub fn run(
@@ -0,0 +1,118 @@
+use crate::prelude::*;
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// A timeout fails if its child runs longer than a duration, stopping the child.
+/// Otherwise the result of the child is passed through.
+#[derive(Debug, Default, Component, Reflect, Clone, Deserialize, Serialize, Inspectable)]
+pub struct Timeout {
+    pub duration: f64,
+    #[serde(default)]
+    pub start: f64,
+}
+
+impl BehaviorInfo for Timeout {
+    const TYPE: BehaviorType = BehaviorType::Decorator;
+    const NAME: &'static str = "Timeout";
+    const DESC: &'static str = "Fail if child runs longer than a duration";
+    const FLOW: BehaviorFlow = BehaviorFlow {
+        aborts: true,
+        ..BehaviorFlow::NONE
+    };
+
+    fn tick_compact(&self, context: &mut BehaviorTickContext) -> Option<BehaviorTick> {
+        if context.entered {
+            context.local.time = context.time;
+        }
+        Some(match context.child(0) {
+            _ if context.children() == 0 => BehaviorTick::Success,
+            BehaviorNodeState::Success => BehaviorTick::Success,
+            BehaviorNodeState::Failure => BehaviorTick::Failure,
+            // Out of time, the child is stopped with us
+            _ if context.time - context.local.time > self.duration => BehaviorTick::Failure,
+            _ => BehaviorTick::Child(0),
+        })
+    }
+}
+
+pub fn run(
+    time: BehaviorTime,
+    mut commands: Commands,
+    mut timeouts: Query<
+        (
+            Entity,
+            &BehaviorChildren,
+            &mut Timeout,
+            &mut BehaviorRunning,
+            &BehaviorNode,
+            Option<&BehaviorCursor>,
+        ),
+        (
+            With<Timeout>,
+            Without<BehaviorSuccess>,
+            Without<BehaviorFailure>,
+            Without<BehaviorStopped>,
+            Without<BehaviorPaused>,
+        ),
+    >,
+    nodes: Query<BehaviorChildQuery, BehaviorChildQueryFilter>,
+) {
+    for (entity, children, mut timeout, mut running, node, cursor) in &mut timeouts {
+        let now = time.elapsed(node);
+        if !running.on_enter_handled {
+            running.on_enter_handled = true;
+            timeout.start = now;
+        }
+        if cursor.is_none() {
+            // Child is running, stop it once out of time
+            if now - timeout.start > timeout.duration {
+                for child in children.iter() {
+                    commands.entity(*child).insert(BehaviorStopped);
+                }
+                commands.entity(entity).insert(BehaviorCursor);
+                commands.entity(entity).insert(BehaviorFailure);
+            }
+        } else if children.is_empty() {
+            commands.entity(entity).insert(BehaviorSuccess);
+        } else {
+            if children.len() > 1 {
+                warn!("Has more than one child, only the first will be used");
+            }
+            let child_entity = children[0]; // Safe because we checked for empty
+            if let Ok(BehaviorChildQueryItem {
+                child_entity,
+                child_parent,
+                child_failure,
+                child_success,
+                child_running: _,
+            }) = nodes.get(child_entity)
+            {
+                if let Some(child_parent) = **child_parent {
+                    if entity == child_parent {
+                        // Child failed, so we fail
+                        if child_failure.is_some() {
+                            commands.entity(entity).insert(BehaviorFailure);
+                        }
+                        // Child succeeded, so we succeed
+                        else if child_success.is_some() {
+                            commands.entity(entity).insert(BehaviorSuccess);
+                        }
+                        // Child is ready, pass on cursor
+                        else {
+                            commands.entity(entity).remove::<BehaviorCursor>();
+                            commands.entity(child_entity).insert(BehaviorCursor);
+                        }
+                    } else {
+                        // Child is not ours, so we fail
+                        warn!("Child is not ours");
+                        commands.entity(entity).insert(BehaviorFailure);
+                    }
+                } else {
+                    // Child has no parent, so we fail
+                    warn!("Child has no parent");
+                    commands.entity(entity).insert(BehaviorFailure);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,474 @@
+use crate::{
+    asset::BehaviorDocument,
+    color_hex_utils::color_from_hex,
+    editor::{BehaviorGraph, EditorNode, EditorNodeId},
+    registry::BehaviorRegistry,
+    BehaviorCursor, BehaviorFailure, BehaviorRunning, BehaviorSpawner, BehaviorSuccess,
+    BehaviorTree, BehaviorType,
+};
+use bevy::prelude::*;
+use bevy_inspector_egui::{
+    bevy_egui::EguiContext,
+    egui::{self, epaint::CubicBezierShape, Rounding},
+    Context,
+};
+use serde::{Deserialize, Serialize};
+
+/// Size of a node in the editor canvas
+const NODE_SIZE: egui::Vec2 = egui::Vec2::new(150.0, 44.0);
+/// Radius of the port dragged from a node to connect a child
+const PORT_RADIUS: f32 = 6.0;
+
+/// State of the behavior editor, for documents of type `T`
+pub struct BehaviorEditor<T> {
+    pub open: bool,
+    pub graph: BehaviorGraph<T>,
+    /// Path of the edited .bht.ron file
+    pub path: String,
+    pub selected: Option<EditorNodeId>,
+    /// Result of the last action, shown under the toolbar
+    pub status: String,
+    pan: egui::Vec2,
+    connecting: Option<EditorNodeId>,
+}
+
+impl<T> Default for BehaviorEditor<T> {
+    fn default() -> Self {
+        Self {
+            open: true,
+            graph: BehaviorGraph::default(),
+            path: "assets/behaviors/new.bht.ron".to_string(),
+            selected: None,
+            status: String::new(),
+            pan: egui::Vec2::new(20.0, 20.0),
+            connecting: None,
+        }
+    }
+}
+
+impl<T> BehaviorEditor<T>
+where
+    T: BehaviorSpawner + Default + Clone + Serialize + for<'de> Deserialize<'de>,
+{
+    pub fn load(&mut self) {
+        let document = std::fs::read_to_string(&self.path)
+            .map_err(|err| err.to_string())
+            .and_then(|text| {
+                BehaviorDocument::<T>::from_ron(&self.path, &text).map_err(|err| err.to_string())
+            });
+        match document {
+            Ok(document) => {
+                self.graph = BehaviorGraph::from_document(&document);
+                self.selected = None;
+                self.status = format!("Loaded {}", self.path);
+            }
+            Err(err) => self.status = err,
+        }
+    }
+
+    pub fn save(&mut self) {
+        let text = self
+            .graph
+            .to_document()
+            .map_err(|err| err.to_string())
+            .and_then(|document| document.to_ron().map_err(|err| err.to_string()));
+        match text.and_then(|text| std::fs::write(&self.path, text).map_err(|err| err.to_string()))
+        {
+            Ok(()) => self.status = format!("Saved {}", self.path),
+            Err(err) => self.status = err,
+        }
+    }
+}
+
+fn titlebar_color<T>(node: &EditorNode<T>) -> egui::Color32
+where
+    T: BehaviorSpawner,
+{
+    match node.behavior.behavior_type() {
+        Some(BehaviorType::Action) => color_from_hex("#4284F3").unwrap(),
+        Some(BehaviorType::Composite) => color_from_hex("#36980D").unwrap(),
+        Some(BehaviorType::Decorator) => color_from_hex("#ACA000").unwrap(),
+        None => color_from_hex("#3f3f3f").unwrap(),
+    }
+}
+
+/// Color of the live state of the spawned node, if any
+fn state_color(world: &World, entity: Option<Entity>) -> Option<egui::Color32> {
+    let entity = entity?;
+    if world.get::<BehaviorFailure>(entity).is_some() {
+        Some(egui::Color32::RED)
+    } else if world.get::<BehaviorSuccess>(entity).is_some() {
+        Some(egui::Color32::DARK_GREEN)
+    } else if world.get::<BehaviorRunning>(entity).is_some() {
+        Some(egui::Color32::GREEN)
+    } else {
+        None
+    }
+}
+
+pub fn editor_ui<T>(world: &mut World)
+where
+    T: BehaviorSpawner
+        + Default
+        + Clone
+        + Serialize
+        + for<'de> Deserialize<'de>
+        + Send
+        + Sync
+        + 'static,
+{
+    let ctx = match world.get_resource_mut::<EguiContext>() {
+        Some(mut egui_context) => egui_context.ctx_mut().clone(),
+        None => return,
+    };
+    world.resource_scope(|world, mut editor: Mut<BehaviorEditor<T>>| {
+        if !editor.open {
+            return;
+        }
+        let mut open = editor.open;
+        egui::Window::new("Behavior Editor")
+            .open(&mut open)
+            .default_size(egui::Vec2::new(900.0, 600.0))
+            .resizable(true)
+            .show(&ctx, |ui| {
+                toolbar(ui, world, &mut editor);
+                egui::SidePanel::left("behavior_editor_palette")
+                    .resizable(true)
+                    .default_width(200.0)
+                    .show_inside(ui, |ui| {
+                        egui::ScrollArea::vertical().show(ui, |ui| {
+                            palette(ui, world.get_resource::<BehaviorRegistry>(), &mut editor);
+                            ui.separator();
+                            properties(ui, &ctx, &mut editor);
+                        });
+                    });
+                egui::CentralPanel::default().show_inside(ui, |ui| {
+                    canvas(ui, world, &mut editor);
+                });
+            });
+        editor.open = open;
+    });
+}
+
+fn toolbar<T>(ui: &mut egui::Ui, world: &mut World, editor: &mut BehaviorEditor<T>)
+where
+    T: BehaviorSpawner + Default + Clone + Serialize + for<'de> Deserialize<'de>,
+{
+    ui.horizontal(|ui| {
+        ui.text_edit_singleline(&mut editor.path);
+        if ui.button("Load").clicked() {
+            editor.load();
+        }
+        if ui.button("Save").clicked() {
+            editor.save();
+        }
+        if ui.button("New").clicked() {
+            editor.graph = BehaviorGraph::default();
+            editor.selected = None;
+        }
+        if ui.button("Layout").clicked() {
+            editor.graph.layout();
+        }
+        // Edit a copy of a spawned tree, and follow its state
+        egui::ComboBox::from_id_source("behavior_editor_trees")
+            .selected_text("Open tree")
+            .show_ui(ui, |ui| {
+                let mut trees = world.query::<(Entity, &BehaviorTree, Option<&Name>)>();
+                let trees = trees
+                    .iter(world)
+                    .filter_map(|(entity, tree, name)| {
+                        let label = format!(
+                            "[{}]: {}",
+                            entity.id(),
+                            name.map(|name| name.to_string()).unwrap_or_default()
+                        );
+                        tree.root.map(|root| (label, root))
+                    })
+                    .collect::<Vec<_>>();
+                for (label, root) in trees {
+                    if ui.selectable_label(false, &label).clicked() {
+                        match BehaviorGraph::from_entity(world, root) {
+                            Some(graph) => {
+                                editor.graph = graph;
+                                editor.selected = None;
+                                editor.status = format!("Opened {}", label);
+                            }
+                            None => editor.status = format!("Unknown behavior in {}", label),
+                        }
+                    }
+                }
+            });
+    });
+    if !editor.status.is_empty() {
+        ui.label(&editor.status);
+    }
+}
+
+/// Add a default node of each registered behavior, grouped by type. Without a registry,
+/// the defaults of the document type are listed instead.
+fn palette<T>(
+    ui: &mut egui::Ui,
+    registry: Option<&BehaviorRegistry>,
+    editor: &mut BehaviorEditor<T>,
+) where
+    T: BehaviorSpawner + Default + Clone,
+{
+    let behaviors = T::defaults();
+    for (typ, label) in [
+        (BehaviorType::Composite, "Composites"),
+        (BehaviorType::Decorator, "Decorators"),
+        (BehaviorType::Action, "Actions"),
+    ] {
+        ui.collapsing(label, |ui| match registry {
+            Some(registry) => {
+                for registration in registry.category(typ.clone()) {
+                    // Describe the behavior and its fields
+                    let mut hover = registration.desc.to_string();
+                    for field in registration.fields.iter() {
+                        hover.push_str(&format!("\n{}: {}", field.name, field.type_name));
+                    }
+                    if ui.button(registration.name).on_hover_text(hover).clicked() {
+                        match registration.behavior::<T>() {
+                            Some(behavior) => add_to_graph(editor, registration.name, behavior),
+                            None => {
+                                editor.status = format!(
+                                    "{} is not a node of this document",
+                                    registration.name
+                                );
+                            }
+                        }
+                    }
+                }
+            }
+            None => {
+                for behavior in behaviors
+                    .iter()
+                    .filter(|behavior| behavior.behavior_type() == Some(typ.clone()))
+                {
+                    let name = behavior.behavior_name().unwrap_or("Behavior");
+                    if ui.button(name).clicked() {
+                        add_to_graph(editor, name, behavior.clone());
+                    }
+                }
+            }
+        });
+    }
+}
+
+/// Add a node to the graph, as the last child of the selected node when possible
+fn add_to_graph<T>(editor: &mut BehaviorEditor<T>, name: &str, behavior: T)
+where
+    T: BehaviorSpawner + Default + Clone,
+{
+    let position = -editor.pan + egui::Vec2::new(40.0, 40.0);
+    let id = editor
+        .graph
+        .add_node(name.to_string(), behavior, Vec2::new(position.x, position.y));
+    if let Some(selected) = editor.selected {
+        if editor.graph.connect(selected, id).is_err() {
+            editor.selected = Some(id);
+        }
+    } else {
+        editor.selected = Some(id);
+    }
+}
+
+/// Edit the selected node name, fields and place in the graph
+fn properties<T>(ui: &mut egui::Ui, ctx: &egui::Context, editor: &mut BehaviorEditor<T>)
+where
+    T: BehaviorSpawner + Default + Clone,
+{
+    let id = match editor.selected {
+        Some(id) => id,
+        None => {
+            ui.label("Select a node to edit it");
+            return;
+        }
+    };
+    let mut result = Ok(());
+    let mut remove = false;
+    if let Ok(node) = editor.graph.get_mut(id) {
+        ui.text_edit_singleline(&mut node.name);
+        let mut context = Context::new_shared(Some(ctx));
+        node.behavior.inspect(ui, &mut context);
+    } else {
+        editor.selected = None;
+        return;
+    }
+    ui.horizontal(|ui| {
+        if ui
+            .button("◀")
+            .on_hover_text("Move before sibling")
+            .clicked()
+        {
+            result = editor.graph.move_child(id, -1);
+        }
+        if ui.button("▶").on_hover_text("Move after sibling").clicked() {
+            result = editor.graph.move_child(id, 1);
+        }
+        if ui.button("Detach").clicked() {
+            result = editor.graph.disconnect(id);
+        }
+        if ui.button("Root").on_hover_text("Make root").clicked() {
+            result = editor.graph.set_root(id);
+        }
+        if ui.button("Delete").clicked() {
+            remove = true;
+        }
+    });
+    if remove {
+        result = editor.graph.remove_node(id);
+        editor.selected = None;
+    }
+    if let Err(err) = result {
+        editor.status = err.to_string();
+    }
+}
+
+/// Draw nodes and connections, drag nodes to move them and ports to connect children
+fn canvas<T>(ui: &mut egui::Ui, world: &World, editor: &mut BehaviorEditor<T>)
+where
+    T: BehaviorSpawner + Default + Clone,
+{
+    let (rect, response) = ui.allocate_exact_size(ui.available_size(), egui::Sense::drag());
+    if response.dragged() {
+        editor.pan += response.drag_delta();
+    }
+    let painter = ui.painter_at(rect);
+    let origin = rect.min + editor.pan;
+    let node_rect = |node: &EditorNode<T>| {
+        egui::Rect::from_min_size(
+            origin + egui::Vec2::new(node.position.x, node.position.y),
+            NODE_SIZE,
+        )
+    };
+
+    // Connections, from parent bottom to child top
+    for (_, node) in editor.graph.iter() {
+        let from = node_rect(node).center_bottom();
+        for child in node.children.iter() {
+            if let Ok(child) = editor.graph.get(*child) {
+                let to = node_rect(child).center_top();
+                let bend = egui::Vec2::new(0.0, (to.y - from.y).abs().max(40.0) / 2.0);
+                painter.add(CubicBezierShape::from_points_stroke(
+                    [from, from + bend, to - bend, to],
+                    false,
+                    egui::Color32::TRANSPARENT,
+                    egui::Stroke::new(2.0, egui::Color32::GRAY),
+                ));
+            }
+        }
+    }
+
+    let pointer = ui.input().pointer.hover_pos();
+    let mut moved = vec![];
+    let mut selected = editor.selected;
+    let mut connect = None;
+    let ids = editor.graph.iter().map(|(id, _)| id).collect::<Vec<_>>();
+    for id in ids {
+        let node = match editor.graph.get(id) {
+            Ok(node) => node,
+            Err(_) => continue,
+        };
+        let rect = node_rect(node);
+        let root = editor.graph.root == Some(id);
+        let stroke = if selected == Some(id) {
+            egui::Stroke::new(3.0, color_from_hex("#FF00FF").unwrap())
+        } else if node.entity.map_or(false, |entity| {
+            world.get::<BehaviorCursor>(entity).is_some()
+        }) {
+            egui::Stroke::new(2.0, egui::Color32::WHITE)
+        } else {
+            egui::Stroke::none()
+        };
+        painter.rect(
+            rect,
+            Rounding::same(3.0),
+            color_from_hex("#303030").unwrap(),
+            stroke,
+        );
+        let title = egui::Rect::from_min_size(rect.min, egui::Vec2::new(rect.width(), 20.0));
+        painter.rect_filled(title, Rounding::same(3.0), titlebar_color(node));
+        painter.text(
+            title.left_center() + egui::Vec2::new(4.0, 0.0),
+            egui::Align2::LEFT_CENTER,
+            if root {
+                format!("⏵ {}", node.name)
+            } else {
+                node.name.clone()
+            },
+            egui::FontId::proportional(13.0),
+            egui::Color32::WHITE,
+        );
+        painter.text(
+            rect.left_bottom() + egui::Vec2::new(16.0, -12.0),
+            egui::Align2::LEFT_CENTER,
+            node.behavior.behavior_name().unwrap_or_default(),
+            egui::FontId::proportional(11.0),
+            egui::Color32::LIGHT_GRAY,
+        );
+        let state = state_color(world, node.entity).unwrap_or(egui::Color32::GRAY);
+        painter.circle_filled(rect.left_bottom() + egui::Vec2::new(8.0, -12.0), 3.0, state);
+
+        // Drag node to move it, click to select it
+        let node_response = ui.interact(
+            rect,
+            ui.id().with(("behavior_editor_node", id)),
+            egui::Sense::click_and_drag(),
+        );
+        if node_response.dragged() {
+            moved.push((id, node_response.drag_delta()));
+        }
+        if node_response.clicked() || node_response.drag_started() {
+            selected = Some(id);
+        }
+
+        // Drag port onto another node to make it a child
+        if node.behavior.behavior_type() != Some(BehaviorType::Action) {
+            let port = rect.center_bottom();
+            painter.circle_filled(port, PORT_RADIUS, egui::Color32::LIGHT_GRAY);
+            let port_response = ui.interact(
+                egui::Rect::from_center_size(port, egui::Vec2::splat(PORT_RADIUS * 2.0)),
+                ui.id().with(("behavior_editor_port", id)),
+                egui::Sense::drag(),
+            );
+            if port_response.drag_started() {
+                editor.connecting = Some(id);
+            }
+            if editor.connecting == Some(id) {
+                if let Some(pointer) = pointer {
+                    painter.line_segment(
+                        [port, pointer],
+                        egui::Stroke::new(2.0, egui::Color32::WHITE),
+                    );
+                }
+                if port_response.drag_released() {
+                    connect = Some(id);
+                }
+            }
+        }
+    }
+
+    for (id, delta) in moved {
+        if let Ok(node) = editor.graph.get_mut(id) {
+            node.position += Vec2::new(delta.x, delta.y);
+        }
+    }
+    editor.selected = selected;
+
+    if let Some(parent) = connect {
+        editor.connecting = None;
+        let target = pointer.and_then(|pointer| {
+            editor
+                .graph
+                .iter()
+                .find(|(id, node)| *id != parent && node_rect(node).contains(pointer))
+                .map(|(id, _)| id)
+        });
+        if let Some(child) = target {
+            if let Err(err) = editor.graph.connect(parent, child) {
+                editor.status = err.to_string();
+            }
+        }
+    }
+}
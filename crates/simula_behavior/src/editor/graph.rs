@@ -0,0 +1,353 @@
+use crate::{
+    asset::{BTNode, BehaviorDocument},
+    blackboard::BlackboardValue,
+    decorators::Subtree,
+    BehaviorBlackboard, BehaviorChildren, BehaviorNode, BehaviorSpawner, BehaviorType,
+};
+use bevy::prelude::*;
+use std::collections::BTreeMap;
+
+pub type EditorNodeId = usize;
+
+/// A behavior node being edited, with its place in the graph
+#[derive(Debug, Clone)]
+pub struct EditorNode<T> {
+    pub name: String,
+    pub behavior: T,
+    pub parent: Option<EditorNodeId>,
+    pub children: Vec<EditorNodeId>,
+    /// Position in the editor canvas
+    pub position: Vec2,
+    /// Spawned node this one was read from, to show its state live
+    pub entity: Option<Entity>,
+}
+
+/// Why a graph edit was refused
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+pub enum BehaviorGraphError {
+    #[error("unknown node {0}")]
+    UnknownNode(EditorNodeId),
+    #[error("\"{0}\" can't be its own ancestor")]
+    Cycle(String),
+    #[error("action \"{0}\" can't have children")]
+    ActionWithChildren(String),
+    #[error("decorator \"{0}\" already has a child")]
+    DecoratorChildren(String),
+    #[error("no root node")]
+    NoRoot,
+}
+
+/// Behavior nodes as an editable graph, converted from and to documents
+#[derive(Debug, Clone)]
+pub struct BehaviorGraph<T> {
+    nodes: BTreeMap<EditorNodeId, EditorNode<T>>,
+    next_id: EditorNodeId,
+    pub root: Option<EditorNodeId>,
+    pub blackboard: BTreeMap<String, BlackboardValue>,
+}
+
+impl<T> Default for BehaviorGraph<T> {
+    fn default() -> Self {
+        Self {
+            nodes: BTreeMap::new(),
+            next_id: 0,
+            root: None,
+            blackboard: BTreeMap::new(),
+        }
+    }
+}
+
+/// Spacing of nodes laid out as a tree
+const LAYOUT_SPACING: Vec2 = Vec2::new(180.0, 110.0);
+
+impl<T> BehaviorGraph<T>
+where
+    T: BehaviorSpawner + Default + Clone,
+{
+    pub fn from_document(document: &BehaviorDocument<T>) -> Self {
+        let mut graph = Self {
+            blackboard: document.blackboard.clone(),
+            ..default()
+        };
+        let root = graph.add_document_node(None, &document.root);
+        graph.root = Some(root);
+        graph.layout();
+        graph
+    }
+
+    fn add_document_node(
+        &mut self,
+        parent: Option<EditorNodeId>,
+        node: &BTNode<T>,
+    ) -> EditorNodeId {
+        let BTNode(name, behavior, children) = node;
+        let id = self.add_node(name.clone(), behavior.clone(), Vec2::ZERO);
+        self.attach(parent, id);
+        for child in children.iter() {
+            self.add_document_node(Some(id), child);
+        }
+        id
+    }
+
+    /// Read a spawned tree, nodes keep their entity to show their state
+    pub fn from_entity(world: &World, root: Entity) -> Option<Self> {
+        let mut graph = Self::default();
+        let id = graph.add_entity_node(world, None, root)?;
+        graph.root = Some(id);
+        graph.blackboard = world
+            .get::<BehaviorNode>(root)
+            .and_then(|node| node.tree)
+            .and_then(|tree| world.get::<BehaviorBlackboard>(tree))
+            .map(|blackboard| blackboard.to_values())
+            .unwrap_or_default();
+        graph.layout();
+        Some(graph)
+    }
+
+    fn add_entity_node(
+        &mut self,
+        world: &World,
+        parent: Option<EditorNodeId>,
+        entity: Entity,
+    ) -> Option<EditorNodeId> {
+        let name = world.get::<Name>(entity)?.to_string();
+        let behavior = T::from_entity(world, entity)?;
+        let id = self.add_node(name, behavior, Vec2::ZERO);
+        self.attach(parent, id);
+        if let Some(node) = self.nodes.get_mut(&id) {
+            node.entity = Some(entity);
+        }
+        // Subtree children come from their own asset
+        if world.get::<Subtree>(entity).is_none() {
+            if let Some(children) = world.get::<BehaviorChildren>(entity) {
+                for child in children.iter() {
+                    self.add_entity_node(world, Some(id), *child);
+                }
+            }
+        }
+        Some(id)
+    }
+
+    /// Create a document from the root node and its descendants, detached nodes are left out
+    pub fn to_document(&self) -> Result<BehaviorDocument<T>, BehaviorGraphError> {
+        let root = self.root.ok_or(BehaviorGraphError::NoRoot)?;
+        Ok(BehaviorDocument {
+            root: self.document_node(root)?,
+            blackboard: self.blackboard.clone(),
+        })
+    }
+
+    fn document_node(&self, id: EditorNodeId) -> Result<BTNode<T>, BehaviorGraphError> {
+        let node = self.get(id)?;
+        let children = node
+            .children
+            .iter()
+            .map(|child| self.document_node(*child))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(BTNode(node.name.clone(), node.behavior.clone(), children))
+    }
+
+    pub fn add_node(&mut self, name: String, behavior: T, position: Vec2) -> EditorNodeId {
+        let id = self.next_id;
+        self.next_id += 1;
+        self.nodes.insert(
+            id,
+            EditorNode {
+                name,
+                behavior,
+                parent: None,
+                children: vec![],
+                position,
+                entity: None,
+            },
+        );
+        if self.root.is_none() {
+            self.root = Some(id);
+        }
+        id
+    }
+
+    fn attach(&mut self, parent: Option<EditorNodeId>, child: EditorNodeId) {
+        if let Some(parent) = parent {
+            if let Some(node) = self.nodes.get_mut(&parent) {
+                node.children.push(child);
+            }
+        }
+        if let Some(node) = self.nodes.get_mut(&child) {
+            node.parent = parent;
+        }
+    }
+
+    /// Make `child` the last child of `parent`, detaching it from its previous parent
+    pub fn connect(
+        &mut self,
+        parent: EditorNodeId,
+        child: EditorNodeId,
+    ) -> Result<(), BehaviorGraphError> {
+        let parent_node = self.get(parent)?;
+        self.get(child)?;
+        if parent == child || self.is_ancestor(child, parent) {
+            return Err(BehaviorGraphError::Cycle(parent_node.name.clone()));
+        }
+        let children = parent_node
+            .children
+            .iter()
+            .filter(|id| **id != child)
+            .count();
+        match parent_node.behavior.behavior_type() {
+            Some(BehaviorType::Action) => {
+                return Err(BehaviorGraphError::ActionWithChildren(
+                    parent_node.name.clone(),
+                ))
+            }
+            Some(BehaviorType::Decorator) if children > 0 => {
+                return Err(BehaviorGraphError::DecoratorChildren(
+                    parent_node.name.clone(),
+                ))
+            }
+            _ => {}
+        }
+        self.disconnect(child)?;
+        if self.root == Some(child) {
+            self.root = None;
+        }
+        self.attach(Some(parent), child);
+        Ok(())
+    }
+
+    /// Detach a node from its parent, it stays in the graph with its children
+    pub fn disconnect(&mut self, child: EditorNodeId) -> Result<(), BehaviorGraphError> {
+        let parent = self.get(child)?.parent;
+        if let Some(parent) = parent {
+            if let Some(node) = self.nodes.get_mut(&parent) {
+                node.children.retain(|id| *id != child);
+            }
+        }
+        if let Some(node) = self.nodes.get_mut(&child) {
+            node.parent = None;
+        }
+        Ok(())
+    }
+
+    /// Move a node among its siblings, by `offset` places
+    pub fn move_child(
+        &mut self,
+        child: EditorNodeId,
+        offset: isize,
+    ) -> Result<(), BehaviorGraphError> {
+        let parent = match self.get(child)?.parent {
+            Some(parent) => parent,
+            None => return Ok(()),
+        };
+        if let Some(node) = self.nodes.get_mut(&parent) {
+            if let Some(index) = node.children.iter().position(|id| *id == child) {
+                let last = node.children.len() as isize - 1;
+                let target = (index as isize + offset).clamp(0, last) as usize;
+                let child = node.children.remove(index);
+                node.children.insert(target, child);
+            }
+        }
+        Ok(())
+    }
+
+    /// Remove a node, its children stay in the graph detached
+    pub fn remove_node(&mut self, id: EditorNodeId) -> Result<(), BehaviorGraphError> {
+        self.disconnect(id)?;
+        if let Some(node) = self.nodes.remove(&id) {
+            for child in node.children {
+                if let Some(child) = self.nodes.get_mut(&child) {
+                    child.parent = None;
+                }
+            }
+        }
+        if self.root == Some(id) {
+            self.root = None;
+        }
+        Ok(())
+    }
+
+    /// Make a detached node the root
+    pub fn set_root(&mut self, id: EditorNodeId) -> Result<(), BehaviorGraphError> {
+        self.disconnect(id)?;
+        self.root = Some(id);
+        Ok(())
+    }
+
+    /// Place the root tree top down, and detached nodes to its right
+    pub fn layout(&mut self) {
+        let mut column = 0.0;
+        let mut tops = self
+            .nodes
+            .iter()
+            .filter(|(id, node)| node.parent.is_none() && Some(**id) != self.root)
+            .map(|(id, _)| *id)
+            .collect::<Vec<_>>();
+        if let Some(root) = self.root {
+            tops.insert(0, root);
+        }
+        for top in tops {
+            self.layout_node(top, 0.0, &mut column);
+            column += 1.0;
+        }
+    }
+
+    /// Place a node above its children, leaves take one column each
+    fn layout_node(&mut self, id: EditorNodeId, depth: f32, column: &mut f32) -> f32 {
+        let children = self
+            .nodes
+            .get(&id)
+            .map(|node| node.children.clone())
+            .unwrap_or_default();
+        let x = if children.is_empty() {
+            let x = *column;
+            *column += 1.0;
+            x
+        } else {
+            let xs = children
+                .iter()
+                .map(|child| self.layout_node(*child, depth + 1.0, column))
+                .collect::<Vec<_>>();
+            (xs[0] + xs[xs.len() - 1]) / 2.0
+        };
+        if let Some(node) = self.nodes.get_mut(&id) {
+            node.position = Vec2::new(x, depth) * LAYOUT_SPACING;
+        }
+        x
+    }
+
+    /// Whether `ancestor` is above `id`
+    pub fn is_ancestor(&self, ancestor: EditorNodeId, id: EditorNodeId) -> bool {
+        let mut current = self.nodes.get(&id).and_then(|node| node.parent);
+        while let Some(parent) = current {
+            if parent == ancestor {
+                return true;
+            }
+            current = self.nodes.get(&parent).and_then(|node| node.parent);
+        }
+        false
+    }
+
+    pub fn get(&self, id: EditorNodeId) -> Result<&EditorNode<T>, BehaviorGraphError> {
+        self.nodes
+            .get(&id)
+            .ok_or(BehaviorGraphError::UnknownNode(id))
+    }
+
+    pub fn get_mut(&mut self, id: EditorNodeId) -> Result<&mut EditorNode<T>, BehaviorGraphError> {
+        self.nodes
+            .get_mut(&id)
+            .ok_or(BehaviorGraphError::UnknownNode(id))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (EditorNodeId, &EditorNode<T>)> {
+        self.nodes.iter().map(|(id, node)| (*id, node))
+    }
+
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+}
@@ -0,0 +1,36 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+pub use editor::{editor_ui, BehaviorEditor};
+pub use graph::{BehaviorGraph, BehaviorGraphError, EditorNode, EditorNodeId};
+
+use crate::BehaviorSpawner;
+
+pub mod editor;
+pub mod graph;
+
+/// Visual editor for behavior documents of type `T`
+pub struct BehaviorEditorPlugin<T>(std::marker::PhantomData<fn() -> T>);
+
+impl<T> Default for BehaviorEditorPlugin<T> {
+    fn default() -> Self {
+        Self(default())
+    }
+}
+
+impl<T> Plugin for BehaviorEditorPlugin<T>
+where
+    T: BehaviorSpawner
+        + Default
+        + Clone
+        + Serialize
+        + for<'de> Deserialize<'de>
+        + Send
+        + Sync
+        + 'static,
+{
+    fn build(&self, app: &mut App) {
+        app.init_resource::<BehaviorEditor<T>>()
+            .add_system(editor_ui::<T>.exclusive_system());
+    }
+}
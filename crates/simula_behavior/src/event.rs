@@ -0,0 +1,68 @@
+use crate::{
+    actions::{emit_event, wait_for_event, EmitEvent, WaitForEvent},
+    register_behavior, BehaviorAppExt, BehaviorBlackboard,
+};
+use bevy::{ecs::event::Events, prelude::*};
+use bevy_inspector_egui::Inspectable;
+use serde::{de::DeserializeOwned, Serialize};
+
+/// Filters and payloads of events are node fields, so they are reflected, inspected
+/// and saved with documents
+pub trait BehaviorEventData:
+    Reflect + Inspectable + Default + Clone + std::fmt::Debug + Serialize + DeserializeOwned
+{
+}
+
+impl<T> BehaviorEventData for T where
+    T: Reflect + Inspectable + Default + Clone + std::fmt::Debug + Serialize + DeserializeOwned
+{
+}
+
+/// An event that behavior trees wait for with `WaitForEvent`, and send with `EmitEvent`
+pub trait BehaviorEventInfo: std::fmt::Debug + Clone + Send + Sync + 'static {
+    /// Name of the waiting node, like `WaitForTransfer`
+    const WAIT_NAME: &'static str;
+    /// Name of the emitting node, like `EmitTransfer`
+    const EMIT_NAME: &'static str;
+    /// Selects the events a waiting node accepts
+    type Filter: BehaviorEventData;
+    /// Describes the event an emitting node sends
+    type Payload: BehaviorEventData;
+
+    /// Whether the event passes the filter of a waiting node
+    fn matches(&self, filter: &Self::Filter, blackboard: Option<&BehaviorBlackboard>) -> bool;
+
+    /// Create the event sent by an emitting node, None fails the node
+    fn from_payload(
+        payload: &Self::Payload,
+        blackboard: Option<&BehaviorBlackboard>,
+    ) -> Option<Self>;
+
+    /// Write data of an accepted event to the tree blackboard
+    fn store(&self, _filter: &Self::Filter, _blackboard: &mut BehaviorBlackboard) {}
+}
+
+/// Add an event, and the behavior nodes that wait for and emit it
+pub struct BehaviorEventPlugin<E>(std::marker::PhantomData<fn() -> E>);
+
+impl<E> Default for BehaviorEventPlugin<E> {
+    fn default() -> Self {
+        Self(default())
+    }
+}
+
+impl<E> Plugin for BehaviorEventPlugin<E>
+where
+    E: BehaviorEventInfo,
+{
+    fn build(&self, app: &mut App) {
+        // Events may already be added by gameplay plugins, adding twice would clear them early
+        if !app.world.contains_resource::<Events<E>>() {
+            app.add_event::<E>();
+        }
+        app.add_behavior_system(wait_for_event::run::<E>)
+            .add_behavior_system(emit_event::run::<E>);
+        register_behavior::<WaitForEvent<E>>(app);
+        register_behavior::<EmitEvent<E>>(app);
+    }
+}
@@ -1,6 +1,10 @@
 use crate::{
-    inspector::{BehaviorInspectorNode, BehaviorInspectorNodeAttributes},
-    BehaviorTree,
+    inspector::{
+        BehaviorInspectorNode, BehaviorInspectorNodeAttributes, BehaviorStatsView,
+        BehaviorStatsViewAttributes, BehaviorTimeline, BehaviorTimelineAttributes,
+    },
+    net::{BehaviorProxy, BehaviorTreeSnapshot},
+    BehaviorTree, BehaviorTreeControl,
 };
 use bevy::prelude::*;
 use bevy_inspector_egui::{egui, Context, Inspectable};
@@ -50,6 +54,54 @@ fn item_label(item: &BehaviorInspectorItem) -> String {
     format!("{}{}", entity, item.name)
 }
 
+/// Pause, resume, step and restart buttons of a tree
+fn tree_controls(ui: &mut egui::Ui, world: &mut World, tree: Entity) -> bool {
+    let mut control = world
+        .get::<BehaviorTreeControl>(tree)
+        .cloned()
+        .unwrap_or_default();
+    let mut changed = false;
+    ui.horizontal(|ui| {
+        if control.is_paused() {
+            if ui.button("Resume").clicked() {
+                control.resume();
+                changed = true;
+            }
+        } else if ui.button("Pause").clicked() {
+            control.pause();
+            changed = true;
+        }
+        if ui.button("Step").on_hover_text("Run one frame").clicked() {
+            control.step();
+            changed = true;
+        }
+        if ui.button("Restart").clicked() {
+            control.restart();
+            changed = true;
+        }
+        if control.is_paused() {
+            ui.label("Paused");
+        }
+    });
+    if changed {
+        world.entity_mut(tree).insert(control);
+    }
+    changed
+}
+
+/// Status of a tree mirrored from another peer, it can't be controlled from here
+fn remote_status(ui: &mut egui::Ui, world: &World, tree: Entity) {
+    let paused = world
+        .get::<BehaviorTreeSnapshot>(tree)
+        .map_or(false, |snapshot| snapshot.paused);
+    ui.horizontal(|ui| {
+        ui.label("Remote");
+        if paused {
+            ui.label("Paused");
+        }
+    });
+}
+
 macro_rules! some_or_return {
     ( $e:expr ) => {
         match $e {
@@ -109,10 +161,32 @@ impl Inspectable for BehaviorInspector {
                 .resizable(true)
                 .collapsible(true)
                 .show(ui.ctx(), |ui| {
+                    if let Some(world) = unsafe { context.world_mut() } {
+                        if world.get::<BehaviorProxy>(*entity).is_some() {
+                            remote_status(ui, world, *entity);
+                        } else {
+                            changed |= tree_controls(ui, world, *entity);
+                        }
+                    }
                     changed |= node.ui(ui, BehaviorInspectorNodeAttributes::default(), context);
                 });
+            let mut timeline = BehaviorTimeline {
+                tree: Some(*entity),
+            };
+            egui::Window::new(format!("Timeline: {}", name))
+                .title_bar(true)
+                .resizable(true)
+                .collapsible(true)
+                .default_width(600.0)
+                .show(ui.ctx(), |ui| {
+                    changed |= timeline.ui(ui, BehaviorTimelineAttributes::default(), context);
+                });
         }
 
+        ui.collapsing("Stats", |ui| {
+            changed |= BehaviorStatsView.ui(ui, BehaviorStatsViewAttributes, context);
+        });
+
         changed
     }
 }
@@ -2,9 +2,13 @@ use bevy::prelude::*;
 use bevy_inspector_egui::InspectorPlugin;
 pub use inspector::{BehaviorInspector, BehaviorInspectorAttributes};
 pub use node::{BehaviorInspectorNode, BehaviorInspectorNodeAttributes};
+pub use stats::{BehaviorStatsView, BehaviorStatsViewAttributes};
+pub use timeline::{BehaviorTimeline, BehaviorTimelineAttributes};
 
 pub mod inspector;
 pub mod node;
+pub mod stats;
+pub mod timeline;
 
 pub struct BehaviorInspectorPlugin;
 
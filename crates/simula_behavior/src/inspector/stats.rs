@@ -0,0 +1,91 @@
+use crate::stats::BehaviorStats;
+use bevy::prelude::*;
+use bevy_inspector_egui::{egui, Context, Inspectable};
+
+#[derive(Default, Clone)]
+pub struct BehaviorStatsViewAttributes;
+
+/// Counters per node type and timings per node system, of all behavior trees
+#[derive(Default, Clone)]
+pub struct BehaviorStatsView;
+
+fn ratio(value: Option<f64>) -> String {
+    value.map_or("-".to_string(), |value| format!("{:.0}%", value * 100.0))
+}
+
+fn seconds(value: Option<f64>) -> String {
+    value.map_or("-".to_string(), |value| format!("{:.3}s", value))
+}
+
+impl Inspectable for BehaviorStatsView {
+    type Attributes = BehaviorStatsViewAttributes;
+
+    fn ui(&mut self, ui: &mut egui::Ui, _options: Self::Attributes, context: &mut Context) -> bool {
+        let world = match unsafe { context.world_mut() } {
+            Some(world) => world,
+            None => return false,
+        };
+        let mut stats = match world.get_resource_mut::<BehaviorStats>() {
+            Some(stats) => stats,
+            None => return false,
+        };
+
+        ui.horizontal(|ui| {
+            if ui.button("Export CSV").clicked() {
+                if let Err(err) = stats.write_csv("behavior_nodes.csv", "behavior_systems.csv") {
+                    warn!("Failed to export behavior stats: {}", err);
+                }
+            }
+            if ui.button("Reset").clicked() {
+                stats.clear();
+            }
+            let mut profiling = stats.profiling();
+            if ui.checkbox(&mut profiling, "Profile systems").changed() {
+                stats.set_profiling(profiling);
+            }
+        });
+
+        ui.collapsing("Nodes", |ui| {
+            egui::Grid::new("behavior_stats_nodes")
+                .striped(true)
+                .show(ui, |ui| {
+                    for header in [
+                        "Behavior", "Started", "Success", "Failure", "Stopped", "Ratio", "Mean",
+                    ] {
+                        ui.strong(header);
+                    }
+                    ui.end_row();
+                    for (name, node) in stats.nodes() {
+                        ui.label(name);
+                        ui.label(node.activations.to_string());
+                        ui.label(node.successes.to_string());
+                        ui.label(node.failures.to_string());
+                        ui.label(node.stops.to_string());
+                        ui.label(ratio(node.success_ratio()));
+                        ui.label(seconds(node.mean_duration()));
+                        ui.end_row();
+                    }
+                });
+        });
+
+        ui.collapsing("Systems", |ui| {
+            egui::Grid::new("behavior_stats_systems")
+                .striped(true)
+                .show(ui, |ui| {
+                    for header in ["System", "Runs", "Mean", "Last frame", "Max frame"] {
+                        ui.strong(header);
+                    }
+                    ui.end_row();
+                    for system in stats.systems() {
+                        ui.label(&system.name);
+                        ui.label(system.runs.to_string());
+                        ui.label(format!("{:.3}ms", system.mean().as_secs_f64() * 1000.0));
+                        ui.label(format!("{:.3}ms", system.last_frame.as_secs_f64() * 1000.0));
+                        ui.label(format!("{:.3}ms", system.max_frame.as_secs_f64() * 1000.0));
+                        ui.end_row();
+                    }
+                });
+        });
+        false
+    }
+}
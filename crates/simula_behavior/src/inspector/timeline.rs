@@ -0,0 +1,158 @@
+use crate::{
+    clock::BehaviorClock,
+    recorder::{BehaviorEvent, BehaviorRecorder, BehaviorTransition},
+};
+use bevy::prelude::*;
+use bevy_inspector_egui::{egui, Context, Inspectable};
+
+/// A span of time a node was running, and how it ended
+#[derive(Debug, Clone, PartialEq)]
+pub struct TimelineInterval {
+    pub start: f64,
+    /// None while still running
+    pub end: Option<f64>,
+    pub transition: Option<BehaviorTransition>,
+}
+
+/// Running intervals of one node
+#[derive(Debug, Clone, PartialEq)]
+pub struct TimelineRow {
+    pub node: Entity,
+    pub name: String,
+    pub intervals: Vec<TimelineInterval>,
+}
+
+/// Group recorded events into running intervals per node, in order of first start
+pub fn timeline_rows<'a>(events: impl Iterator<Item = &'a BehaviorEvent>) -> Vec<TimelineRow> {
+    let mut rows: Vec<TimelineRow> = vec![];
+    for event in events {
+        let index = match rows.iter().position(|row| row.node == event.node) {
+            Some(index) => index,
+            None => {
+                rows.push(TimelineRow {
+                    node: event.node,
+                    name: event.name.clone(),
+                    intervals: vec![],
+                });
+                rows.len() - 1
+            }
+        };
+        let intervals = &mut rows[index].intervals;
+        match event.transition {
+            BehaviorTransition::Started => intervals.push(TimelineInterval {
+                start: event.time,
+                end: None,
+                transition: None,
+            }),
+            transition => match intervals.last_mut() {
+                Some(interval) if interval.end.is_none() => {
+                    interval.end = Some(event.time);
+                    interval.transition = Some(transition);
+                }
+                // Started before the oldest recorded event
+                _ => intervals.push(TimelineInterval {
+                    start: event.time,
+                    end: Some(event.time),
+                    transition: Some(transition),
+                }),
+            },
+        }
+    }
+    rows
+}
+
+fn interval_color(interval: &TimelineInterval) -> egui::Color32 {
+    match interval.transition {
+        None => egui::Color32::GREEN,
+        Some(BehaviorTransition::Success) => egui::Color32::DARK_GREEN,
+        Some(BehaviorTransition::Failure) => egui::Color32::RED,
+        Some(_) => egui::Color32::GRAY,
+    }
+}
+
+#[derive(Default, Clone)]
+pub struct BehaviorTimelineAttributes;
+
+/// Timeline of a behavior tree, each node running intervals shown as bars
+#[derive(Default, Clone)]
+pub struct BehaviorTimeline {
+    pub tree: Option<Entity>,
+}
+
+macro_rules! some_or_return {
+    ( $e:expr ) => {
+        match $e {
+            Some(x) => x,
+            None => return false,
+        }
+    };
+}
+
+impl Inspectable for BehaviorTimeline {
+    type Attributes = BehaviorTimelineAttributes;
+
+    fn ui(&mut self, ui: &mut egui::Ui, _options: Self::Attributes, context: &mut Context) -> bool {
+        let tree = some_or_return!(self.tree);
+        let world = some_or_return!(unsafe { context.world_mut() });
+        let now = world
+            .get_resource::<BehaviorClock>()
+            .map_or(0.0, |clock| clock.elapsed());
+        let mut recorder = some_or_return!(world.get_resource_mut::<BehaviorRecorder>());
+        let rows = timeline_rows(recorder.tree_events(tree));
+
+        ui.horizontal(|ui| {
+            // Recording is off until asked for
+            let mut enabled = recorder.enabled;
+            if ui.checkbox(&mut enabled, "Record").changed() {
+                recorder.enabled = enabled;
+            }
+            ui.label(format!("{} events", recorder.len()));
+            if ui.button("Export JSON lines").clicked() {
+                if let Err(err) = recorder.write_json_lines("behavior_trace.jsonl") {
+                    warn!("Failed to export behavior trace: {}", err);
+                }
+            }
+        });
+
+        let start = rows
+            .iter()
+            .flat_map(|row| row.intervals.iter())
+            .map(|interval| interval.start)
+            .fold(now, f64::min);
+        let span = (now - start).max(f64::EPSILON);
+
+        let label_width = 150.0;
+        let row_height = 16.0;
+        let width = ui.available_width().max(label_width + 100.0);
+        let (rect, _response) = ui.allocate_exact_size(
+            egui::Vec2::new(width, row_height * rows.len() as f32),
+            egui::Sense::hover(),
+        );
+        let painter = ui.painter_at(rect);
+        let bars = rect.width() - label_width;
+        let x = |time: f64| rect.left() + label_width + ((time - start) / span) as f32 * bars;
+        for (index, row) in rows.iter().enumerate() {
+            let top = rect.top() + row_height * index as f32;
+            painter.text(
+                egui::Pos2::new(rect.left(), top + row_height / 2.0),
+                egui::Align2::LEFT_CENTER,
+                format!("[{}] {}", row.node.id(), row.name),
+                egui::FontId::proportional(12.0),
+                ui.visuals().text_color(),
+            );
+            for interval in row.intervals.iter() {
+                let left = x(interval.start);
+                let right = x(interval.end.unwrap_or(now)).max(left + 2.0);
+                painter.rect_filled(
+                    egui::Rect::from_min_max(
+                        egui::Pos2::new(left, top + 2.0),
+                        egui::Pos2::new(right, top + row_height - 2.0),
+                    ),
+                    egui::Rounding::same(2.0),
+                    interval_color(interval),
+                );
+            }
+        }
+        false
+    }
+}
@@ -0,0 +1,457 @@
+use crate::{
+    asset::{BTNode, BehaviorDocument},
+    blackboard::BlackboardValue,
+    clock::BehaviorClock,
+    net::BehaviorNodeState,
+    BehaviorBlackboard, BehaviorSpawner, BehaviorTreeControl,
+};
+use bevy::{
+    ecs::system::Command,
+    prelude::*,
+    utils::{HashMap, HashSet},
+};
+use std::{
+    collections::BTreeMap,
+    marker::PhantomData,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+};
+
+static NEXT_DEFINITION: AtomicU64 = AtomicU64::new(0);
+
+/// Identifies a definition, trees can only be reused for the definition they came from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct BehaviorDefinitionId(u64);
+
+/// A node of a definition, parent and children are indices into the definition nodes
+#[derive(Debug, Clone)]
+pub struct BehaviorDefinitionNode<T> {
+    pub name: String,
+    pub behavior: T,
+    pub parent: Option<usize>,
+    pub children: Vec<usize>,
+    /// Index after the last node below this one, its descendants are the nodes in between
+    pub end: usize,
+}
+
+/// A behavior document flattened once, depth first from the root, and shared by every
+/// compact tree spawned from it
+#[derive(Debug)]
+pub struct BehaviorDefinition<T> {
+    id: BehaviorDefinitionId,
+    nodes: Vec<BehaviorDefinitionNode<T>>,
+    blackboard: BTreeMap<String, BlackboardValue>,
+}
+
+impl<T> BehaviorDefinition<T>
+where
+    T: Default + BehaviorSpawner,
+{
+    pub fn from_document(document: BehaviorDocument<T>) -> Arc<Self> {
+        let mut nodes = vec![];
+        Self::flatten(document.root, None, &mut nodes);
+        Arc::new(Self {
+            id: BehaviorDefinitionId(NEXT_DEFINITION.fetch_add(1, Ordering::Relaxed)),
+            nodes,
+            blackboard: document.blackboard,
+        })
+    }
+
+    fn flatten(node: BTNode<T>, parent: Option<usize>, nodes: &mut Vec<BehaviorDefinitionNode<T>>) {
+        let BTNode(name, behavior, children) = node;
+        let index = nodes.len();
+        nodes.push(BehaviorDefinitionNode {
+            name,
+            behavior,
+            parent,
+            children: vec![],
+            end: index + 1,
+        });
+        for child in children {
+            let child_index = nodes.len();
+            nodes[index].children.push(child_index);
+            Self::flatten(child, Some(index), nodes);
+        }
+        nodes[index].end = nodes.len();
+    }
+
+    pub fn id(&self) -> BehaviorDefinitionId {
+        self.id
+    }
+
+    pub fn nodes(&self) -> &[BehaviorDefinitionNode<T>] {
+        &self.nodes
+    }
+
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+}
+
+/// Data a node of a compact tree keeps between frames, in place of its component fields
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct BehaviorLocal {
+    /// A counter, like repeats or attempts
+    pub count: u64,
+    /// A tree time, like when the node started
+    pub time: f64,
+}
+
+/// What a node of a compact tree does this frame
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BehaviorTick {
+    Running,
+    Success,
+    Failure,
+    /// Run a child, by its position among the node children. A child that completed is
+    /// made idle first, and the node runs again to see it idle.
+    Child(usize),
+}
+
+impl BehaviorTick {
+    pub fn complete(success: bool) -> Self {
+        if success {
+            Self::Success
+        } else {
+            Self::Failure
+        }
+    }
+}
+
+/// What a node of a compact tree sees when it runs
+pub struct BehaviorTickContext<'a> {
+    /// First time the node runs since it started
+    pub entered: bool,
+    pub local: &'a mut BehaviorLocal,
+    pub blackboard: &'a mut BehaviorBlackboard,
+    /// Time of the tree, in seconds
+    pub time: f64,
+    children: &'a [usize],
+    states: &'a [BehaviorNodeState],
+}
+
+impl<'a> BehaviorTickContext<'a> {
+    /// Number of children of the node
+    pub fn children(&self) -> usize {
+        self.children.len()
+    }
+
+    /// State of a child, by its position among the node children
+    pub fn child(&self, position: usize) -> BehaviorNodeState {
+        self.children
+            .get(position)
+            .map_or(BehaviorNodeState::Idle, |child| self.states[*child])
+    }
+}
+
+/// A behavior tree of a single entity: the definition is shared, only the state of every
+/// node and their local data belong to the tree. Lives on the tree entity, next to its
+/// blackboard and control.
+#[derive(Component)]
+pub struct BehaviorInstance<T>
+where
+    T: Send + Sync + 'static,
+{
+    definition: Arc<BehaviorDefinition<T>>,
+    /// State of every node, in definition order
+    states: Vec<BehaviorNodeState>,
+    /// Local data of every node, in definition order
+    locals: Vec<BehaviorLocal>,
+}
+
+impl<T> BehaviorInstance<T>
+where
+    T: Default + BehaviorSpawner + Send + Sync + 'static,
+{
+    pub fn new(definition: &Arc<BehaviorDefinition<T>>) -> Self {
+        Self {
+            definition: definition.clone(),
+            states: vec![BehaviorNodeState::Idle; definition.len()],
+            locals: vec![BehaviorLocal::default(); definition.len()],
+        }
+    }
+
+    /// Spawn a tree entity running a definition
+    pub fn spawn(commands: &mut Commands, definition: &Arc<BehaviorDefinition<T>>) -> Entity {
+        commands
+            .spawn()
+            .insert(Self::new(definition))
+            .insert(BehaviorBlackboard::from_values(&definition.blackboard))
+            .insert(BehaviorTreeControl::default())
+            .id()
+    }
+
+    pub fn definition(&self) -> &Arc<BehaviorDefinition<T>> {
+        &self.definition
+    }
+
+    /// State of every node, in definition order
+    pub fn states(&self) -> &[BehaviorNodeState] {
+        &self.states
+    }
+
+    /// Local data of every node, in definition order
+    pub fn locals(&self) -> &[BehaviorLocal] {
+        &self.locals
+    }
+
+    /// State of the root node, which is the state of the tree
+    pub fn state(&self) -> BehaviorNodeState {
+        self.states
+            .first()
+            .copied()
+            .unwrap_or(BehaviorNodeState::Success)
+    }
+
+    /// Start over as if just spawned, node local data included
+    pub fn reset(&mut self) {
+        self.states.fill(BehaviorNodeState::Idle);
+        self.locals.fill(BehaviorLocal::default());
+    }
+
+    /// Run the tree for a frame at tree time `time`, returns the state of the tree. A tree
+    /// that completed stays completed until reset.
+    pub fn tick(&mut self, blackboard: &mut BehaviorBlackboard, time: f64) -> BehaviorNodeState {
+        match self.state() {
+            BehaviorNodeState::Idle | BehaviorNodeState::Running => {
+                let Self {
+                    definition,
+                    states,
+                    locals,
+                } = self;
+                tick_node(definition, states, locals, blackboard, time, 0)
+            }
+            state => state,
+        }
+    }
+}
+
+/// Run a node until it completes or waits for the next frame. Every child runs at most
+/// once per frame, so repeating nodes wait for the next frame to start over.
+fn tick_node<T>(
+    definition: &BehaviorDefinition<T>,
+    states: &mut [BehaviorNodeState],
+    locals: &mut [BehaviorLocal],
+    blackboard: &mut BehaviorBlackboard,
+    time: f64,
+    index: usize,
+) -> BehaviorNodeState
+where
+    T: Default + BehaviorSpawner,
+{
+    let node = &definition.nodes[index];
+    let mut entered = states[index] == BehaviorNodeState::Idle;
+    if entered {
+        // Children start over with their parent
+        states[index..node.end].fill(BehaviorNodeState::Idle);
+        states[index] = BehaviorNodeState::Running;
+    }
+    let mut runs = 0;
+    loop {
+        let mut context = BehaviorTickContext {
+            entered,
+            local: &mut locals[index],
+            blackboard: &mut *blackboard,
+            time,
+            children: &node.children,
+            states: &*states,
+        };
+        let tick = node.behavior.tick_compact(&mut context).unwrap_or_else(|| {
+            warn!("Can't run in a compact tree: {}", node.name);
+            BehaviorTick::Failure
+        });
+        entered = false;
+        let state = match tick {
+            BehaviorTick::Running => return BehaviorNodeState::Running,
+            BehaviorTick::Success => BehaviorNodeState::Success,
+            BehaviorTick::Failure => BehaviorNodeState::Failure,
+            BehaviorTick::Child(position) => {
+                let child = match node.children.get(position) {
+                    Some(child) => *child,
+                    None => {
+                        warn!("No child at {}: {}", position, node.name);
+                        states[index] = BehaviorNodeState::Failure;
+                        stop_children(definition, states, index);
+                        return BehaviorNodeState::Failure;
+                    }
+                };
+                if matches!(
+                    states[child],
+                    BehaviorNodeState::Success | BehaviorNodeState::Failure
+                ) {
+                    // Start the child over, the node sees it idle when it runs again
+                    states[child] = BehaviorNodeState::Idle;
+                    runs += 1;
+                    if runs > node.children.len() {
+                        return BehaviorNodeState::Running;
+                    }
+                    continue;
+                }
+                match tick_node(definition, states, locals, blackboard, time, child) {
+                    BehaviorNodeState::Running => return BehaviorNodeState::Running,
+                    // The node sees the result of its child in the same frame
+                    _ => continue,
+                }
+            }
+        };
+        states[index] = state;
+        stop_children(definition, states, index);
+        return state;
+    }
+}
+
+/// Stop the nodes still running below a completed node
+fn stop_children<T>(definition: &BehaviorDefinition<T>, states: &mut [BehaviorNodeState], index: usize) {
+    for state in states[index + 1..definition.nodes[index].end].iter_mut() {
+        if *state == BehaviorNodeState::Running {
+            *state = BehaviorNodeState::Idle;
+        }
+    }
+}
+
+/// Run the compact trees of a behavior enum, on the time of each tree
+pub fn run_instances<T>(
+    clock: Res<BehaviorClock>,
+    mut instances: Query<(
+        &mut BehaviorInstance<T>,
+        &mut BehaviorBlackboard,
+        &mut BehaviorTreeControl,
+    )>,
+) where
+    T: Default + BehaviorSpawner + Send + Sync + 'static,
+{
+    let now = clock.elapsed();
+    for (mut instance, mut blackboard, mut control) in &mut instances {
+        if control.advance(now) {
+            continue;
+        }
+        if control.take_restart() {
+            instance.reset();
+        }
+        instance.tick(&mut blackboard, now - control.paused_time());
+    }
+}
+
+/// Reset a reused tree in place, keeping its state buffers
+struct ResetBehaviorInstance<T> {
+    tree: Entity,
+    phantom: PhantomData<fn() -> T>,
+}
+
+impl<T> Command for ResetBehaviorInstance<T>
+where
+    T: Default + BehaviorSpawner + Send + Sync + 'static,
+{
+    fn write(self, world: &mut World) {
+        if let Some(mut instance) = world.get_mut::<BehaviorInstance<T>>(self.tree) {
+            instance.reset();
+        }
+    }
+}
+
+/// Compact trees released when their agents go away, reused for new trees of the same
+/// definition instead of spawning new entities and state
+#[derive(Debug)]
+pub struct BehaviorTreePool {
+    /// Maximum number of trees kept per definition, others are despawned on release
+    pub capacity: usize,
+    free: HashMap<BehaviorDefinitionId, Vec<Entity>>,
+    released: HashSet<Entity>,
+}
+
+impl Default for BehaviorTreePool {
+    fn default() -> Self {
+        Self::new(1024)
+    }
+}
+
+impl BehaviorTreePool {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            free: default(),
+            released: default(),
+        }
+    }
+
+    /// A running tree of the definition, reused from the pool when one was released
+    pub fn spawn<T>(&mut self, commands: &mut Commands, definition: &Arc<BehaviorDefinition<T>>) -> Entity
+    where
+        T: Default + BehaviorSpawner + Send + Sync + 'static,
+    {
+        let pooled = self
+            .free
+            .get_mut(&definition.id())
+            .and_then(|trees| trees.pop());
+        match pooled {
+            Some(tree) => {
+                self.released.remove(&tree);
+                commands.add(ResetBehaviorInstance::<T> {
+                    tree,
+                    phantom: PhantomData,
+                });
+                commands
+                    .entity(tree)
+                    .insert(BehaviorBlackboard::from_values(&definition.blackboard))
+                    .insert(BehaviorTreeControl::default());
+                tree
+            }
+            None => BehaviorInstance::spawn(commands, definition),
+        }
+    }
+
+    /// Pause a tree of a definition and keep it for later. It is taken from `parent` first,
+    /// so it outlives the parent being despawned.
+    pub fn release(
+        &mut self,
+        commands: &mut Commands,
+        tree: Entity,
+        definition: BehaviorDefinitionId,
+        parent: Option<Entity>,
+    ) {
+        if let Some(parent) = parent {
+            commands.entity(parent).remove_children(&[tree]);
+        }
+        if !self.released.insert(tree) {
+            return;
+        }
+        let trees = self.free.entry(definition).or_default();
+        if trees.len() >= self.capacity {
+            self.released.remove(&tree);
+            commands.entity(tree).despawn_recursive();
+            return;
+        }
+        commands.entity(tree).insert(BehaviorTreeControl::paused());
+        trees.push(tree);
+    }
+
+    /// Number of released trees of a definition, ready to be reused
+    pub fn available(&self, definition: BehaviorDefinitionId) -> usize {
+        self.free.get(&definition).map_or(0, |trees| trees.len())
+    }
+
+    /// Number of released trees, of all definitions
+    pub fn len(&self) -> usize {
+        self.released.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.released.is_empty()
+    }
+
+    /// Despawn all released trees
+    pub fn clear(&mut self, commands: &mut Commands) {
+        for (_, trees) in self.free.drain() {
+            for tree in trees {
+                commands.entity(tree).despawn_recursive();
+            }
+        }
+        self.released.clear();
+    }
+}
@@ -1,33 +1,84 @@
+extern crate self as simula_behavior;
+
 use actions::*;
-use asset::{BTNode, BehaviorAsset, BehaviorAssetLoader, BehaviorAssetLoading, BehaviorDocument};
-use bevy::{ecs::query::WorldQuery, ecs::system::EntityCommands, prelude::*, reflect::TypeUuid};
-use bevy_inspector_egui::{Inspectable, RegisterInspectable};
+use asset::{
+    BTNode, BehaviorAsset, BehaviorAssetLoader, BehaviorAssetLoading, BehaviorDocument,
+    BehaviorLoadFailed, BehaviorReload,
+};
+use bevy::{
+    ecs::query::WorldQuery, ecs::system::EntityCommands, prelude::*, reflect::GetTypeRegistration,
+    reflect::TypeUuid,
+};
+use bevy_inspector_egui::{egui, Context, Inspectable, RegisterInspectable};
+pub use blackboard::BehaviorBlackboard;
+use clock::{update_clock, BehaviorClock};
 use composites::*;
+use control::{update_tree_control, BehaviorPaused, BehaviorTreeControl};
 use decorators::*;
 use inspector::BehaviorInspectorPlugin;
+use instance::{BehaviorTick, BehaviorTickContext};
+use recorder::{update_recorder, BehaviorRecorder, BehaviorRecording, BehaviorTransition};
+use registry::{BehaviorFlow, BehaviorRegistry};
+use serde::{Deserialize, Serialize};
+pub use simula_behavior_macro::{behavior_builtins, BehaviorSpawner};
+use stats::{update_stats, BehaviorStats};
+pub use step::BehaviorAppExt;
+use step::{run_behavior_nodes, run_to_quiescence, BehaviorExecution, BehaviorTransitions};
 
 pub mod actions;
 pub mod asset;
+pub mod blackboard;
+pub mod clock;
 pub mod color_hex_utils;
 pub mod composites;
+pub mod control;
 pub mod decorators;
+pub mod editor;
+pub mod event;
 pub mod inspector;
+pub mod instance;
+pub mod lint;
+pub mod net;
+pub mod recorder;
+pub mod registry;
+pub mod stats;
+pub mod step;
 pub mod test;
 
 pub mod prelude {
     pub use crate::actions::*;
     pub use crate::asset::{
-        behavior_loader, BTNode, BehaviorAsset, BehaviorAssetLoader, BehaviorAssetLoading,
-        BehaviorDocument,
+        behavior_loader, behavior_reloader, BTNode, BehaviorAsset, BehaviorAssetLoaded,
+        BehaviorAssetLoader, BehaviorAssetLoading, BehaviorDocument, BehaviorDocumentError,
+        BehaviorLoadFailed, BehaviorReload, BehaviorSource,
     };
+    pub use crate::blackboard::{BehaviorBlackboard, BehaviorProp, BlackboardValue};
+    pub use crate::clock::{BehaviorClock, BehaviorClockMode};
     pub use crate::composites::*;
+    pub use crate::control::{BehaviorPaused, BehaviorTime, BehaviorTreeControl};
     pub use crate::decorators::*;
+    pub use crate::editor::{BehaviorEditor, BehaviorEditorPlugin, BehaviorGraph};
+    pub use crate::event::{BehaviorEventInfo, BehaviorEventPlugin};
     pub use crate::inspector::BehaviorInspector;
+    pub use crate::instance::{
+        BehaviorDefinition, BehaviorInstance, BehaviorLocal, BehaviorTick, BehaviorTickContext,
+        BehaviorTreePool,
+    };
+    pub use crate::net::{
+        BehaviorNetPlugin, BehaviorNodeState, BehaviorProxy, BehaviorTreeSnapshot,
+        ReplicateBehaviorTree,
+    };
+    pub use crate::recorder::{BehaviorEvent, BehaviorRecorder, BehaviorTransition};
+    pub use crate::registry::{
+        BehaviorChildCount, BehaviorFlow, BehaviorRegistration, BehaviorRegistry,
+    };
+    pub use crate::stats::{BehaviorNodeStats, BehaviorStats, BehaviorSystemStats};
+    pub use crate::step::{BehaviorAppExt, BehaviorExecution, BehaviorSteps, BehaviorTransitions};
     pub use crate::{
-        BehaviorChildQuery, BehaviorChildQueryFilter, BehaviorChildQueryItem, BehaviorChildren,
-        BehaviorCursor, BehaviorFailure, BehaviorInfo, BehaviorNode, BehaviorParent,
-        BehaviorPlugin, BehaviorRunQuery, BehaviorRunning, BehaviorSpawner, BehaviorSuccess,
-        BehaviorTree, BehaviorType,
+        behavior_builtins, BehaviorChildQuery, BehaviorChildQueryFilter, BehaviorChildQueryItem,
+        BehaviorChildren, BehaviorCursor, BehaviorFailure, BehaviorInfo, BehaviorNode,
+        BehaviorParent, BehaviorPlugin, BehaviorRunQuery, BehaviorRunning, BehaviorSpawner,
+        BehaviorSpawnerPlugin, BehaviorStopped, BehaviorSuccess, BehaviorTree, BehaviorType,
     };
     pub use bevy_inspector_egui::{Inspectable, RegisterInspectable};
 }
@@ -71,6 +122,109 @@ where
 /// How to spawn a behavior node
 pub trait BehaviorSpawner {
     fn insert(&self, commands: &mut EntityCommands);
+
+    /// Read back the behavior node of an entity, if it is one of ours. Saving a spawned tree
+    /// as a document needs it, `#[derive(BehaviorSpawner)]` implements it for all variants.
+    fn from_entity(world: &World, entity: Entity) -> Option<Self>
+    where
+        Self: Sized;
+
+    /// Register node types, and systems of custom nodes
+    fn register(_app: &mut App)
+    where
+        Self: Sized,
+    {
+    }
+
+    /// Add the node types to a registry, like `register` does for the app registry
+    fn register_behaviors(_registry: &mut BehaviorRegistry)
+    where
+        Self: Sized,
+    {
+    }
+
+    /// Type of the behavior node, used to validate documents
+    fn behavior_type(&self) -> Option<BehaviorType> {
+        None
+    }
+
+    /// Remove any behavior node this spawner can insert, used before inserting another
+    fn remove(_commands: &mut EntityCommands)
+    where
+        Self: Sized,
+    {
+    }
+
+    /// Name of the behavior node, like `Sequencer`
+    fn behavior_name(&self) -> Option<&'static str> {
+        None
+    }
+
+    /// One default node of each behavior, offered when adding nodes in the editor
+    fn defaults() -> Vec<Self>
+    where
+        Self: Sized,
+    {
+        vec![]
+    }
+
+    /// Edit the fields of the behavior node
+    fn inspect(&mut self, _ui: &mut egui::Ui, _context: &mut Context) -> bool {
+        false
+    }
+
+    /// Run the behavior node in a compact tree, `None` if it only runs as an entity
+    fn tick_compact(&self, _context: &mut BehaviorTickContext) -> Option<BehaviorTick> {
+        None
+    }
+}
+
+/// Register type, inspectable and `BehaviorRegistry` entry of a behavior node
+pub fn register_behavior<T>(app: &mut App)
+where
+    T: BehaviorInfo + GetTypeRegistration,
+{
+    app.register_type::<T>()
+        .register_inspectable::<T>()
+        .init_resource::<BehaviorRegistry>();
+    app.world.resource_mut::<BehaviorRegistry>().register::<T>();
+}
+
+/// Marks the subtree system as added, subtrees are loaded as the first document type
+/// registered with `BehaviorSpawnerPlugin`
+struct BehaviorSubtreeSystem;
+
+/// Load, spawn and register behaviors of a document type
+pub struct BehaviorSpawnerPlugin<T>(std::marker::PhantomData<fn() -> T>);
+
+impl<T> Default for BehaviorSpawnerPlugin<T> {
+    fn default() -> Self {
+        Self(default())
+    }
+}
+
+impl<T> Plugin for BehaviorSpawnerPlugin<T>
+where
+    T: BehaviorSpawner
+        + TypeUuid
+        + Send
+        + Sync
+        + 'static
+        + Default
+        + std::fmt::Debug
+        + for<'de> Deserialize<'de>,
+{
+    fn build(&self, app: &mut App) {
+        app.add_system(asset::behavior_loader::<T>)
+            .add_system(asset::behavior_reloader::<T>)
+            .add_system(instance::run_instances::<T>);
+        // Subtree nodes are shared by all document types, only one system may load them
+        if !app.world.contains_resource::<BehaviorSubtreeSystem>() {
+            app.insert_resource(BehaviorSubtreeSystem)
+                .add_behavior_system(subtree::run::<T>);
+        }
+        T::register(app);
+    }
 }
 
 impl Plugin for BehaviorPlugin {
@@ -82,45 +236,91 @@ impl Plugin for BehaviorPlugin {
             .register_type::<BehaviorRunning>()
             .register_type::<BehaviorFailure>()
             .register_type::<BehaviorCursor>()
-            .register_type::<Debug>()
-            .register_type::<Delay>()
-            .register_type::<Selector>()
-            .register_type::<Sequencer>()
-            .register_type::<All>()
-            .register_type::<Any>()
-            .register_type::<Inverter>()
-            .register_type::<Repeater>()
-            .register_type::<Succeeder>()
+            .register_type::<BehaviorStopped>()
+            .register_type::<BehaviorPaused>()
+            .register_type::<BehaviorTreeControl>()
+            .register_type::<UtilityScore>()
             .register_inspectable::<BehaviorTree>()
             .register_inspectable::<BehaviorNode>()
             .register_inspectable::<BehaviorSuccess>()
             .register_inspectable::<BehaviorRunning>()
             .register_inspectable::<BehaviorFailure>()
             .register_inspectable::<BehaviorCursor>()
-            .register_inspectable::<Debug>()
-            .register_inspectable::<Delay>()
-            .register_inspectable::<Selector>()
-            .register_inspectable::<Sequencer>()
-            .register_inspectable::<All>()
-            .register_inspectable::<Any>()
-            .register_inspectable::<Inverter>()
-            .register_inspectable::<Repeater>()
-            .register_inspectable::<Succeeder>()
+            .register_inspectable::<BehaviorStopped>()
+            .register_inspectable::<BehaviorBlackboard>()
+            .register_inspectable::<UtilityScore>()
             .add_asset::<BehaviorAsset>()
             .init_asset_loader::<BehaviorAssetLoader>()
+            .add_event::<BehaviorLoadFailed>()
+            .init_resource::<BehaviorClock>()
+            .add_system_to_stage(CoreStage::First, update_clock.chain(update_tree_control))
+            .add_system_to_stage(
+                CoreStage::PostUpdate,
+                stop_behavior.chain(update_behavior.chain(complete_behavior.chain(start_behavior))),
+            )
+            .init_resource::<BehaviorExecution>()
+            .init_resource::<BehaviorTransitions>()
+            .init_resource::<BehaviorRecorder>()
+            .add_system_to_stage(CoreStage::First, update_recorder)
+            .init_resource::<BehaviorStats>()
+            .init_resource::<BehaviorTreePool>()
+            .add_system_to_stage(CoreStage::Last, update_stats)
+            .add_system(run_behavior_nodes.exclusive_system().at_start())
             .add_system_to_stage(
                 CoreStage::PostUpdate,
-                update_behavior.chain(complete_behavior.chain(start_behavior)),
+                run_to_quiescence.exclusive_system().at_end(),
             )
-            .add_system(sequencer::run)
-            .add_system(selector::run)
-            .add_system(all::run)
-            .add_system(any::run)
-            .add_system(repeater::run)
-            .add_system(inverter::run)
-            .add_system(succeeder::run)
-            .add_system(delay::run)
-            .add_system(debug::run);
+            .add_behavior_system(sequencer::run)
+            .add_behavior_system(selector::run)
+            .add_behavior_system(all::run)
+            .add_behavior_system(any::run)
+            .add_behavior_system(parallel::run)
+            .add_behavior_system(utility_selector::run)
+            .add_behavior_system(weighted_selector::run)
+            .add_behavior_system(reactive_sequence::run)
+            .add_behavior_system(reactive_selector::run)
+            .add_behavior_system(repeater::run)
+            .add_behavior_system(inverter::run)
+            .add_behavior_system(succeeder::run)
+            .add_behavior_system(observer::run)
+            .add_behavior_system(timeout::run)
+            .add_behavior_system(cooldown::run)
+            .add_behavior_system(retry::run)
+            .add_behavior_system(force_failure::run)
+            .add_behavior_system(limit::run)
+            .add_behavior_system(delay::run)
+            .add_behavior_system(debug::run)
+            .add_behavior_system(condition::run)
+            .add_behavior_system(set_blackboard::run)
+            .add_behavior_system(drive_signal::run)
+            .add_behavior_system(sample_signal::run);
+
+        // Built-in nodes, listed in the registry for tooling
+        register_behavior::<Debug>(app);
+        register_behavior::<Delay>(app);
+        register_behavior::<Condition>(app);
+        register_behavior::<SetBlackboard>(app);
+        register_behavior::<DriveSignal>(app);
+        register_behavior::<SampleSignal>(app);
+        register_behavior::<Selector>(app);
+        register_behavior::<Sequencer>(app);
+        register_behavior::<All>(app);
+        register_behavior::<Any>(app);
+        register_behavior::<Parallel>(app);
+        register_behavior::<UtilitySelector>(app);
+        register_behavior::<WeightedSelector>(app);
+        register_behavior::<ReactiveSequence>(app);
+        register_behavior::<ReactiveSelector>(app);
+        register_behavior::<Inverter>(app);
+        register_behavior::<Repeater>(app);
+        register_behavior::<Succeeder>(app);
+        register_behavior::<Subtree>(app);
+        register_behavior::<Observer>(app);
+        register_behavior::<Timeout>(app);
+        register_behavior::<Cooldown>(app);
+        register_behavior::<Retry>(app);
+        register_behavior::<ForceFailure>(app);
+        register_behavior::<Limit>(app);
     }
 }
 
@@ -177,7 +377,9 @@ pub struct BehaviorParent(Option<Entity>);
 pub struct BehaviorChildren(Vec<Entity>);
 
 /// A component added to identify the type of a behavior node
-#[derive(Debug, Default, PartialEq, Reflect, Clone, Component, Inspectable)]
+#[derive(
+    Debug, Default, PartialEq, Reflect, Clone, Component, Inspectable, Serialize, Deserialize,
+)]
 #[reflect(Component)]
 pub enum BehaviorType {
     #[default]
@@ -194,6 +396,8 @@ where
     const TYPE: BehaviorType;
     const NAME: &'static str;
     const DESC: &'static str;
+    /// How the node runs its children, read by the linter
+    const FLOW: BehaviorFlow = BehaviorFlow::NONE;
 
     fn insert(commands: &mut EntityCommands) {
         commands.insert_bundle(BehaviorBundle::<Self>::default());
@@ -205,6 +409,12 @@ where
             ..Default::default()
         });
     }
+
+    /// Run in a compact tree, on node local data instead of component fields. `None` if
+    /// the node only runs as an entity.
+    fn tick_compact(&self, _context: &mut BehaviorTickContext) -> Option<BehaviorTick> {
+        None
+    }
 }
 
 pub fn add_children(commands: &mut Commands, parent: Entity, children: &[Entity]) {
@@ -231,6 +441,19 @@ impl BehaviorTree {
         commands: &mut Commands,
         document: Handle<BehaviorAsset>,
     ) -> Self
+    where
+        T: TypeUuid + Send + Sync + 'static + Default + std::fmt::Debug,
+    {
+        Self::from_asset_with::<T>(parent, commands, document, BehaviorReload::default())
+    }
+
+    /// Spawn from behavior asset, choosing how the tree follows changes to the asset
+    pub fn from_asset_with<T>(
+        parent: Option<Entity>,
+        commands: &mut Commands,
+        document: Handle<BehaviorAsset>,
+        reload: BehaviorReload,
+    ) -> Self
     where
         T: TypeUuid + Send + Sync + 'static + Default + std::fmt::Debug,
     {
@@ -239,6 +462,7 @@ impl BehaviorTree {
             .insert(BehaviorAssetLoading::<T> {
                 document,
                 parent,
+                reload,
                 ..default()
             })
             .id();
@@ -276,20 +500,87 @@ impl BehaviorTree {
     ) -> Entity
     where
         T: Default + BehaviorSpawner,
+    {
+        Self::insert_tree_with(entity, parent, commands, node, &mut |_, _, _| {})
+    }
+
+    /// Spawn a behavior tree from a behavior node, calling `inserted` with the entity and
+    /// parent of every node, parents first
+    pub fn insert_tree_with<T, F>(
+        entity: Entity,
+        parent: Option<Entity>,
+        commands: &mut Commands,
+        node: &BTNode<T>,
+        inserted: &mut F,
+    ) -> Entity
+    where
+        T: Default + BehaviorSpawner,
+        F: FnMut(Entity, Option<Entity>, &BTNode<T>),
     {
         let BTNode(name, node_type, nodes) = node;
+        inserted(entity, parent, node);
         let mut entity_commands = commands.entity(entity);
         node_type.insert(&mut entity_commands);
         entity_commands.insert(Name::new(name.clone()));
         entity_commands.insert(BehaviorParent(parent));
         let children = nodes
             .iter()
-            .map(|node| Self::insert_tree(commands.spawn().id(), Some(entity), commands, node))
+            .map(|node| {
+                Self::insert_tree_with(
+                    commands.spawn().id(),
+                    Some(entity),
+                    commands,
+                    node,
+                    inserted,
+                )
+            })
             .collect::<Vec<Entity>>();
         add_children(commands, entity, &children);
         entity
     }
 
+    /// Create a document from the spawned tree, the inverse of `from_document`
+    pub fn to_document<T>(&self, world: &World) -> Option<BehaviorDocument<T>>
+    where
+        T: Default + BehaviorSpawner,
+    {
+        let root = self.root?;
+        let blackboard = world
+            .get::<BehaviorNode>(root)
+            .and_then(|node| node.tree)
+            .and_then(|tree| world.get::<BehaviorBlackboard>(tree))
+            .map(|blackboard| blackboard.to_values())
+            .unwrap_or_default();
+        Some(BehaviorDocument {
+            root: Self::node_to_document(world, root)?,
+            blackboard,
+        })
+    }
+
+    /// Create a document node from a spawned behavior node and its children
+    pub fn node_to_document<T>(world: &World, entity: Entity) -> Option<BTNode<T>>
+    where
+        T: Default + BehaviorSpawner,
+    {
+        let name = world.get::<Name>(entity)?.to_string();
+        let data = T::from_entity(world, entity);
+        if data.is_none() {
+            warn!("Unknown behavior node: {}", name);
+        }
+        // Subtree children come from their own asset
+        let children = if world.get::<Subtree>(entity).is_some() {
+            vec![]
+        } else if let Some(children) = world.get::<BehaviorChildren>(entity) {
+            children
+                .iter()
+                .map(|child| Self::node_to_document(world, *child))
+                .collect::<Option<Vec<BTNode<T>>>>()?
+        } else {
+            vec![]
+        };
+        Some(BTNode(name, data?, children))
+    }
+
     /// Spawn a behavior tree from a behavior node
     pub fn spawn_tree<T>(
         parent: Option<Entity>,
@@ -313,6 +604,7 @@ pub struct BehaviorRunQuery {
     _running: With<BehaviorRunning>,
     _failure: Without<BehaviorFailure>,
     _success: Without<BehaviorSuccess>,
+    _paused: Without<BehaviorPaused>,
 }
 
 /// Query filter for behaviors ready to start
@@ -323,6 +615,7 @@ pub struct BehaviorReadyQuery {
     _running: Without<BehaviorRunning>,
     _failure: Without<BehaviorFailure>,
     _success: Without<BehaviorSuccess>,
+    _paused: Without<BehaviorPaused>,
 }
 
 /// Query filter for behaviors that have completed
@@ -332,6 +625,7 @@ pub struct BehaviorDoneQuery {
     _cursor: With<BehaviorCursor>,
     _running: With<BehaviorRunning>,
     _done: Or<(With<BehaviorFailure>, With<BehaviorSuccess>)>,
+    _paused: Without<BehaviorPaused>,
 }
 
 /// Query for behavior children
@@ -383,9 +677,16 @@ fn complete_behavior(
             With<BehaviorFailure>,
         )>,
     >,
+    stopping: Query<(&BehaviorParent, Option<&BehaviorStopped>)>,
     mut trace: Option<ResMut<BehaviorTrace>>,
+    mut transitions: Option<ResMut<BehaviorTransitions>>,
+    mut recording: BehaviorRecording,
 ) {
     for (entity, success, failure, parent, children, name) in &dones {
+        // Stopped branches are reset, not completed
+        if is_stopping(entity, &stopping) {
+            continue;
+        }
         let state = if success.is_some() {
             "SUCCESS"
         } else if failure.is_some() {
@@ -403,6 +704,15 @@ fn complete_behavior(
         if let Some(trace) = trace.as_mut() {
             trace.push(format!("[{}] {} {}", entity.id(), state, name.to_string(),));
         }
+        if let Some(transitions) = transitions.as_mut() {
+            transitions.increment();
+        }
+        let transition = if success.is_some() {
+            BehaviorTransition::Success
+        } else {
+            BehaviorTransition::Failure
+        };
+        recording.record(entity, name, transition);
         commands.entity(entity).remove::<BehaviorRunning>();
         commands.entity(entity).remove::<BehaviorCursor>();
 
@@ -431,9 +741,16 @@ fn start_behavior(
             With<BehaviorFailure>,
         )>,
     >,
+    stopping: Query<(&BehaviorParent, Option<&BehaviorStopped>)>,
     mut trace: Option<ResMut<BehaviorTrace>>,
+    mut transitions: Option<ResMut<BehaviorTransitions>>,
+    mut recording: BehaviorRecording,
 ) {
     for (entity, children, name) in &ready {
+        // Stopped branches are reset, not started
+        if is_stopping(entity, &stopping) {
+            continue;
+        }
         // Reset children
         reset_nodes(false, &mut commands, children, &nodes);
         // debug!("[{}] RESETNG {}", entity.id(), name.to_string());
@@ -441,10 +758,70 @@ fn start_behavior(
         if let Some(trace) = trace.as_mut() {
             trace.push(format!("[{}] STARTED {}", entity.id(), name.to_string(),));
         }
+        if let Some(transitions) = transitions.as_mut() {
+            transitions.increment();
+        }
+        recording.record(entity, name, BehaviorTransition::Started);
         commands.entity(entity).insert(BehaviorRunning::default());
     }
 }
 
+/// Process stopped behaviors, reset them and their children without completing
+fn stop_behavior(
+    mut commands: Commands,
+    stopped: Query<(Entity, &BehaviorChildren, &Name), (With<BehaviorNode>, With<BehaviorStopped>)>,
+    nodes: Query<
+        (Entity, &BehaviorChildren),
+        Or<(
+            With<BehaviorCursor>,
+            With<BehaviorRunning>,
+            With<BehaviorSuccess>,
+            With<BehaviorFailure>,
+        )>,
+    >,
+    mut trace: Option<ResMut<BehaviorTrace>>,
+    mut transitions: Option<ResMut<BehaviorTransitions>>,
+    mut recording: BehaviorRecording,
+) {
+    for (entity, children, name) in &stopped {
+        debug!("[{}] STOPPED {}", entity.id().to_string(), name.to_string());
+        if let Some(trace) = trace.as_mut() {
+            trace.push(format!("[{}] STOPPED {}", entity.id(), name.to_string(),));
+        }
+        if let Some(transitions) = transitions.as_mut() {
+            transitions.increment();
+        }
+        recording.record(entity, name, BehaviorTransition::Stopped);
+        commands.entity(entity).remove::<BehaviorStopped>();
+        commands.entity(entity).remove::<BehaviorCursor>();
+        commands.entity(entity).remove::<BehaviorRunning>();
+        commands.entity(entity).remove::<BehaviorSuccess>();
+        commands.entity(entity).remove::<BehaviorFailure>();
+
+        // Reset all children recursively
+        reset_nodes(true, &mut commands, children, &nodes);
+    }
+}
+
+/// A behavior is stopping if it, or any of its ancestors, is stopped
+fn is_stopping(
+    entity: Entity,
+    stopping: &Query<(&BehaviorParent, Option<&BehaviorStopped>)>,
+) -> bool {
+    let mut current = Some(entity);
+    while let Some(entity) = current {
+        if let Ok((parent, stopped)) = stopping.get(entity) {
+            if stopped.is_some() {
+                return true;
+            }
+            current = **parent;
+        } else {
+            break;
+        }
+    }
+    false
+}
+
 fn reset_nodes(
     recursively: bool,
     commands: &mut Commands,
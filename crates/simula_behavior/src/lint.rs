@@ -0,0 +1,471 @@
+use crate::{
+    decorators::{Repeat, Repeater},
+    registry::{BehaviorChildCount, BehaviorFlow, BehaviorRegistry},
+    BehaviorSpawner, BehaviorType,
+};
+use serde::{
+    de::{self, EnumAccess, IgnoredAny, SeqAccess, VariantAccess, Visitor},
+    Deserialize, Deserializer, Serialize,
+};
+use std::{collections::BTreeMap, fmt, marker::PhantomData, path::Path};
+
+/// How bad a lint issue is, errors make a document fail to load or run as written
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BehaviorLintSeverity {
+    Warning,
+    Error,
+}
+
+/// A problem found in a behavior document
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct BehaviorLintIssue {
+    pub severity: BehaviorLintSeverity,
+    /// Short id of the check, like `decorator-children`
+    pub rule: &'static str,
+    /// Names of the nodes from the root, joined with `/`, empty for the whole document
+    pub node: String,
+    pub message: String,
+}
+
+/// Issues of one document
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct BehaviorLintReport {
+    pub file: String,
+    pub issues: Vec<BehaviorLintIssue>,
+}
+
+impl BehaviorLintReport {
+    pub fn errors(&self) -> usize {
+        self.count(BehaviorLintSeverity::Error)
+    }
+
+    pub fn warnings(&self) -> usize {
+        self.count(BehaviorLintSeverity::Warning)
+    }
+
+    fn count(&self, severity: BehaviorLintSeverity) -> usize {
+        self.issues
+            .iter()
+            .filter(|issue| issue.severity == severity)
+            .count()
+    }
+
+    /// One line per issue, like a compiler would
+    pub fn to_text(&self) -> String {
+        let mut text = String::new();
+        for issue in self.issues.iter() {
+            let severity = match issue.severity {
+                BehaviorLintSeverity::Warning => "warning",
+                BehaviorLintSeverity::Error => "error",
+            };
+            let node = if issue.node.is_empty() {
+                String::new()
+            } else {
+                format!(" {}:", issue.node)
+            };
+            text.push_str(&format!(
+                "{}: {}[{}]:{} {}\n",
+                self.file, severity, issue.rule, node, issue.message
+            ));
+        }
+        text
+    }
+}
+
+/// What the checks know of a node variant, from its registration
+#[derive(Debug, Clone, Copy)]
+struct KnownNode {
+    category: Option<BehaviorType>,
+    children: Option<BehaviorChildCount>,
+    flow: BehaviorFlow,
+}
+
+/// Check behavior documents against the nodes of a behavior enum, without spawning them.
+/// Documents are read loosely, so that all unknown nodes are reported at once.
+pub struct BehaviorLinter<T> {
+    /// Known nodes, by variant
+    known: BTreeMap<String, KnownNode>,
+    phantom: PhantomData<fn() -> T>,
+}
+
+impl<T> Default for BehaviorLinter<T>
+where
+    T: BehaviorSpawner + Serialize,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> BehaviorLinter<T>
+where
+    T: BehaviorSpawner + Serialize,
+{
+    /// Check against the registered nodes of the enum
+    pub fn new() -> Self {
+        Self::with_registry(&BehaviorRegistry::of::<T>())
+    }
+
+    /// Check against the nodes of a registry, variants of the enum that aren't registered
+    /// are unknown
+    pub fn with_registry(registry: &BehaviorRegistry) -> Self {
+        let mut known = BTreeMap::new();
+        for behavior in T::defaults() {
+            let registration = behavior.behavior_name().and_then(|name| registry.get(name));
+            // Variant names can differ from behavior names, read them back from RON
+            if let (Some(registration), Ok(text)) = (registration, ron::to_string(&behavior)) {
+                let variant = text.split('(').next().unwrap_or_default().to_string();
+                known.insert(
+                    variant,
+                    KnownNode {
+                        category: Some(registration.category),
+                        children: Some(registration.children),
+                        flow: registration.flow,
+                    },
+                );
+            }
+        }
+        Self {
+            known,
+            phantom: PhantomData,
+        }
+    }
+
+    /// Accept a node that isn't part of the enum, children aren't checked without a type
+    pub fn allow(&mut self, variant: impl Into<String>, typ: Option<BehaviorType>) -> &mut Self {
+        self.known.insert(
+            variant.into(),
+            KnownNode {
+                category: typ,
+                children: typ.as_ref().map(BehaviorChildCount::from_type),
+                flow: BehaviorFlow::NONE,
+            },
+        );
+        self
+    }
+
+    fn flow(&self, variant: &str) -> BehaviorFlow {
+        self.known
+            .get(variant)
+            .map_or(BehaviorFlow::NONE, |known| known.flow)
+    }
+
+    /// The repeater that keeps a node running forever, the node itself or one below its
+    /// decorators, unless a decorator on the way aborts it
+    fn repeats_forever<'a>(&self, node: &'a LintNode) -> Option<&'a LintNode> {
+        if matches!(node.repeat, Some(Repeat::Forever)) {
+            return Some(node);
+        }
+        let known = self.known.get(node.variant.as_str())?;
+        if known.category != Some(BehaviorType::Decorator) || known.flow.aborts {
+            return None;
+        }
+        match node.children.as_slice() {
+            [child] => self.repeats_forever(child),
+            _ => None,
+        }
+    }
+
+    pub fn is_known(&self, variant: &str) -> bool {
+        self.known.contains_key(variant)
+    }
+
+    pub fn lint_file(&self, path: impl AsRef<Path>) -> BehaviorLintReport {
+        let path = path.as_ref();
+        match std::fs::read_to_string(path) {
+            Ok(text) => self.lint_str(&path.display().to_string(), &text),
+            Err(err) => BehaviorLintReport {
+                file: path.display().to_string(),
+                issues: vec![BehaviorLintIssue {
+                    severity: BehaviorLintSeverity::Error,
+                    rule: "read",
+                    node: String::new(),
+                    message: err.to_string(),
+                }],
+            },
+        }
+    }
+
+    pub fn lint_str(&self, file: &str, text: &str) -> BehaviorLintReport {
+        let mut report = BehaviorLintReport {
+            file: file.to_string(),
+            issues: vec![],
+        };
+        match ron::from_str::<LintDocument>(text) {
+            Ok(document) => {
+                self.lint_node(&document.root, &[], &mut report.issues);
+                lint_duplicates(&document.root, &mut report.issues);
+            }
+            Err(err) => report.issues.push(BehaviorLintIssue {
+                severity: BehaviorLintSeverity::Error,
+                rule: "parse",
+                node: String::new(),
+                message: err.to_string(),
+            }),
+        }
+        report
+    }
+
+    fn lint_node(
+        &self,
+        node: &LintNode,
+        ancestors: &[&LintNode],
+        issues: &mut Vec<BehaviorLintIssue>,
+    ) {
+        let path = ancestors
+            .iter()
+            .chain(std::iter::once(&node))
+            .map(|node| node.name.as_str())
+            .collect::<Vec<_>>()
+            .join("/");
+        let mut issue = |severity, rule, message: String| {
+            issues.push(BehaviorLintIssue {
+                severity,
+                rule,
+                node: path.clone(),
+                message,
+            })
+        };
+        let variant = node.variant.as_str();
+        let count = node.children.len();
+        let known = self.known.get(variant);
+        match known.map(|known| (known.category, known.children)) {
+            None => issue(
+                BehaviorLintSeverity::Error,
+                "unknown-node",
+                format!("Unknown behavior node `{}`", variant),
+            ),
+            Some((Some(BehaviorType::Action), Some(BehaviorChildCount::None))) if count > 0 => {
+                issue(
+                    BehaviorLintSeverity::Error,
+                    "action-children",
+                    format!(
+                        "Action `{}` has {} children, actions have none",
+                        variant, count
+                    ),
+                )
+            }
+            // A subtree gets its child from its asset when it runs
+            Some((Some(BehaviorType::Decorator), Some(BehaviorChildCount::One)))
+                if count != 1 && !(variant == "Subtree" && count == 0) =>
+            {
+                issue(
+                    BehaviorLintSeverity::Error,
+                    "decorator-children",
+                    format!(
+                        "Decorator `{}` has {} children, decorators have exactly one",
+                        variant, count
+                    ),
+                )
+            }
+            _ => {}
+        }
+
+        let flow = known.map_or(BehaviorFlow::NONE, |known| known.flow);
+
+        // A succeeder never fails, so a selector stops there
+        if flow.selects {
+            let succeeder = node
+                .children
+                .iter()
+                .position(|child| child.variant == "Succeeder");
+            if let Some(index) = succeeder.filter(|index| index + 1 < node.children.len()) {
+                let unreachable = node.children[index + 1..]
+                    .iter()
+                    .map(|child| format!("`{}`", child.name))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                issue(
+                    BehaviorLintSeverity::Warning,
+                    "unreachable",
+                    format!(
+                        "{} never run, `{}` always succeeds before them",
+                        unreachable, node.children[index].name
+                    ),
+                );
+            }
+        }
+
+        // Reactive composites only check earlier `Condition` children again, not other nodes
+        if flow.reactive {
+            let count = node.children.len();
+            for child in node.children.iter().take(count.saturating_sub(1)) {
+                if child.variant != "Condition" {
+                    issue(
+                        BehaviorLintSeverity::Warning,
+                        "reactive-condition",
+                        format!(
+                            "`{}` is not a `Condition`, `{}` does not check it again while a later child runs",
+                            child.name, node.name
+                        ),
+                    );
+                }
+            }
+        }
+
+        // A repeater that never completes holds up its sequence, unless something aborts it
+        if flow.sequences {
+            let aborted = flow.aborts
+                || ancestors
+                    .iter()
+                    .any(|ancestor| self.flow(&ancestor.variant).aborts);
+            for child in node.children.iter().filter(|_| !aborted) {
+                if let Some(repeater) = self.repeats_forever(child) {
+                    issue(
+                        BehaviorLintSeverity::Warning,
+                        "repeat-forever",
+                        format!(
+                            "`{}` repeats forever, so `{}` never completes and nothing stops it",
+                            repeater.name, node.name
+                        ),
+                    );
+                }
+            }
+        }
+
+        let mut ancestors = ancestors.to_vec();
+        ancestors.push(node);
+        for child in node.children.iter() {
+            self.lint_node(child, &ancestors, issues);
+        }
+    }
+}
+
+fn lint_duplicates(root: &LintNode, issues: &mut Vec<BehaviorLintIssue>) {
+    let mut counts = BTreeMap::<&str, usize>::new();
+    let mut stack = vec![root];
+    while let Some(node) = stack.pop() {
+        *counts.entry(node.name.as_str()).or_default() += 1;
+        stack.extend(node.children.iter());
+    }
+    for (name, count) in counts.into_iter().filter(|(_, count)| *count > 1) {
+        issues.push(BehaviorLintIssue {
+            severity: BehaviorLintSeverity::Warning,
+            rule: "duplicate-name",
+            node: String::new(),
+            message: format!("{} nodes are named `{}`", count, name),
+        });
+    }
+}
+
+/// A document read without knowing its behavior enum
+#[derive(Deserialize)]
+struct LintDocument {
+    root: LintNode,
+}
+
+/// A document node, keeping only what the checks need
+struct LintNode {
+    name: String,
+    variant: String,
+    /// Repeat of `Repeater` nodes
+    repeat: Option<Repeat>,
+    children: Vec<LintNode>,
+}
+
+impl<'de> Deserialize<'de> for LintNode {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct NodeVisitor;
+
+        impl<'de> Visitor<'de> for NodeVisitor {
+            type Value = LintNode;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a behavior node (name, behavior, children)")
+            }
+
+            fn visit_seq<A>(self, mut seq: A) -> Result<LintNode, A::Error>
+            where
+                A: SeqAccess<'de>,
+            {
+                let name = seq
+                    .next_element::<String>()?
+                    .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+                let behavior = seq
+                    .next_element::<LintBehavior>()?
+                    .ok_or_else(|| de::Error::invalid_length(1, &self))?;
+                let children = seq.next_element::<Vec<LintNode>>()?.unwrap_or_default();
+                Ok(LintNode {
+                    name,
+                    variant: behavior.variant,
+                    repeat: behavior.repeat,
+                    children,
+                })
+            }
+        }
+
+        deserializer.deserialize_tuple_struct("BTNode", 3, NodeVisitor)
+    }
+}
+
+/// Any behavior enum variant, its data is skipped
+struct LintBehavior {
+    variant: String,
+    repeat: Option<Repeat>,
+}
+
+impl<'de> Deserialize<'de> for LintBehavior {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct BehaviorVisitor;
+
+        impl<'de> Visitor<'de> for BehaviorVisitor {
+            type Value = LintBehavior;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a behavior, like `Sequencer(())`")
+            }
+
+            fn visit_enum<A>(self, data: A) -> Result<LintBehavior, A::Error>
+            where
+                A: EnumAccess<'de>,
+            {
+                let (Variant(variant), data) = data.variant::<Variant>()?;
+                let repeat = if variant == "Repeater" {
+                    Some(data.newtype_variant::<Repeater>()?.repeat)
+                } else {
+                    data.newtype_variant::<IgnoredAny>()?;
+                    None
+                };
+                Ok(LintBehavior { variant, repeat })
+            }
+        }
+
+        deserializer.deserialize_enum("Behavior", &[], BehaviorVisitor)
+    }
+}
+
+/// Name of an enum variant, whatever it is
+struct Variant(String);
+
+impl<'de> Deserialize<'de> for Variant {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct VariantVisitor;
+
+        impl<'de> Visitor<'de> for VariantVisitor {
+            type Value = Variant;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a variant name")
+            }
+
+            fn visit_str<E>(self, value: &str) -> Result<Variant, E>
+            where
+                E: de::Error,
+            {
+                Ok(Variant(value.to_string()))
+            }
+        }
+
+        deserializer.deserialize_identifier(VariantVisitor)
+    }
+}
@@ -0,0 +1,300 @@
+use crate::{
+    add_children, control::BehaviorPaused, BehaviorChildren, BehaviorCursor, BehaviorFailure,
+    BehaviorNode, BehaviorParent, BehaviorRunning, BehaviorSuccess, BehaviorTree,
+    BehaviorTreeControl, BehaviorType,
+};
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use simula_net::{replicate, NetId, Proxy, Replicate};
+
+/// Replicate behavior trees that opt in with `ReplicateBehaviorTree`, and mirror the ones
+/// received from other peers as proxy trees. Needs `simula_net::NetPlugin`.
+pub struct BehaviorNetPlugin;
+
+impl Plugin for BehaviorNetPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<ReplicateBehaviorTree>()
+            .register_type::<BehaviorTreeSnapshot>()
+            .register_type::<Replicate<BehaviorTreeSnapshot>>()
+            .add_system(setup_replication)
+            .add_system(replicate::<BehaviorTreeSnapshot>)
+            .add_system_to_stage(CoreStage::PostUpdate, update_proxy_trees)
+            .add_system_to_stage(CoreStage::Last, snapshot_trees);
+    }
+}
+
+/// Add to a behavior tree entity to replicate its structure and node states to other peers
+#[derive(Debug, Clone, Component, Reflect)]
+#[reflect(Component)]
+pub struct ReplicateBehaviorTree {
+    /// rate in hertz
+    pub rate: f64,
+}
+
+impl Default for ReplicateBehaviorTree {
+    fn default() -> Self {
+        Self { rate: 10.0 }
+    }
+}
+
+/// State of a node, as shown in the inspector
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BehaviorNodeState {
+    #[default]
+    Idle,
+    Running,
+    Success,
+    Failure,
+}
+
+impl BehaviorNodeState {
+    pub fn from_markers(running: bool, success: bool, failure: bool) -> Self {
+        if success {
+            Self::Success
+        } else if failure {
+            Self::Failure
+        } else if running {
+            Self::Running
+        } else {
+            Self::Idle
+        }
+    }
+}
+
+/// A node of a replicated tree, children are indices into the tree nodes
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BehaviorNodeSnapshot {
+    pub name: String,
+    /// Behavior of the node, like `Sequencer`
+    pub behavior: String,
+    pub typ: BehaviorType,
+    pub children: Vec<usize>,
+    pub state: BehaviorNodeState,
+    pub cursor: bool,
+}
+
+/// Structure and node states of a behavior tree, depth first from the root
+#[derive(Debug, Default, Clone, PartialEq, Component, Reflect, Serialize, Deserialize)]
+#[reflect(Component)]
+pub struct BehaviorTreeSnapshot {
+    #[reflect(ignore)]
+    pub nodes: Vec<BehaviorNodeSnapshot>,
+    pub paused: bool,
+}
+
+impl BehaviorTreeSnapshot {
+    /// Same nodes in the same places, whatever their states
+    pub fn same_structure(&self, other: &Self) -> bool {
+        self.nodes.len() == other.nodes.len()
+            && self.nodes.iter().zip(other.nodes.iter()).all(|(a, b)| {
+                a.name == b.name
+                    && a.behavior == b.behavior
+                    && a.typ == b.typ
+                    && a.children == b.children
+            })
+    }
+}
+
+/// A tree mirrored from another peer, its nodes never run locally
+#[derive(Debug, Default, Clone, Component)]
+pub struct BehaviorProxy {
+    /// Proxy node entities, in snapshot order
+    pub nodes: Vec<Entity>,
+    /// Snapshot the proxy nodes mirror
+    pub snapshot: BehaviorTreeSnapshot,
+}
+
+/// A node of a proxy tree
+#[derive(Debug, Default, Clone, Copy, Component)]
+pub struct BehaviorProxyNode;
+
+/// Start replicating trees that opted in
+pub fn setup_replication(
+    mut commands: Commands,
+    trees: Query<(Entity, &ReplicateBehaviorTree, Option<&NetId>), Added<ReplicateBehaviorTree>>,
+) {
+    for (entity, replicate, net_id) in &trees {
+        let mut entity = commands.entity(entity);
+        if net_id.is_none() {
+            entity.insert(NetId::default());
+        }
+        entity
+            .insert(BehaviorTreeSnapshot::default())
+            .insert(Replicate::<BehaviorTreeSnapshot> {
+                rate: replicate.rate,
+                ..default()
+            });
+    }
+}
+
+type SnapshotNodes<'w, 's> = Query<
+    'w,
+    's,
+    (
+        &'static BehaviorNode,
+        &'static Name,
+        &'static BehaviorChildren,
+        Option<&'static BehaviorRunning>,
+        Option<&'static BehaviorSuccess>,
+        Option<&'static BehaviorFailure>,
+        Option<&'static BehaviorCursor>,
+    ),
+>;
+
+fn snapshot_node(
+    nodes: &SnapshotNodes,
+    entity: Entity,
+    snapshot: &mut Vec<BehaviorNodeSnapshot>,
+) -> Option<usize> {
+    let (node, name, children, running, success, failure, cursor) = nodes.get(entity).ok()?;
+    let index = snapshot.len();
+    snapshot.push(BehaviorNodeSnapshot {
+        name: name.to_string(),
+        behavior: node.name.clone(),
+        typ: node.typ.clone(),
+        children: vec![],
+        state: BehaviorNodeState::from_markers(
+            running.is_some(),
+            success.is_some(),
+            failure.is_some(),
+        ),
+        cursor: cursor.is_some(),
+    });
+    let children = children
+        .iter()
+        .filter_map(|child| snapshot_node(nodes, *child, snapshot))
+        .collect();
+    snapshot[index].children = children;
+    Some(index)
+}
+
+/// Take snapshots of replicated trees, once they are done changing for the frame
+pub fn snapshot_trees(
+    mut trees: Query<
+        (
+            &BehaviorTree,
+            &mut BehaviorTreeSnapshot,
+            Option<&BehaviorTreeControl>,
+        ),
+        With<ReplicateBehaviorTree>,
+    >,
+    nodes: SnapshotNodes,
+) {
+    for (tree, mut snapshot, control) in &mut trees {
+        let mut taken = BehaviorTreeSnapshot {
+            nodes: vec![],
+            paused: control.map_or(false, |control| control.is_paused()),
+        };
+        if let Some(root) = tree.root {
+            snapshot_node(&nodes, root, &mut taken.nodes);
+        }
+        // Only touch the snapshot on changes, so proxies update on change detection
+        if *snapshot != taken {
+            *snapshot = taken;
+        }
+    }
+}
+
+fn insert_state(commands: &mut Commands, entity: Entity, node: &BehaviorNodeSnapshot) {
+    let mut entity = commands.entity(entity);
+    entity
+        .remove::<BehaviorRunning>()
+        .remove::<BehaviorSuccess>()
+        .remove::<BehaviorFailure>()
+        .remove::<BehaviorCursor>();
+    match node.state {
+        BehaviorNodeState::Idle => {}
+        BehaviorNodeState::Running => {
+            entity.insert(BehaviorRunning::default());
+        }
+        BehaviorNodeState::Success => {
+            entity.insert(BehaviorSuccess);
+        }
+        BehaviorNodeState::Failure => {
+            entity.insert(BehaviorFailure);
+        }
+    }
+    if node.cursor {
+        entity.insert(BehaviorCursor);
+    }
+}
+
+/// Spawn proxy nodes for received trees, and keep their states in sync
+pub fn update_proxy_trees(
+    mut commands: Commands,
+    mut proxies: Query<
+        (Entity, &BehaviorTreeSnapshot, Option<&mut BehaviorProxy>),
+        (With<Proxy>, Changed<BehaviorTreeSnapshot>),
+    >,
+) {
+    for (tree, snapshot, proxy) in &mut proxies {
+        let mut proxy = match proxy {
+            Some(proxy) if proxy.snapshot.same_structure(snapshot) => proxy,
+            proxy => {
+                // New tree, or its structure changed, so spawn its nodes again
+                if let Some(root) = proxy.and_then(|proxy| proxy.nodes.first().copied()) {
+                    commands.entity(root).despawn_recursive();
+                }
+                let nodes = spawn_proxy_nodes(&mut commands, tree, snapshot);
+                commands.entity(tree).insert(BehaviorTree {
+                    root: nodes.first().copied(),
+                });
+                commands.entity(tree).insert(BehaviorProxy {
+                    nodes,
+                    snapshot: snapshot.clone(),
+                });
+                continue;
+            }
+        };
+        for (index, node) in snapshot.nodes.iter().enumerate() {
+            let last = &proxy.snapshot.nodes[index];
+            if last.state != node.state || last.cursor != node.cursor {
+                insert_state(&mut commands, proxy.nodes[index], node);
+            }
+        }
+        proxy.snapshot = snapshot.clone();
+    }
+}
+
+fn spawn_proxy_nodes(
+    commands: &mut Commands,
+    tree: Entity,
+    snapshot: &BehaviorTreeSnapshot,
+) -> Vec<Entity> {
+    let nodes = snapshot
+        .nodes
+        .iter()
+        .map(|node| {
+            let entity = commands
+                .spawn()
+                .insert(BehaviorNode {
+                    typ: node.typ.clone(),
+                    name: node.behavior.clone(),
+                    desc: String::new(),
+                    tree: Some(tree),
+                })
+                .insert(node.typ.clone())
+                .insert(Name::new(node.name.clone()))
+                .insert(BehaviorParent::default())
+                .insert(BehaviorChildren::default())
+                // Paused, so the lifecycle systems leave proxy nodes alone
+                .insert(BehaviorPaused)
+                .insert(BehaviorProxyNode)
+                .id();
+            insert_state(commands, entity, node);
+            entity
+        })
+        .collect::<Vec<_>>();
+    for (index, node) in snapshot.nodes.iter().enumerate() {
+        let children = node
+            .children
+            .iter()
+            .filter_map(|child| nodes.get(*child).copied())
+            .collect::<Vec<_>>();
+        add_children(commands, nodes[index], &children);
+    }
+    if let Some(root) = nodes.first() {
+        commands.entity(tree).push_children(&[*root]);
+    }
+    nodes
+}
@@ -0,0 +1,190 @@
+use crate::{
+    blackboard::{BehaviorBlackboard, BlackboardValue},
+    control::BehaviorTime,
+    stats::BehaviorStats,
+    BehaviorNode, BehaviorType,
+};
+use bevy::{ecs::system::SystemParam, prelude::*};
+use serde::{Serialize, Serializer};
+use std::{
+    collections::{BTreeMap, VecDeque},
+    io::Write,
+    path::Path,
+};
+
+/// A state change of a behavior node
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum BehaviorTransition {
+    Started,
+    Success,
+    Failure,
+    Stopped,
+}
+
+/// A recorded state change, with the context needed to find out why a tree stalled
+#[derive(Debug, Clone, Serialize)]
+pub struct BehaviorEvent {
+    #[serde(serialize_with = "serialize_tree")]
+    pub tree: Option<Entity>,
+    #[serde(serialize_with = "serialize_entity")]
+    pub node: Entity,
+    pub typ: BehaviorType,
+    /// Behavior of the node, like `Sequencer`
+    pub behavior: String,
+    pub name: String,
+    pub transition: BehaviorTransition,
+    pub frame: u64,
+    /// Time of the tree, in seconds, which doesn't move while it's paused
+    pub time: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub blackboard: Option<BTreeMap<String, BlackboardValue>>,
+}
+
+fn serialize_entity<S>(entity: &Entity, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    serializer.serialize_u32(entity.id())
+}
+
+fn serialize_tree<S>(tree: &Option<Entity>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    match tree {
+        Some(tree) => serializer.serialize_some(&tree.id()),
+        None => serializer.serialize_none(),
+    }
+}
+
+/// Structured log of behavior state changes, keeping only the latest events
+#[derive(Debug, Clone)]
+pub struct BehaviorRecorder {
+    /// Record events, off by default
+    pub enabled: bool,
+    /// Maximum number of events kept, older ones are dropped first
+    pub capacity: usize,
+    /// Snapshot the tree blackboard on every event, costly on large blackboards
+    pub snapshot_blackboard: bool,
+    pub frame: u64,
+    events: VecDeque<BehaviorEvent>,
+}
+
+impl Default for BehaviorRecorder {
+    fn default() -> Self {
+        Self::new(4096)
+    }
+}
+
+impl BehaviorRecorder {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            enabled: false,
+            capacity,
+            snapshot_blackboard: false,
+            frame: 0,
+            events: VecDeque::with_capacity(capacity),
+        }
+    }
+
+    pub fn push(&mut self, event: BehaviorEvent) {
+        if self.capacity == 0 {
+            return;
+        }
+        while self.events.len() >= self.capacity {
+            self.events.pop_front();
+        }
+        self.events.push_back(event);
+    }
+
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = &BehaviorEvent> {
+        self.events.iter()
+    }
+
+    /// Events of one tree, oldest first
+    pub fn tree_events(&self, tree: Entity) -> impl Iterator<Item = &BehaviorEvent> {
+        self.events
+            .iter()
+            .filter(move |event| event.tree == Some(tree))
+    }
+
+    pub fn len(&self) -> usize {
+        self.events.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.events.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.events.clear();
+    }
+
+    /// One JSON object per line, oldest first
+    pub fn to_json_lines(&self) -> String {
+        let mut lines = String::new();
+        for event in self.events.iter() {
+            match serde_json::to_string(event) {
+                Ok(line) => {
+                    lines.push_str(&line);
+                    lines.push('\n');
+                }
+                Err(err) => warn!("Failed to serialize behavior event: {}", err),
+            }
+        }
+        lines
+    }
+
+    pub fn write_json_lines(&self, path: impl AsRef<Path>) -> std::io::Result<()> {
+        let mut file = std::fs::File::create(path)?;
+        file.write_all(self.to_json_lines().as_bytes())
+    }
+}
+
+/// Record behavior state changes, from the systems that change them
+#[derive(SystemParam)]
+pub struct BehaviorRecording<'w, 's> {
+    recorder: Option<ResMut<'w, BehaviorRecorder>>,
+    stats: Option<ResMut<'w, BehaviorStats>>,
+    time: BehaviorTime<'w, 's>,
+    nodes: Query<'w, 's, &'static BehaviorNode>,
+    blackboards: Query<'w, 's, &'static BehaviorBlackboard>,
+}
+
+impl<'w, 's> BehaviorRecording<'w, 's> {
+    pub fn record(&mut self, entity: Entity, name: &Name, transition: BehaviorTransition) {
+        let node = self.nodes.get(entity).ok();
+        let time = self.time.elapsed(node.unwrap_or(&BehaviorNode::default()));
+        if let (Some(stats), Some(node)) = (self.stats.as_mut(), node) {
+            stats.record(entity, &node.name, transition, time);
+        }
+        let recorder = match self.recorder.as_mut() {
+            Some(recorder) if recorder.enabled => recorder,
+            _ => return,
+        };
+        let tree = node.and_then(|node| node.tree);
+        let blackboard = if recorder.snapshot_blackboard {
+            tree.and_then(|tree| self.blackboards.get(tree).ok())
+                .map(|blackboard| blackboard.to_values())
+        } else {
+            None
+        };
+        let event = BehaviorEvent {
+            tree,
+            node: entity,
+            typ: node.map(|node| node.typ.clone()).unwrap_or_default(),
+            behavior: node.map(|node| node.name.clone()).unwrap_or_default(),
+            name: name.to_string(),
+            transition,
+            frame: recorder.frame,
+            time,
+            blackboard,
+        };
+        recorder.push(event);
+    }
+}
+
+/// Count frames for recorded events
+pub fn update_recorder(mut recorder: ResMut<BehaviorRecorder>) {
+    recorder.frame += 1;
+}
@@ -0,0 +1,271 @@
+use crate::{BehaviorInfo, BehaviorSpawner, BehaviorType};
+use bevy::{
+    ecs::system::{CommandQueue, EntityCommands},
+    prelude::*,
+    reflect::ReflectRef,
+    utils::HashMap,
+};
+use std::collections::BTreeMap;
+
+/// How many children a behavior node can have
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BehaviorChildCount {
+    None,
+    One,
+    Any,
+}
+
+impl BehaviorChildCount {
+    pub const fn from_type(typ: &BehaviorType) -> Self {
+        match typ {
+            BehaviorType::Action => BehaviorChildCount::None,
+            BehaviorType::Decorator => BehaviorChildCount::One,
+            BehaviorType::Composite => BehaviorChildCount::Any,
+        }
+    }
+
+    pub fn allows(&self, count: usize) -> bool {
+        match self {
+            BehaviorChildCount::None => count == 0,
+            BehaviorChildCount::One => count <= 1,
+            BehaviorChildCount::Any => true,
+        }
+    }
+}
+
+/// How a node runs its children, for tools that check documents without running them
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct BehaviorFlow {
+    /// Completes with its first successful child, like a selector
+    pub selects: bool,
+    /// Runs its children one after the other, like a sequence
+    pub sequences: bool,
+    /// Checks earlier children again while a later one runs
+    pub reactive: bool,
+    /// Can stop a running child on its own
+    pub aborts: bool,
+}
+
+impl BehaviorFlow {
+    pub const NONE: Self = Self {
+        selects: false,
+        sequences: false,
+        reactive: false,
+        aborts: false,
+    };
+}
+
+/// A field of a behavior node, as seen through reflection
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BehaviorFieldInfo {
+    pub name: String,
+    pub type_name: String,
+}
+
+/// Everything tooling needs to know about a behavior node type
+#[derive(Clone)]
+pub struct BehaviorRegistration {
+    pub name: &'static str,
+    pub desc: &'static str,
+    pub category: BehaviorType,
+    pub children: BehaviorChildCount,
+    pub flow: BehaviorFlow,
+    pub type_name: &'static str,
+    pub fields: Vec<BehaviorFieldInfo>,
+    /// Insert a default node of this type
+    pub spawn: fn(&mut EntityCommands),
+    /// Insert a node of this type, with fields applied from reflected data.
+    /// Returns false, and inserts nothing, if the data is of another type.
+    pub spawn_with: fn(&mut EntityCommands, &dyn Reflect) -> bool,
+    /// Read back the node of an entity, if it has one of this type
+    pub from_entity: fn(&World, Entity) -> Option<Box<dyn Reflect>>,
+}
+
+impl std::fmt::Debug for BehaviorRegistration {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BehaviorRegistration")
+            .field("name", &self.name)
+            .field("category", &self.category)
+            .field("children", &self.children)
+            .field("flow", &self.flow)
+            .field("fields", &self.fields)
+            .finish()
+    }
+}
+
+impl BehaviorRegistration {
+    pub fn of<T>() -> Self
+    where
+        T: BehaviorInfo,
+    {
+        Self {
+            name: T::NAME,
+            desc: T::DESC,
+            category: T::TYPE,
+            children: BehaviorChildCount::from_type(&T::TYPE),
+            flow: T::FLOW,
+            type_name: std::any::type_name::<T>(),
+            fields: fields(&T::default()),
+            spawn: <T as BehaviorInfo>::insert,
+            spawn_with: |commands, data| {
+                // Applying data of another type panics
+                if data.type_name() != std::any::type_name::<T>() {
+                    return false;
+                }
+                let mut behavior = T::default();
+                behavior.apply(data);
+                T::insert_with(commands, &behavior);
+                true
+            },
+            from_entity: |world, entity| {
+                world
+                    .get::<T>(entity)
+                    .map(|behavior| Box::new(behavior.clone()) as Box<dyn Reflect>)
+            },
+        }
+    }
+
+    /// A default node of this type, inserted through the registration and read back as a
+    /// behavior enum. None if the enum has no variant for it.
+    pub fn behavior<T>(&self) -> Option<T>
+    where
+        T: BehaviorSpawner,
+    {
+        let mut world = World::new();
+        let mut command_queue = CommandQueue::default();
+        let mut commands = Commands::new(&mut command_queue, &world);
+        let mut entity = commands.spawn();
+        (self.spawn)(&mut entity);
+        let entity = entity.id();
+        command_queue.apply(&mut world);
+        T::from_entity(&world, entity)
+    }
+}
+
+/// Fields of a reflected struct, tuple struct fields are named by index
+fn fields(value: &dyn Reflect) -> Vec<BehaviorFieldInfo> {
+    match value.reflect_ref() {
+        ReflectRef::Struct(value) => (0..value.field_len())
+            .filter_map(|index| {
+                Some(BehaviorFieldInfo {
+                    name: value.name_at(index)?.to_string(),
+                    type_name: value.field_at(index)?.type_name().to_string(),
+                })
+            })
+            .collect(),
+        ReflectRef::TupleStruct(value) => (0..value.field_len())
+            .filter_map(|index| {
+                Some(BehaviorFieldInfo {
+                    name: index.to_string(),
+                    type_name: value.field(index)?.type_name().to_string(),
+                })
+            })
+            .collect(),
+        _ => vec![],
+    }
+}
+
+/// Registered behavior node types by name, populated by `register_behavior`
+#[derive(Debug, Default)]
+pub struct BehaviorRegistry {
+    behaviors: BTreeMap<&'static str, BehaviorRegistration>,
+    type_names: HashMap<&'static str, &'static str>,
+}
+
+impl BehaviorRegistry {
+    /// Registry of the nodes of a behavior enum, without an app
+    pub fn of<T>() -> Self
+    where
+        T: BehaviorSpawner,
+    {
+        let mut registry = Self::default();
+        T::register_behaviors(&mut registry);
+        registry
+    }
+
+    pub fn register<T>(&mut self)
+    where
+        T: BehaviorInfo,
+    {
+        let registration = BehaviorRegistration::of::<T>();
+        if let Some(previous) = self.behaviors.get(registration.name) {
+            if previous.type_name != registration.type_name {
+                warn!(
+                    "Behavior {} registered by {} and {}",
+                    registration.name, previous.type_name, registration.type_name
+                );
+            }
+        }
+        self.type_names
+            .insert(registration.type_name, registration.name);
+        self.behaviors.insert(registration.name, registration);
+    }
+
+    pub fn get(&self, name: &str) -> Option<&BehaviorRegistration> {
+        self.behaviors.get(name)
+    }
+
+    /// Registration of a node type, by its reflected type name
+    pub fn get_by_type_name(&self, type_name: &str) -> Option<&BehaviorRegistration> {
+        self.get(self.type_names.get(type_name)?)
+    }
+
+    /// Registrations in name order
+    pub fn iter(&self) -> impl Iterator<Item = &BehaviorRegistration> {
+        self.behaviors.values()
+    }
+
+    pub fn category(&self, category: BehaviorType) -> impl Iterator<Item = &BehaviorRegistration> {
+        self.iter()
+            .filter(move |registration| registration.category == category)
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.behaviors.contains_key(name)
+    }
+
+    pub fn len(&self) -> usize {
+        self.behaviors.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.behaviors.is_empty()
+    }
+
+    /// Insert a node by behavior name, with fields applied from `data` if any.
+    /// Returns false if no such behavior is registered, or `data` is of another type.
+    pub fn spawn(
+        &self,
+        name: &str,
+        commands: &mut EntityCommands,
+        data: Option<&dyn Reflect>,
+    ) -> bool {
+        match self.get(name) {
+            Some(registration) => match data {
+                Some(data) => (registration.spawn_with)(commands, data),
+                None => {
+                    (registration.spawn)(commands);
+                    true
+                }
+            },
+            None => false,
+        }
+    }
+
+    /// Read back the behavior node of an entity, whatever its registered type
+    pub fn from_entity(
+        &self,
+        world: &World,
+        entity: Entity,
+    ) -> Option<(&BehaviorRegistration, Box<dyn Reflect>)> {
+        self.iter().find_map(|registration| {
+            (registration.from_entity)(world, entity).map(|data| (registration, data))
+        })
+    }
+
+    /// Check a node has a valid number of children for its behavior
+    pub fn allows_children(&self, name: &str, count: usize) -> Option<bool> {
+        self.get(name)
+            .map(|registration| registration.children.allows(count))
+    }
+}
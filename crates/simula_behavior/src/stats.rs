@@ -0,0 +1,418 @@
+use crate::{recorder::BehaviorTransition, BehaviorNode};
+use bevy::{
+    diagnostic::{Diagnostic, DiagnosticId, Diagnostics},
+    ecs::{
+        archetype::ArchetypeComponentId, component::ComponentId, query::Access,
+        schedule::SystemLabelId, system::System,
+    },
+    prelude::*,
+    utils::{HashMap, Instant},
+};
+use std::{
+    borrow::Cow,
+    collections::{hash_map::DefaultHasher, BTreeMap},
+    hash::{Hash, Hasher},
+    io::Write,
+    path::Path,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
+    time::Duration,
+};
+
+/// Counters of one node type, like `Sequencer`
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct BehaviorNodeStats {
+    pub activations: u64,
+    pub successes: u64,
+    pub failures: u64,
+    pub stops: u64,
+    /// Behavior clock seconds spent running, over all completed or stopped runs
+    pub running_time: f64,
+}
+
+impl BehaviorNodeStats {
+    /// Share of completed runs that succeeded, `None` before the first completion
+    pub fn success_ratio(&self) -> Option<f64> {
+        let completed = self.successes + self.failures;
+        (completed > 0).then(|| self.successes as f64 / completed as f64)
+    }
+
+    /// Mean seconds from start to completion or stop, `None` before the first one
+    pub fn mean_duration(&self) -> Option<f64> {
+        let ended = self.successes + self.failures + self.stops;
+        (ended > 0).then(|| self.running_time / ended as f64)
+    }
+}
+
+/// Timing of one node `run` system, over all the steps it ran in
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct BehaviorSystemStats {
+    /// Short name of the system, like `sequencer::run`
+    pub name: String,
+    pub runs: u64,
+    pub total: Duration,
+    /// Time spent in the last complete frame
+    pub last_frame: Duration,
+    /// Longest time spent in a single frame
+    pub max_frame: Duration,
+    frame: Duration,
+}
+
+impl BehaviorSystemStats {
+    pub fn mean(&self) -> Duration {
+        if self.runs == 0 {
+            Duration::ZERO
+        } else {
+            self.total.div_f64(self.runs as f64)
+        }
+    }
+
+    fn add_run(&mut self, elapsed: Duration) {
+        self.runs += 1;
+        self.total += elapsed;
+        self.frame += elapsed;
+    }
+
+    fn end_frame(&mut self) -> Duration {
+        self.last_frame = std::mem::take(&mut self.frame);
+        self.max_frame = self.max_frame.max(self.last_frame);
+        self.last_frame
+    }
+
+    fn reset(&mut self) {
+        *self = Self {
+            name: std::mem::take(&mut self.name),
+            ..default()
+        };
+    }
+}
+
+/// Per node type counters and per system timings of all behavior trees
+#[derive(Debug, Default)]
+pub struct BehaviorStats {
+    nodes: BTreeMap<String, BehaviorNodeStats>,
+    /// Shared with the profiled systems, keyed by full system name
+    systems: BTreeMap<String, Arc<Mutex<BehaviorSystemStats>>>,
+    /// Start time of running nodes
+    started: HashMap<Entity, f64>,
+    frame_activations: u64,
+    /// Time node systems, shared with the profiled systems. Off by default, as timing
+    /// every run has a cost.
+    profiling: Arc<AtomicBool>,
+}
+
+impl BehaviorStats {
+    /// Activations of all nodes, per frame
+    pub const ACTIVATIONS: DiagnosticId =
+        DiagnosticId::from_u128(0x6a1f2b8e_3c4d_4e5f_9a0b_1c2d3e4f5a6b);
+
+    /// Base of system diagnostics, mixed with a hash of the system name
+    const SYSTEMS: u128 = 0x2f0c9d1e_8b7a_4c3d_8e2f_5a6b7c8d9e0f;
+
+    /// Count a node state change, `time` is the time of its tree in seconds
+    pub fn record(
+        &mut self,
+        entity: Entity,
+        behavior: &str,
+        transition: BehaviorTransition,
+        time: f64,
+    ) {
+        let stats = self.nodes.entry(behavior.to_string()).or_default();
+        match transition {
+            BehaviorTransition::Started => {
+                stats.activations += 1;
+                self.frame_activations += 1;
+                self.started.insert(entity, time);
+                return;
+            }
+            BehaviorTransition::Success => stats.successes += 1,
+            BehaviorTransition::Failure => stats.failures += 1,
+            BehaviorTransition::Stopped => stats.stops += 1,
+        }
+        if let Some(start) = self.started.remove(&entity) {
+            stats.running_time += (time - start).max(0.0);
+        }
+    }
+
+    /// Number of nodes started and not ended yet
+    pub fn running(&self) -> usize {
+        self.started.len()
+    }
+
+    /// Whether node systems are timed
+    pub fn profiling(&self) -> bool {
+        self.profiling.load(Ordering::Relaxed)
+    }
+
+    /// Start or stop timing node systems
+    pub fn set_profiling(&self, profiling: bool) {
+        self.profiling.store(profiling, Ordering::Relaxed);
+    }
+
+    pub fn node(&self, behavior: &str) -> Option<&BehaviorNodeStats> {
+        self.nodes.get(behavior)
+    }
+
+    /// Node types, sorted by name
+    pub fn nodes(&self) -> impl Iterator<Item = (&str, &BehaviorNodeStats)> {
+        self.nodes
+            .iter()
+            .map(|(name, stats)| (name.as_str(), stats))
+    }
+
+    /// Timing of a system, by short or full name
+    pub fn system(&self, name: &str) -> Option<BehaviorSystemStats> {
+        self.systems().find(|stats| stats.name == name).or_else(|| {
+            self.systems
+                .get(name)
+                .and_then(|stats| stats.lock().ok().map(|stats| stats.clone()))
+        })
+    }
+
+    /// Timings of all profiled systems, sorted by full name
+    pub fn systems(&self) -> impl Iterator<Item = BehaviorSystemStats> + '_ {
+        self.systems
+            .values()
+            .filter_map(|stats| stats.lock().ok().map(|stats| stats.clone()))
+    }
+
+    /// Reset all counters and timings
+    pub fn clear(&mut self) {
+        self.nodes.clear();
+        self.started.clear();
+        self.frame_activations = 0;
+        for stats in self.systems.values() {
+            if let Ok(mut stats) = stats.lock() {
+                stats.reset();
+            }
+        }
+    }
+
+    /// Node counters as CSV, one line per node type
+    pub fn nodes_csv(&self) -> String {
+        let mut csv = String::from(
+            "behavior,activations,successes,failures,stops,success_ratio,mean_duration\n",
+        );
+        for (name, stats) in self.nodes() {
+            csv.push_str(&format!(
+                "{},{},{},{},{},{},{}\n",
+                name,
+                stats.activations,
+                stats.successes,
+                stats.failures,
+                stats.stops,
+                optional(stats.success_ratio()),
+                optional(stats.mean_duration()),
+            ));
+        }
+        csv
+    }
+
+    /// System timings as CSV, one line per system, times in milliseconds
+    pub fn systems_csv(&self) -> String {
+        let mut csv = String::from("system,runs,total_ms,mean_ms,last_frame_ms,max_frame_ms\n");
+        for stats in self.systems() {
+            csv.push_str(&format!(
+                "\"{}\",{},{},{},{},{}\n",
+                stats.name,
+                stats.runs,
+                millis(stats.total),
+                millis(stats.mean()),
+                millis(stats.last_frame),
+                millis(stats.max_frame),
+            ));
+        }
+        csv
+    }
+
+    pub fn write_csv(
+        &self,
+        nodes_path: impl AsRef<Path>,
+        systems_path: impl AsRef<Path>,
+    ) -> std::io::Result<()> {
+        std::fs::File::create(nodes_path)?.write_all(self.nodes_csv().as_bytes())?;
+        std::fs::File::create(systems_path)?.write_all(self.systems_csv().as_bytes())
+    }
+
+    /// Diagnostic of the time spent per frame in a system, in milliseconds
+    pub fn system_diagnostic(name: &str) -> DiagnosticId {
+        let mut hasher = DefaultHasher::new();
+        name.hash(&mut hasher);
+        DiagnosticId::from_u128(Self::SYSTEMS ^ hasher.finish() as u128)
+    }
+
+    /// Shared timing of a system, created on first use
+    fn profile(&mut self, name: &str) -> Arc<Mutex<BehaviorSystemStats>> {
+        self.systems
+            .entry(name.to_string())
+            .or_insert_with(|| {
+                Arc::new(Mutex::new(BehaviorSystemStats {
+                    name: short_name(name),
+                    ..default()
+                }))
+            })
+            .clone()
+    }
+}
+
+fn optional(value: Option<f64>) -> String {
+    value.map(|value| value.to_string()).unwrap_or_default()
+}
+
+fn millis(duration: Duration) -> f64 {
+    duration.as_secs_f64() * 1000.0
+}
+
+/// Last two path segments of a system name, like `sequencer::run<Event>`
+fn short_name(name: &str) -> String {
+    let (path, generics) = match name.find('<') {
+        Some(index) => name.split_at(index),
+        None => (name, ""),
+    };
+    let segments = path.rsplit("::").take(2).collect::<Vec<_>>();
+    let mut short = segments.into_iter().rev().collect::<Vec<_>>().join("::");
+    if !generics.is_empty() {
+        let inner = generics.trim_start_matches('<').trim_end_matches('>');
+        short.push('<');
+        short.push_str(inner.rsplit("::").next().unwrap_or(inner));
+        short.push('>');
+    }
+    short
+}
+
+/// A node system that keeps track of the time it takes, while `BehaviorStats` is profiling
+pub struct ProfiledSystem<S> {
+    system: S,
+    stats: Arc<Mutex<BehaviorSystemStats>>,
+    profiling: Arc<AtomicBool>,
+}
+
+impl<S> ProfiledSystem<S>
+where
+    S: System<In = (), Out = ()>,
+{
+    /// Profile a system, into the timing of its name in `BehaviorStats`
+    pub fn new(world: &mut World, system: S) -> Self {
+        let mut behavior_stats = world.get_resource_or_insert_with(BehaviorStats::default);
+        let stats = behavior_stats.profile(&system.name());
+        let profiling = behavior_stats.profiling.clone();
+        Self {
+            system,
+            stats,
+            profiling,
+        }
+    }
+
+    fn start(&self) -> Option<Instant> {
+        self.profiling.load(Ordering::Relaxed).then(Instant::now)
+    }
+
+    fn add_run(&self, start: Option<Instant>) {
+        let start = match start {
+            Some(start) => start,
+            None => return,
+        };
+        let elapsed = start.elapsed();
+        if let Ok(mut stats) = self.stats.lock() {
+            stats.add_run(elapsed);
+        }
+    }
+}
+
+impl<S> System for ProfiledSystem<S>
+where
+    S: System<In = (), Out = ()>,
+{
+    type In = ();
+    type Out = ();
+
+    fn name(&self) -> Cow<'static, str> {
+        self.system.name()
+    }
+
+    fn component_access(&self) -> &Access<ComponentId> {
+        self.system.component_access()
+    }
+
+    fn archetype_component_access(&self) -> &Access<ArchetypeComponentId> {
+        self.system.archetype_component_access()
+    }
+
+    fn is_send(&self) -> bool {
+        self.system.is_send()
+    }
+
+    unsafe fn run_unsafe(&mut self, input: Self::In, world: &World) -> Self::Out {
+        let start = self.start();
+        self.system.run_unsafe(input, world);
+        self.add_run(start);
+    }
+
+    fn run(&mut self, input: Self::In, world: &mut World) -> Self::Out {
+        let start = self.start();
+        self.system.run(input, world);
+        self.add_run(start);
+    }
+
+    fn apply_buffers(&mut self, world: &mut World) {
+        self.system.apply_buffers(world);
+    }
+
+    fn initialize(&mut self, world: &mut World) {
+        self.system.initialize(world);
+    }
+
+    fn update_archetype_component_access(&mut self, world: &World) {
+        self.system.update_archetype_component_access(world);
+    }
+
+    fn check_change_tick(&mut self, change_tick: u32) {
+        self.system.check_change_tick(change_tick);
+    }
+
+    fn default_labels(&self) -> Vec<SystemLabelId> {
+        self.system.default_labels()
+    }
+}
+
+/// Close the frame of system timings, and report them to `Diagnostics` if there are any
+pub fn update_stats(
+    mut stats: ResMut<BehaviorStats>,
+    removed: RemovedComponents<BehaviorNode>,
+    diagnostics: Option<ResMut<Diagnostics>>,
+) {
+    // Nodes despawned while running never end
+    for entity in removed.iter() {
+        stats.started.remove(&entity);
+    }
+
+    let activations = std::mem::take(&mut stats.frame_activations);
+    let mut frame = Vec::with_capacity(stats.systems.len());
+    for (name, system) in stats.systems.iter() {
+        if let Ok(mut system) = system.lock() {
+            frame.push((name, system.name.clone(), system.end_frame()));
+        }
+    }
+
+    let mut diagnostics = match diagnostics {
+        Some(diagnostics) => diagnostics,
+        None => return,
+    };
+    if diagnostics.get(BehaviorStats::ACTIVATIONS).is_none() {
+        diagnostics.add(Diagnostic::new(
+            BehaviorStats::ACTIVATIONS,
+            "behavior activations",
+            20,
+        ));
+    }
+    diagnostics.add_measurement(BehaviorStats::ACTIVATIONS, activations as f64);
+    for (name, short, elapsed) in frame {
+        let id = BehaviorStats::system_diagnostic(name);
+        if diagnostics.get(id).is_none() {
+            diagnostics.add(Diagnostic::new(id, format!("bt {}", short), 20).with_suffix("ms"));
+        }
+        diagnostics.add_measurement(id, millis(elapsed));
+    }
+}
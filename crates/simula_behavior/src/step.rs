@@ -0,0 +1,95 @@
+use crate::{
+    complete_behavior, start_behavior, stats::ProfiledSystem, stop_behavior, update_behavior,
+};
+use bevy::prelude::*;
+
+/// How far behavior trees progress every frame
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub enum BehaviorExecution {
+    /// One step per frame, every cursor handoff waits for the next frame
+    #[default]
+    PerFrame,
+    /// Keep stepping within the frame until no tree makes progress, that is until they
+    /// all reach running leaves or complete, with at most `budget` extra steps
+    RunToQuiescence { budget: usize },
+}
+
+/// Number of behavior state transitions, compared between steps to detect progress
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct BehaviorTransitions(pub u64);
+
+impl BehaviorTransitions {
+    pub fn increment(&mut self) {
+        self.0 = self.0.wrapping_add(1);
+    }
+}
+
+/// Behavior systems run again for extra steps within a frame
+pub struct BehaviorSteps {
+    /// Node systems, like `sequencer::run`
+    pub nodes: SystemStage,
+    /// Stop, complete and start behaviors once nodes ran
+    pub lifecycle: SystemStage,
+}
+
+impl Default for BehaviorSteps {
+    fn default() -> Self {
+        let mut lifecycle = SystemStage::single_threaded();
+        lifecycle.add_system(
+            stop_behavior.chain(update_behavior.chain(complete_behavior.chain(start_behavior))),
+        );
+        Self {
+            nodes: SystemStage::parallel(),
+            lifecycle,
+        }
+    }
+}
+
+/// Add behavior node systems, so they also run in extra steps and get profiled. Each system
+/// is added once, to `BehaviorSteps`, and runs in the first step of the frame from `Update`.
+pub trait BehaviorAppExt {
+    fn add_behavior_system<Params>(&mut self, system: impl IntoSystem<(), (), Params>)
+        -> &mut Self;
+}
+
+impl BehaviorAppExt for App {
+    fn add_behavior_system<Params>(
+        &mut self,
+        system: impl IntoSystem<(), (), Params>,
+    ) -> &mut Self {
+        self.init_resource::<BehaviorSteps>();
+        let system = ProfiledSystem::new(&mut self.world, IntoSystem::into_system(system));
+        self.world
+            .resource_mut::<BehaviorSteps>()
+            .nodes
+            .add_system(system);
+        self
+    }
+}
+
+/// Run node systems for the first step of the frame
+pub fn run_behavior_nodes(world: &mut World) {
+    world.init_resource::<BehaviorSteps>();
+    world.resource_scope(|world, mut steps: Mut<BehaviorSteps>| {
+        steps.nodes.run(world);
+    });
+}
+
+/// Run extra behavior steps until trees stop making progress, or the budget runs out
+pub fn run_to_quiescence(world: &mut World) {
+    let budget = match world.get_resource::<BehaviorExecution>() {
+        Some(BehaviorExecution::RunToQuiescence { budget }) => *budget,
+        _ => return,
+    };
+    world.init_resource::<BehaviorTransitions>();
+    world.resource_scope(|world, mut steps: Mut<BehaviorSteps>| {
+        for _ in 0..budget {
+            let before = *world.resource::<BehaviorTransitions>();
+            steps.nodes.run(world);
+            steps.lifecycle.run(world);
+            if *world.resource::<BehaviorTransitions>() == before {
+                break;
+            }
+        }
+    });
+}
@@ -1,97 +1,333 @@
 use crate::{
-    actions::*, asset::BehaviorDocument, complete_behavior, composites::*, decorators::*,
-    start_behavior, BehaviorCursor, BehaviorInfo, BehaviorSpawner, BehaviorTrace, BehaviorTree,
+    actions::*,
+    asset::{
+        behavior_loader, behavior_reloader, BTNode, BehaviorAsset, BehaviorAssetLoader,
+        BehaviorDocument, BehaviorLoadFailed, BehaviorReload,
+    },
+    behavior_builtins,
+    blackboard::BlackboardValue,
+    clock::{update_clock, BehaviorClock},
+    complete_behavior,
+    composites::*,
+    control::update_tree_control,
+    decorators::*,
+    instance::run_instances,
+    recorder::{update_recorder, BehaviorRecorder},
+    start_behavior,
+    stats::{update_stats, BehaviorStats},
+    step::{run_behavior_nodes, run_to_quiescence, BehaviorAppExt, BehaviorExecution},
+    stop_behavior, update_behavior, BehaviorBlackboard, BehaviorCursor, BehaviorSpawner,
+    BehaviorTrace, BehaviorTree,
 };
 use bevy::{
-    ecs::system::{CommandQueue, EntityCommands},
+    asset::{AssetServerSettings, LoadState},
+    ecs::system::CommandQueue,
     prelude::*,
     reflect::TypeUuid,
+    utils::HashSet,
 };
-use serde::{Deserialize, Serialize};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::collections::BTreeMap;
 
 pub const MAX_ITERS: usize = 200;
 
+/// Most frames to wait for assets to load
+pub const MAX_LOAD_ITERS: usize = 10_000;
+
+/// Simulated seconds per frame, behaviors see the same time whatever the machine
+pub const FIXED_STEP: f64 = 1.0 / 60.0;
+
 pub fn test_app(app: &mut App) -> &mut App {
+    // Behaviors run on simulated time
+    app.insert_resource(BehaviorClock::fixed(FIXED_STEP));
+    app.add_system_to_stage(CoreStage::First, update_clock.chain(update_tree_control));
+    app.init_resource::<BehaviorRecorder>();
+    app.add_system_to_stage(CoreStage::First, update_recorder);
+    app.init_resource::<BehaviorStats>();
+    app.add_system_to_stage(CoreStage::Last, update_stats);
     // Add the behaviors system to the app
+    app.add_system(stop_behavior);
+    app.add_system(update_behavior);
     app.add_system(start_behavior);
     app.add_system(complete_behavior);
-    app.add_system(sequencer::run);
-    app.add_system(selector::run);
-    app.add_system(all::run);
-    app.add_system(any::run);
-    app.add_system(repeater::run);
-    app.add_system(inverter::run);
-    app.add_system(succeeder::run);
-    app.add_system(delay::run);
-    app.add_system(debug::run);
+    app.add_behavior_system(sequencer::run);
+    app.add_behavior_system(selector::run);
+    app.add_behavior_system(all::run);
+    app.add_behavior_system(any::run);
+    app.add_behavior_system(parallel::run);
+    app.add_behavior_system(utility_selector::run);
+    app.add_behavior_system(weighted_selector::run);
+    app.add_behavior_system(reactive_sequence::run);
+    app.add_behavior_system(reactive_selector::run);
+    app.add_behavior_system(repeater::run);
+    app.add_behavior_system(inverter::run);
+    app.add_behavior_system(succeeder::run);
+    app.add_behavior_system(observer::run);
+    app.add_behavior_system(timeout::run);
+    app.add_behavior_system(cooldown::run);
+    app.add_behavior_system(retry::run);
+    app.add_behavior_system(force_failure::run);
+    app.add_behavior_system(limit::run);
+    app.add_behavior_system(delay::run);
+    app.add_behavior_system(debug::run);
+    app.add_behavior_system(condition::run);
+    app.add_behavior_system(set_blackboard::run);
+    app.add_behavior_system(drive_signal::run);
+    app.add_behavior_system(sample_signal::run);
+    app.add_system(run_instances::<TestBehavior>);
+    app.add_system(run_behavior_nodes.exclusive_system().at_start());
+    app.add_system_to_stage(
+        CoreStage::PostUpdate,
+        run_to_quiescence.exclusive_system().at_end(),
+    );
     app.init_resource::<BehaviorTrace>();
     app
 }
 
-#[derive(Serialize, Deserialize, TypeUuid)]
+#[behavior_builtins]
+#[derive(Debug, Clone, Serialize, Deserialize, TypeUuid, BehaviorSpawner)]
 #[uuid = "3d6cc56a-542e-11ed-9abb-02a179e5df2b"]
-pub enum TestBehavior {
-    Debug(Debug),
-    Selector(Selector),
-    Sequencer(Sequencer),
-    All(All),
-    Any(Any),
-    Repeater(Repeater),
-    Inverter(Inverter),
-    Succeeder(Succeeder),
-    Delay(Delay),
-}
-
-impl Default for TestBehavior {
-    fn default() -> Self {
-        Self::Debug(Debug::default())
+pub enum TestBehavior {}
+
+pub fn trace_behavior(behavior: &str) -> BehaviorTrace {
+    // Create app
+    let mut app = App::new();
+    test_app(&mut app);
+    run_behavior(&mut app, behavior, MAX_ITERS)
+}
+
+/// Trace a behavior for a number of simulated seconds, stepped by `FIXED_STEP`
+pub fn trace_behavior_for(behavior: &str, seconds: f64) -> BehaviorTrace {
+    // Create app
+    let mut app = App::new();
+    test_app(&mut app);
+    let frames = (seconds / FIXED_STEP).round() as usize;
+    run_behavior(&mut app, behavior, frames)
+}
+
+/// Trace a behavior for a number of frames, running each frame to quiescence with at
+/// most `budget` extra steps
+pub fn trace_behavior_steps(behavior: &str, frames: usize, budget: usize) -> BehaviorTrace {
+    // Create app
+    let mut app = App::new();
+    test_app(&mut app);
+    app.insert_resource(BehaviorExecution::RunToQuiescence { budget });
+    run_behavior(&mut app, behavior, frames)
+}
+
+/// Record a behavior, optionally with blackboard snapshots, returns the recorded events
+pub fn record_behavior(behavior: &str, snapshot_blackboard: bool) -> BehaviorRecorder {
+    // Create app
+    let mut app = App::new();
+    test_app(&mut app);
+    let mut recorder = app.world.resource_mut::<BehaviorRecorder>();
+    recorder.enabled = true;
+    recorder.snapshot_blackboard = snapshot_blackboard;
+    run_behavior(&mut app, behavior, MAX_ITERS);
+    app.world.resource::<BehaviorRecorder>().clone()
+}
+
+/// Trace a behavior that loads other behavior assets, from the workspace assets folder
+pub fn trace_behavior_with_assets(behavior: &str) -> BehaviorTrace {
+    let mut app = App::new();
+    asset_test_app(&mut app);
+    // Subtrees run as soon as they are loaded, wait for them so the trace doesn't depend on disk
+    let _subtrees = load_subtrees(&mut app, behavior);
+    run_behavior(&mut app, behavior, MAX_ITERS)
+}
+
+/// Trace a behavior spawned from an asset file, like a game would
+pub fn trace_behavior_asset(path: &str) -> BehaviorTrace {
+    let mut app = App::new();
+    asset_test_app(&mut app);
+    // Wait for the asset and the subtrees it links, as if a subtree linked it
+    let behavior = format!("(root:(\"Load\", Subtree((path:{:?}))))", path);
+    let _subtrees = load_subtrees(&mut app, &behavior);
+
+    let document = app.world.resource::<AssetServer>().load(path);
+    let mut command_queue = CommandQueue::default();
+    let mut commands = Commands::new(&mut command_queue, &app.world);
+    let tree = BehaviorTree::from_asset::<TestBehavior>(None, &mut commands, document);
+    if let Some(root) = tree.root {
+        commands.entity(root).insert(BehaviorCursor);
     }
+    commands.spawn().insert(tree);
+    command_queue.apply(&mut app.world);
+    update_frames(&mut app, MAX_ITERS)
+}
+
+/// Test app, with asset loading from the repository assets
+fn asset_test_app(app: &mut App) {
+    app.insert_resource(AssetServerSettings {
+        asset_folder: "../../assets".to_string(),
+        ..default()
+    })
+    .add_plugin(bevy::core::CorePlugin::default())
+    .add_plugin(bevy::time::TimePlugin::default())
+    .add_plugin(AssetPlugin::default())
+    .add_asset::<BehaviorAsset>()
+    .init_asset_loader::<BehaviorAssetLoader>()
+    .add_event::<BehaviorLoadFailed>()
+    .add_system(behavior_loader::<TestBehavior>)
+    .add_system(subtree::run::<TestBehavior>);
+    test_app(app);
 }
 
-impl BehaviorSpawner for TestBehavior {
-    fn insert(&self, commands: &mut EntityCommands) {
-        match self {
-            TestBehavior::Debug(data) => BehaviorInfo::insert_with(commands, data),
-            TestBehavior::Selector(data) => BehaviorInfo::insert_with(commands, data),
-            TestBehavior::Sequencer(data) => BehaviorInfo::insert_with(commands, data),
-            TestBehavior::All(data) => BehaviorInfo::insert_with(commands, data),
-            TestBehavior::Any(data) => BehaviorInfo::insert_with(commands, data),
-            TestBehavior::Repeater(data) => BehaviorInfo::insert_with(commands, data),
-            TestBehavior::Inverter(data) => BehaviorInfo::insert_with(commands, data),
-            TestBehavior::Succeeder(data) => BehaviorInfo::insert_with(commands, data),
-            TestBehavior::Delay(data) => BehaviorInfo::insert_with(commands, data),
+/// Load the subtree assets of a document, and of the documents they link, updating the app
+/// until they are all loaded or failed. Returns the handles, which keep the assets loaded.
+fn load_subtrees(app: &mut App, behavior: &str) -> Vec<Handle<BehaviorAsset>> {
+    let document = ron::from_str::<BehaviorDocument<TestBehavior>>(behavior).unwrap();
+    let asset_server = app.world.resource::<AssetServer>().clone();
+    let mut paths = vec![];
+    subtree_paths(&document.root, &mut paths);
+    let mut seen = HashSet::new();
+    let mut handles = vec![];
+    let mut loading = vec![];
+    for _ in 0..MAX_LOAD_ITERS {
+        for path in paths.drain(..) {
+            if seen.insert(path.clone()) {
+                let handle: Handle<BehaviorAsset> = asset_server.load(path.as_str());
+                handles.push(handle.clone());
+                loading.push(handle);
+            }
+        }
+        let assets = app.world.resource::<Assets<BehaviorAsset>>();
+        loading.retain(|handle| match asset_server.get_load_state(handle) {
+            LoadState::Loaded => {
+                if let Some(Ok(document)) = assets
+                    .get(handle)
+                    .map(|asset| asset.document::<TestBehavior>())
+                {
+                    subtree_paths(&document.root, &mut paths);
+                }
+                false
+            }
+            LoadState::Failed => false,
+            _ => true,
+        });
+        if loading.is_empty() && paths.is_empty() {
+            return handles;
         }
+        app.update();
     }
+    panic!("Subtree assets did not load in {} frames", MAX_LOAD_ITERS);
 }
 
-pub fn trace_behavior(behavior: &str) -> BehaviorTrace {
-    // Load behavior tree from RON string
-    let document = ron::from_str::<BehaviorDocument<TestBehavior>>(behavior);
-    assert!(document.is_ok());
-    let document = document.unwrap();
-    // println!("Loaded behavior tree: \n{:#?}", ron::ser::to_string_pretty(&document, Default::default()).unwrap());
+fn subtree_paths(node: &BTNode<TestBehavior>, paths: &mut Vec<String>) {
+    let BTNode(_, behavior, children) = node;
+    if let TestBehavior::Subtree(subtree) = behavior {
+        paths.push(subtree.path.clone());
+    }
+    for child in children.iter() {
+        subtree_paths(child, paths);
+    }
+}
 
-    // Create app
+/// Trace a behavior spawned from an asset, which is changed to another document halfway.
+/// Returns the trace and the final blackboard values.
+pub fn trace_behavior_reload(
+    before: &str,
+    after: &str,
+    reload: BehaviorReload,
+) -> (BehaviorTrace, BTreeMap<String, BlackboardValue>) {
+    // Create app, with assets but no asset files
     let mut app = App::new();
-    app.add_plugin(bevy::time::TimePlugin::default());
+    app.add_plugin(bevy::core::CorePlugin::default())
+        .add_plugin(bevy::time::TimePlugin::default())
+        .add_plugin(AssetPlugin::default())
+        .add_asset::<BehaviorAsset>()
+        .add_event::<BehaviorLoadFailed>()
+        .add_system(behavior_loader::<TestBehavior>)
+        .add_system(behavior_reloader::<TestBehavior>);
     test_app(&mut app);
+
+    // Spawn tree from asset
+    let document = app
+        .world
+        .resource_mut::<Assets<BehaviorAsset>>()
+        .add(BehaviorAsset::new("reload.bht.ron", before));
+    let mut command_queue = CommandQueue::default();
+    let mut commands = Commands::new(&mut command_queue, &app.world);
+    let tree = BehaviorTree::from_asset_with::<TestBehavior>(
+        None,
+        &mut commands,
+        document.clone(),
+        reload,
+    );
+    if let Some(root) = tree.root {
+        commands.entity(root).insert(BehaviorCursor);
+    }
+    let tree_entity = commands.spawn().insert(tree).id();
+    command_queue.apply(&mut app.world);
+
+    // Run app, changing the asset halfway
+    for iter in 0..MAX_ITERS {
+        if iter == MAX_ITERS / 2 {
+            let mut assets = app.world.resource_mut::<Assets<BehaviorAsset>>();
+            if let Some(asset) = assets.get_mut(&document) {
+                *asset = BehaviorAsset::new("reload.bht.ron", after);
+            }
+        }
+        app.update();
+    }
+
+    // Get app trace and blackboard
+    let trace = app.world.get_resource::<BehaviorTrace>().unwrap().clone();
+    let values = app
+        .world
+        .get::<BehaviorBlackboard>(tree_entity)
+        .map(|blackboard| blackboard.to_values())
+        .unwrap_or_default();
+    (trace, values)
+}
+
+/// Spawn a behavior tree from a RON document, ready to run, returns the tree entity
+pub fn spawn_test_tree(app: &mut App, behavior: &str) -> Entity {
+    spawn_test_tree_of::<TestBehavior>(app, behavior)
+}
+
+/// Spawn a behavior tree from a RON document of any behavior enum, returns the tree entity
+pub fn spawn_test_tree_of<T>(app: &mut App, behavior: &str) -> Entity
+where
+    T: Default + BehaviorSpawner + DeserializeOwned,
+{
+    // Load behavior tree from RON string
+    let document = ron::from_str::<BehaviorDocument<T>>(behavior).unwrap();
+
     let mut command_queue = CommandQueue::default();
     let mut commands = Commands::new(&mut command_queue, &app.world);
 
     // Spawn tree
-    let entity = BehaviorTree::spawn_tree(None, &mut commands, &document.root);
-    commands.entity(entity).insert(BehaviorCursor);
+    let root = BehaviorTree::spawn_tree(None, &mut commands, &document.root);
+    commands.entity(root).insert(BehaviorCursor);
+    let tree = commands
+        .spawn()
+        .insert(BehaviorTree { root: Some(root) })
+        .insert(BehaviorBlackboard::from_document(&document))
+        .id();
 
     // Apply commands
     command_queue.apply(&mut app.world);
+    tree
+}
 
-    // Run app
-    let mut iters = 0;
-    while iters < MAX_ITERS {
-        iters = iters + 1;
+/// Update the app for a number of frames, returns the trace so far
+pub fn update_frames(app: &mut App, frames: usize) -> BehaviorTrace {
+    for _ in 0..frames {
         app.update();
     }
+    app.world.resource::<BehaviorTrace>().clone()
+}
+
+/// Index of a line in a trace, panics if it was never traced
+pub fn position(trace: &BehaviorTrace, line: &str) -> usize {
+    trace
+        .iter()
+        .position(|traced| traced == line)
+        .unwrap_or_else(|| panic!("Missing trace: {}", line))
+}
 
-    // Get app trace
-    app.world.get_resource::<BehaviorTrace>().unwrap().clone()
+fn run_behavior(app: &mut App, behavior: &str, frames: usize) -> BehaviorTrace {
+    spawn_test_tree(app, behavior);
+    update_frames(app, frames)
 }
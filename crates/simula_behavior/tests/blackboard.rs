@@ -0,0 +1,80 @@
+use simula_behavior::{prelude::*, test::*};
+
+#[test]
+fn blackboard_document_defaults() {
+    let behavior = r#"
+    (
+        root:("Do an action", Debug((message:"Hello, from DebugMessage0!"))),
+        blackboard: {
+            "ready": Bool(true),
+            "count": Int(3),
+            "duration": Float(1.5),
+            "target": String("Workbench"),
+        },
+    )
+    "#;
+    let document = ron::from_str::<BehaviorDocument<TestBehavior>>(behavior).unwrap();
+    let blackboard = BehaviorBlackboard::from_document(&document);
+    assert_eq!(blackboard.len(), 4);
+    assert_eq!(blackboard.get::<bool>("ready"), Some(&true));
+    assert_eq!(blackboard.get::<i64>("count"), Some(&3));
+    assert_eq!(blackboard.get::<f64>("duration"), Some(&1.5));
+    assert_eq!(
+        blackboard.get::<String>("target"),
+        Some(&"Workbench".to_string())
+    );
+}
+
+#[test]
+fn blackboard_typed_access() {
+    let mut blackboard = BehaviorBlackboard::default();
+    blackboard.set("count", 1i64);
+    assert_eq!(blackboard.get::<i64>("count"), Some(&1));
+    assert_eq!(blackboard.get::<f64>("count"), None);
+    assert_eq!(blackboard.get::<i64>("missing"), None);
+
+    *blackboard.get_mut::<i64>("count").unwrap() += 1;
+    assert_eq!(blackboard.get::<i64>("count"), Some(&2));
+
+    blackboard.set("count", "many".to_string());
+    assert_eq!(blackboard.get::<i64>("count"), None);
+    assert!(blackboard.remove("count").is_some());
+    assert!(blackboard.is_empty());
+}
+
+#[test]
+fn blackboard_merge_keeps_existing() {
+    let behavior = r#"
+    (
+        root:("Do an action", Debug(())),
+        blackboard: {
+            "count": Int(3),
+            "ready": Bool(false),
+        },
+    )
+    "#;
+    let document = ron::from_str::<BehaviorDocument<TestBehavior>>(behavior).unwrap();
+    let mut blackboard = BehaviorBlackboard::default();
+    blackboard.set("count", 10i64);
+    blackboard.merge_defaults(&document);
+    assert_eq!(blackboard.get::<i64>("count"), Some(&10));
+    assert_eq!(blackboard.get::<bool>("ready"), Some(&false));
+}
+
+#[test]
+fn blackboard_prop_binding() {
+    let mut blackboard = BehaviorBlackboard::default();
+    blackboard.set("duration", 2.5f64);
+
+    let literal = BehaviorProp::Value(1.0f64);
+    let bound = BehaviorProp::<f64>::Blackboard("duration".to_string());
+    let unbound = BehaviorProp::<f64>::Blackboard("missing".to_string());
+    assert_eq!(literal.get(Some(&blackboard)), Some(1.0));
+    assert_eq!(bound.get(Some(&blackboard)), Some(2.5));
+    assert_eq!(bound.get(None), None);
+    assert_eq!(unbound.get(Some(&blackboard)), None);
+
+    let mut bound = bound;
+    bound.set(Some(&mut blackboard), 4.0);
+    assert_eq!(blackboard.get::<f64>("duration"), Some(&4.0));
+}
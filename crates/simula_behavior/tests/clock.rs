@@ -0,0 +1,69 @@
+use simula_behavior::{prelude::*, test::*, BehaviorTrace};
+
+#[test]
+fn clock_delay_not_elapsed() {
+    let behavior = r#"
+    (
+        root:("Wait a second", Delay((duration: 1.0)))
+    )
+    "#;
+    let trace = trace_behavior_for(behavior, 0.5);
+    println!("{:#?}", trace);
+    let expected_trace = BehaviorTrace::from_list(&["[0] STARTED Wait a second"]);
+    assert_eq!(&trace, &expected_trace);
+}
+
+#[test]
+fn clock_delay_elapsed() {
+    let behavior = r#"
+    (
+        root:("Wait a second", Delay((duration: 1.0)))
+    )
+    "#;
+    let trace = trace_behavior_for(behavior, 2.0);
+    println!("{:#?}", trace);
+    let expected_trace =
+        BehaviorTrace::from_list(&["[0] STARTED Wait a second", "[0] SUCCESS Wait a second"]);
+    assert_eq!(&trace, &expected_trace);
+}
+
+#[test]
+fn clock_timeout_race() {
+    let behavior = r#"
+    (
+        root:(
+            "Do both",
+            Sequencer(()),
+            [
+                ("In time", Timeout((duration: 1.0)), [("Take short", Debug((duration: 0.5)))]),
+                ("Too late", Timeout((duration: 0.5)), [("Take long", Debug((duration: 1.0)))]),
+            ]
+        )
+    )
+    "#;
+    let trace = trace_behavior_for(behavior, 5.0);
+    println!("{:#?}", trace);
+    let expected_trace = BehaviorTrace::from_list(&[
+        "[0] STARTED Do both",
+        "[1] STARTED In time",
+        "[2] STARTED Take short",
+        "[2] SUCCESS Take short",
+        "[1] SUCCESS In time",
+        "[3] STARTED Too late",
+        "[4] STARTED Take long",
+        "[4] STOPPED Take long",
+        "[3] FAILURE Too late",
+        "[0] FAILURE Do both",
+    ]);
+    assert_eq!(&trace, &expected_trace);
+}
+
+#[test]
+fn clock_manual() {
+    let mut clock = BehaviorClock::manual();
+    assert_eq!(clock.mode, BehaviorClockMode::Manual);
+    assert_eq!(clock.elapsed(), 0.0);
+    clock.advance(1.5);
+    clock.advance(0.5);
+    assert_eq!(clock.elapsed(), 2.0);
+}
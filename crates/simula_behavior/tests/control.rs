@@ -0,0 +1,121 @@
+use bevy::prelude::*;
+use simula_behavior::{prelude::*, test::*};
+
+/// Spawn a behavior tree with a control, returns the tree entity
+fn spawn_tree(app: &mut App, behavior: &str, control: BehaviorTreeControl) -> Entity {
+    let tree = spawn_test_tree(app, behavior);
+    app.world.entity_mut(tree).insert(control);
+    tree
+}
+
+fn control(app: &mut App, tree: Entity) -> Mut<BehaviorTreeControl> {
+    app.world.get_mut::<BehaviorTreeControl>(tree).unwrap()
+}
+
+const DELAY: &str = r#"
+(
+    root:("Wait a second", Delay((duration: 1.0))),
+)
+"#;
+
+#[test]
+fn paused_tree_does_not_start() {
+    let mut app = App::new();
+    test_app(&mut app);
+    let tree = spawn_tree(&mut app, DELAY, BehaviorTreeControl::paused());
+
+    assert_eq!(update_frames(&mut app, 120), BehaviorTrace::from_list(&[]));
+
+    control(&mut app, tree).resume();
+    assert_eq!(
+        update_frames(&mut app, 90),
+        BehaviorTrace::from_list(&["[0] STARTED Wait a second", "[0] SUCCESS Wait a second"])
+    );
+}
+
+#[test]
+fn paused_tree_freezes_time() {
+    let mut app = App::new();
+    test_app(&mut app);
+    let tree = spawn_tree(&mut app, DELAY, BehaviorTreeControl::default());
+
+    // Half the delay, then a long pause
+    update_frames(&mut app, 30);
+    control(&mut app, tree).pause();
+    update_frames(&mut app, 120);
+    assert!(control(&mut app, tree).paused_time() > 1.9);
+
+    // Not done yet, the paused time doesn't count
+    control(&mut app, tree).resume();
+    assert_eq!(
+        update_frames(&mut app, 20),
+        BehaviorTrace::from_list(&["[0] STARTED Wait a second"])
+    );
+    assert_eq!(
+        update_frames(&mut app, 20),
+        BehaviorTrace::from_list(&["[0] STARTED Wait a second", "[0] SUCCESS Wait a second"])
+    );
+}
+
+#[test]
+fn step_runs_one_frame() {
+    let mut app = App::new();
+    test_app(&mut app);
+    let tree = spawn_tree(&mut app, DELAY, BehaviorTreeControl::paused());
+
+    assert!(update_frames(&mut app, 10).is_empty());
+    control(&mut app, tree).step();
+    assert_eq!(
+        update_frames(&mut app, 10),
+        BehaviorTrace::from_list(&["[0] STARTED Wait a second"])
+    );
+    assert!(control(&mut app, tree).is_paused());
+
+    // Stepping a running tree pauses it
+    control(&mut app, tree).resume();
+    update_frames(&mut app, 10);
+    control(&mut app, tree).step();
+    update_frames(&mut app, 120);
+    assert!(control(&mut app, tree).is_paused());
+    assert_eq!(
+        update_frames(&mut app, 0),
+        BehaviorTrace::from_list(&["[0] STARTED Wait a second"])
+    );
+}
+
+#[test]
+fn restart_starts_over_from_root() {
+    let behavior = r#"
+    (
+        root:(
+            "In order",
+            Sequencer(()),
+            [
+                ("First", Debug(())),
+                ("Second", Debug(())),
+            ]
+        ),
+    )
+    "#;
+    let mut app = App::new();
+    test_app(&mut app);
+    let tree = spawn_tree(&mut app, behavior, BehaviorTreeControl::default());
+    let first_run = update_frames(&mut app, 20);
+    assert_eq!(
+        first_run,
+        BehaviorTrace::from_list(&[
+            "[0] STARTED In order",
+            "[1] STARTED First",
+            "[1] SUCCESS First",
+            "[2] STARTED Second",
+            "[2] SUCCESS Second",
+            "[0] SUCCESS In order",
+        ])
+    );
+
+    control(&mut app, tree).restart();
+    let trace = update_frames(&mut app, 20);
+    assert!(!control(&mut app, tree).is_restarting());
+    assert_eq!(trace[first_run.len()], "[0] STOPPED In order");
+    assert_eq!(&trace[first_run.len() + 1..], &first_run[..]);
+}
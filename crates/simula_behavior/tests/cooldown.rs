@@ -0,0 +1,60 @@
+use simula_behavior::{test::*, BehaviorTrace};
+
+#[test]
+fn cooldown_fails_too_soon() {
+    let behavior = r#"
+    (
+        root:(
+            "Do a few times",
+            Repeater((repeat:Times(2))),
+            [
+                (
+                    "Only once in a while",
+                    Cooldown((duration: 1000.0)),
+                    [
+                        ("Do action", Debug((message:"Hello, from DebugMessage0!"))),
+                    ]
+                )
+            ]
+        )
+    )
+    "#;
+    let trace = trace_behavior(behavior);
+    println!("{:#?}", trace);
+    let expected_trace = BehaviorTrace::from_list(&[
+        "[0] STARTED Do a few times",
+        "[1] STARTED Only once in a while",
+        "[2] STARTED Do action",
+        "[2] SUCCESS Do action",
+        "[1] SUCCESS Only once in a while",
+        "[0] STARTED Do a few times",
+        "[1] STARTED Only once in a while",
+        "[1] FAILURE Only once in a while",
+        "[0] SUCCESS Do a few times",
+    ]);
+    assert_eq!(&trace, &expected_trace);
+}
+
+#[test]
+fn cooldown_passes_failure() {
+    let behavior = r#"
+    (
+        root:(
+            "Only once in a while",
+            Cooldown((duration: 1000.0)),
+            [
+                ("Do action", Debug((message:"Hello, from DebugMessage0!", fail:true))),
+            ]
+        )
+    )
+    "#;
+    let trace = trace_behavior(behavior);
+    println!("{:#?}", trace);
+    let expected_trace = BehaviorTrace::from_list(&[
+        "[0] STARTED Only once in a while",
+        "[1] STARTED Do action",
+        "[1] FAILURE Do action",
+        "[0] FAILURE Only once in a while",
+    ]);
+    assert_eq!(&trace, &expected_trace);
+}
@@ -0,0 +1,184 @@
+use bevy::{ecs::system::CommandQueue, prelude::*};
+use simula_behavior::{prelude::*, test::*};
+
+fn spawn_document(world: &mut World, behavior: &str) -> (BehaviorDocument<TestBehavior>, Entity) {
+    let document = ron::from_str::<BehaviorDocument<TestBehavior>>(behavior).unwrap();
+    let mut command_queue = CommandQueue::default();
+    let mut commands = Commands::new(&mut command_queue, world);
+    let root = BehaviorTree::spawn_tree(None, &mut commands, &document.root);
+    let tree = commands
+        .spawn()
+        .insert(BehaviorTree { root: Some(root) })
+        .insert(BehaviorBlackboard::from_document(&document))
+        .id();
+    command_queue.apply(world);
+    world.get_mut::<BehaviorNode>(root).unwrap().tree = Some(tree);
+    (document, tree)
+}
+
+#[test]
+fn document_round_trip() {
+    let behavior = r#"
+    (
+        root:(
+            "Do a few times",
+            Repeater((repeat:Times(2))),
+            [
+                (
+                    "In this order",
+                    Sequencer(()),
+                    [
+                        ("Do an action", Debug((message:"Hello, from DebugMessage0!"))),
+                        ("Wait a bit", Delay((duration: 1.5))),
+                        ("Is ready", Condition((key: "ready", test: IsTrue))),
+                    ]
+                ),
+            ]
+        ),
+        blackboard: {
+            "ready": Bool(true),
+        },
+    )
+    "#;
+    let mut world = World::new();
+    let (document, tree) = spawn_document(&mut world, behavior);
+    let behavior_tree = world.get::<BehaviorTree>(tree).unwrap().clone();
+    let saved = behavior_tree.to_document::<TestBehavior>(&world).unwrap();
+    assert_eq!(saved.to_ron().unwrap(), document.to_ron().unwrap());
+
+    // Saved text loads back into the same document
+    let loaded = ron::from_str::<BehaviorDocument<TestBehavior>>(&saved.to_ron().unwrap());
+    assert_eq!(
+        loaded.unwrap().to_ron().unwrap(),
+        document.to_ron().unwrap()
+    );
+}
+
+#[test]
+fn document_keeps_tweaks() {
+    let behavior = r#"
+    (
+        root:(
+            "In this order",
+            Sequencer(()),
+            [
+                ("Do an action", Debug((message:"Hello, from DebugMessage0!"))),
+            ]
+        )
+    )
+    "#;
+    let mut world = World::new();
+    let (_document, tree) = spawn_document(&mut world, behavior);
+    let behavior_tree = world.get::<BehaviorTree>(tree).unwrap().clone();
+    let action = world
+        .get::<BehaviorChildren>(behavior_tree.root.unwrap())
+        .unwrap()[0];
+    world.get_mut::<Debug>(action).unwrap().message = "Tweaked".to_string();
+    world.entity_mut(action).insert(Name::new("Tweaked action"));
+    world
+        .get_mut::<BehaviorBlackboard>(tree)
+        .unwrap()
+        .set("count", 3i64);
+
+    let saved = behavior_tree.to_document::<TestBehavior>(&world).unwrap();
+    let BTNode(name, data, _) = &saved.root.2[0];
+    assert_eq!(name, "Tweaked action");
+    assert!(matches!(data, TestBehavior::Debug(Debug { message, .. }) if message == "Tweaked"));
+    assert_eq!(
+        saved.blackboard.get("count"),
+        Some(&BlackboardValue::Int(3))
+    );
+}
+
+#[test]
+fn document_unknown_behavior() {
+    let behavior = r#"(
+    root:(
+        "In this order",
+        Sequncer(()),
+    )
+)"#;
+    let error = BehaviorDocument::<TestBehavior>::from_ron("test.bht.ron", behavior).unwrap_err();
+    println!("{}", error);
+    match error {
+        BehaviorDocumentError::UnknownBehavior {
+            path,
+            line,
+            found,
+            suggestion,
+            ..
+        } => {
+            assert_eq!(path, "test.bht.ron");
+            assert_eq!(line, 4);
+            assert_eq!(found, "Sequncer");
+            assert_eq!(suggestion, Some("Sequencer".to_string()));
+        }
+        _ => panic!("Expected unknown behavior, got: {}", error),
+    }
+}
+
+#[test]
+fn document_parse_error() {
+    let behavior = r#"(
+    root:("Do an action", Debug((message:"Hello")),
+)"#;
+    let error = BehaviorDocument::<TestBehavior>::from_ron("test.bht.ron", behavior).unwrap_err();
+    println!("{}", error);
+    assert!(matches!(
+        error,
+        BehaviorDocumentError::Parse { line: 3, .. }
+    ));
+    assert!(error.to_string().starts_with("test.bht.ron:3:"));
+}
+
+#[test]
+fn document_action_with_children() {
+    let behavior = r#"(
+    root:(
+        "Do an action",
+        Debug((message:"Hello")),
+        [
+            ("Do another action", Debug((message:"Goodbye"))),
+        ]
+    )
+)"#;
+    let error = BehaviorDocument::<TestBehavior>::from_ron("test.bht.ron", behavior).unwrap_err();
+    println!("{}", error);
+    assert_eq!(
+        error,
+        BehaviorDocumentError::ActionWithChildren {
+            path: "test.bht.ron".to_string(),
+            node: "Do an action".to_string(),
+        }
+    );
+}
+
+#[test]
+fn document_decorator_children() {
+    let behavior = r#"(
+    root:(
+        "In this order",
+        Sequencer(()),
+        [
+            (
+                "Not this",
+                Inverter(()),
+                [
+                    ("Do an action", Debug((message:"Hello"))),
+                    ("Do another action", Debug((message:"Goodbye"))),
+                ]
+            ),
+        ]
+    )
+)"#;
+    let error = BehaviorDocument::<TestBehavior>::from_ron("test.bht.ron", behavior).unwrap_err();
+    println!("{}", error);
+    assert_eq!(
+        error,
+        BehaviorDocumentError::DecoratorChildren {
+            path: "test.bht.ron".to_string(),
+            node: "In this order/Not this".to_string(),
+            count: 2,
+        }
+    );
+}
@@ -0,0 +1,153 @@
+use bevy::{ecs::system::CommandQueue, prelude::*};
+use simula_behavior::{
+    editor::{BehaviorGraph, BehaviorGraphError},
+    prelude::*,
+    test::*,
+};
+
+const BEHAVIOR: &str = r#"
+(
+    root:(
+        "Sequencer of actions",
+        Sequencer(()),
+        [
+            ("Do action", Debug((message:"Hello, from DebugMessage0!"))),
+            ("Invert", Inverter(()), [("Do another action", Debug((fail: true)))]),
+        ]
+    ),
+    blackboard: {
+        "ready": Bool(true),
+    },
+)
+"#;
+
+fn document() -> BehaviorDocument<TestBehavior> {
+    BehaviorDocument::from_ron("editor.bht.ron", BEHAVIOR).unwrap()
+}
+
+fn find(graph: &BehaviorGraph<TestBehavior>, name: &str) -> usize {
+    graph
+        .iter()
+        .find(|(_, node)| node.name == name)
+        .map(|(id, _)| id)
+        .unwrap_or_else(|| panic!("Missing node: {}", name))
+}
+
+fn names(graph: &BehaviorGraph<TestBehavior>, name: &str) -> Vec<String> {
+    let node = graph.get(find(graph, name)).unwrap();
+    node.children
+        .iter()
+        .map(|child| graph.get(*child).unwrap().name.clone())
+        .collect()
+}
+
+#[test]
+fn editor_document_round_trip() {
+    let document = document();
+    let graph = BehaviorGraph::from_document(&document);
+    assert_eq!(graph.len(), 4);
+    assert_eq!(
+        document.to_ron().unwrap(),
+        graph.to_document().unwrap().to_ron().unwrap()
+    );
+}
+
+#[test]
+fn editor_add_and_reorder() {
+    let mut graph = BehaviorGraph::from_document(&document());
+    let sequencer = find(&graph, "Sequencer of actions");
+    let id = graph.add_node(
+        "Wait".to_string(),
+        TestBehavior::Delay(Delay::default()),
+        Vec2::ZERO,
+    );
+    graph.connect(sequencer, id).unwrap();
+    assert_eq!(
+        names(&graph, "Sequencer of actions"),
+        vec!["Do action", "Invert", "Wait"]
+    );
+    graph.move_child(id, -2).unwrap();
+    assert_eq!(
+        names(&graph, "Sequencer of actions"),
+        vec!["Wait", "Do action", "Invert"]
+    );
+    // Moving past the ends stops there
+    graph.move_child(id, -1).unwrap();
+    assert_eq!(
+        names(&graph, "Sequencer of actions"),
+        vec!["Wait", "Do action", "Invert"]
+    );
+}
+
+#[test]
+fn editor_refuses_invalid_connections() {
+    let mut graph = BehaviorGraph::from_document(&document());
+    let sequencer = find(&graph, "Sequencer of actions");
+    let action = find(&graph, "Do action");
+    let inverter = find(&graph, "Invert");
+    assert_eq!(
+        graph.connect(action, inverter),
+        Err(BehaviorGraphError::ActionWithChildren(
+            "Do action".to_string()
+        ))
+    );
+    assert_eq!(
+        graph.connect(inverter, action),
+        Err(BehaviorGraphError::DecoratorChildren("Invert".to_string()))
+    );
+    assert_eq!(
+        graph.connect(inverter, sequencer),
+        Err(BehaviorGraphError::Cycle("Invert".to_string()))
+    );
+    // Nothing changed
+    assert_eq!(
+        names(&graph, "Sequencer of actions"),
+        vec!["Do action", "Invert"]
+    );
+}
+
+#[test]
+fn editor_delete_and_reconnect() {
+    let mut graph = BehaviorGraph::from_document(&document());
+    let sequencer = find(&graph, "Sequencer of actions");
+    let inverter = find(&graph, "Invert");
+    let another = find(&graph, "Do another action");
+
+    // Children of a deleted node stay, detached and left out of documents
+    graph.remove_node(inverter).unwrap();
+    assert_eq!(graph.len(), 3);
+    assert_eq!(graph.get(another).unwrap().parent, None);
+    assert_eq!(names(&graph, "Sequencer of actions"), vec!["Do action"]);
+    let document = graph.to_document().unwrap();
+    assert_eq!(document.root.2.len(), 1);
+
+    graph.connect(sequencer, another).unwrap();
+    assert_eq!(
+        names(&graph, "Sequencer of actions"),
+        vec!["Do action", "Do another action"]
+    );
+
+    graph.remove_node(sequencer).unwrap();
+    assert!(matches!(
+        graph.to_document(),
+        Err(BehaviorGraphError::NoRoot)
+    ));
+}
+
+#[test]
+fn editor_from_spawned_tree() {
+    let document = document();
+    let mut world = World::new();
+    let mut command_queue = CommandQueue::default();
+    let mut commands = Commands::new(&mut command_queue, &world);
+    let root = BehaviorTree::spawn_tree(None, &mut commands, &document.root);
+    command_queue.apply(&mut world);
+
+    let graph = BehaviorGraph::<TestBehavior>::from_entity(&world, root).unwrap();
+    assert_eq!(graph.len(), 4);
+    let root_node = graph.get(graph.root.unwrap()).unwrap();
+    assert_eq!(root_node.entity, Some(root));
+    let document = graph.to_document().unwrap();
+    assert_eq!(document.root.0, "Sequencer of actions");
+    assert_eq!(document.blackboard.len(), 0);
+}
@@ -0,0 +1,243 @@
+use bevy::{prelude::*, reflect::TypeUuid};
+use serde::{Deserialize, Serialize};
+use simula_behavior::{prelude::*, test::*};
+
+mod ping {
+    use super::*;
+
+    /// A test event, sent on a channel with a value
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct Ping {
+        pub channel: String,
+        pub value: i64,
+    }
+
+    #[derive(Debug, Default, Reflect, Clone, Serialize, Deserialize, Inspectable)]
+    pub struct PingFilter {
+        pub channel: BehaviorProp<String>,
+        /// Blackboard key to store the ping value to
+        #[serde(default)]
+        pub store: String,
+    }
+
+    #[derive(Debug, Default, Reflect, Clone, Serialize, Deserialize, Inspectable)]
+    pub struct PingPayload {
+        pub channel: String,
+        pub value: BehaviorProp<i64>,
+    }
+
+    impl BehaviorEventInfo for Ping {
+        const WAIT_NAME: &'static str = "WaitForPing";
+        const EMIT_NAME: &'static str = "EmitPing";
+        type Filter = PingFilter;
+        type Payload = PingPayload;
+
+        fn matches(&self, filter: &PingFilter, blackboard: Option<&BehaviorBlackboard>) -> bool {
+            filter.channel.get(blackboard).as_ref() == Some(&self.channel)
+        }
+
+        fn from_payload(
+            payload: &PingPayload,
+            blackboard: Option<&BehaviorBlackboard>,
+        ) -> Option<Self> {
+            Some(Ping {
+                channel: payload.channel.clone(),
+                value: payload.value.get(blackboard)?,
+            })
+        }
+
+        fn store(&self, filter: &PingFilter, blackboard: &mut BehaviorBlackboard) {
+            if !filter.store.is_empty() {
+                blackboard.set(filter.store.clone(), self.value);
+            }
+        }
+    }
+}
+
+use ping::Ping;
+
+#[behavior_builtins]
+#[derive(Debug, Serialize, Deserialize, TypeUuid, BehaviorSpawner)]
+#[uuid = "6f0b3c52-2a8e-4b7f-9a53-0d7c1b8e4f21"]
+enum EventBehavior {
+    WaitForPing(WaitForEvent<Ping>),
+    EmitPing(EmitEvent<Ping>),
+}
+
+/// Pings sent by gameplay, as frame and event
+#[derive(Default)]
+struct Sent(Vec<(usize, Ping)>);
+
+/// Pings seen by gameplay
+#[derive(Default)]
+struct Received(Vec<Ping>);
+
+fn send_pings(mut frame: Local<usize>, sent: Res<Sent>, mut pings: EventWriter<Ping>) {
+    for (at, ping) in sent.0.iter() {
+        if *at == *frame {
+            pings.send(ping.clone());
+        }
+    }
+    *frame += 1;
+}
+
+fn receive_pings(mut pings: EventReader<Ping>, mut received: ResMut<Received>) {
+    received.0.extend(pings.iter().cloned());
+}
+
+fn run_events(behavior: &str, sent: Vec<(usize, Ping)>) -> App {
+    run_events_with(behavior, sent, BehaviorExecution::PerFrame)
+}
+
+fn run_events_with(behavior: &str, sent: Vec<(usize, Ping)>, execution: BehaviorExecution) -> App {
+    let mut app = App::new();
+    test_app(&mut app);
+    app.insert_resource(execution)
+        .add_plugin(BehaviorEventPlugin::<Ping>::default())
+        .insert_resource(Sent(sent))
+        .init_resource::<Received>()
+        .add_system(send_pings)
+        .add_system(receive_pings);
+
+    spawn_test_tree_of::<EventBehavior>(&mut app, behavior);
+    update_frames(&mut app, MAX_ITERS);
+    app
+}
+
+fn ping(channel: &str, value: i64) -> Ping {
+    Ping {
+        channel: channel.to_string(),
+        value,
+    }
+}
+
+fn blackboard_int(app: &mut App, key: &str) -> Option<i64> {
+    let mut blackboards = app.world.query::<&BehaviorBlackboard>();
+    blackboards.single(&app.world).get::<i64>(key).copied()
+}
+
+#[test]
+fn wait_for_event_succeeds_on_matching_event() {
+    let behavior = r#"
+    (
+        root:(
+            "Wait for ping",
+            WaitForPing((filter: (channel: Value("alpha"), store: "value"))),
+        ),
+    )
+    "#;
+    let mut app = run_events(
+        behavior,
+        vec![(10, ping("beta", 1)), (20, ping("alpha", 2))],
+    );
+    let trace = app.world.resource::<BehaviorTrace>().clone();
+    assert_eq!(
+        trace,
+        BehaviorTrace::from_list(&["[0] STARTED Wait for ping", "[0] SUCCESS Wait for ping"])
+    );
+    assert_eq!(blackboard_int(&mut app, "value"), Some(2));
+}
+
+#[test]
+fn wait_for_event_filters_from_blackboard() {
+    let behavior = r#"
+    (
+        root:(
+            "Wait for ping",
+            WaitForPing((filter: (channel: Blackboard("channel")))),
+        ),
+        blackboard: {
+            "channel": String("beta"),
+        },
+    )
+    "#;
+    let mut app = run_events(behavior, vec![(10, ping("alpha", 1))]);
+    let trace = app.world.resource::<BehaviorTrace>().clone();
+    assert_eq!(
+        trace,
+        BehaviorTrace::from_list(&["[0] STARTED Wait for ping"])
+    );
+
+    let mut app = run_events(behavior, vec![(10, ping("beta", 1))]);
+    let trace = app.world.resource::<BehaviorTrace>().clone();
+    assert_eq!(
+        trace,
+        BehaviorTrace::from_list(&["[0] STARTED Wait for ping", "[0] SUCCESS Wait for ping"])
+    );
+}
+
+#[test]
+fn emit_event_sends_payload_from_blackboard() {
+    let behavior = r#"
+    (
+        root:(
+            "Emit pings",
+            Sequencer(()),
+            [
+                ("Emit", EmitPing((payload: (channel: "alpha", value: Blackboard("value"))))),
+                ("Emit missing", EmitPing((payload: (channel: "alpha", value: Blackboard("missing"))))),
+            ]
+        ),
+        blackboard: {
+            "value": Int(7),
+        },
+    )
+    "#;
+    let app = run_events(behavior, vec![]);
+    assert_eq!(app.world.resource::<Received>().0, vec![ping("alpha", 7)]);
+    let trace = app.world.resource::<BehaviorTrace>().clone();
+    assert_eq!(
+        trace,
+        BehaviorTrace::from_list(&[
+            "[0] STARTED Emit pings",
+            "[1] STARTED Emit",
+            "[1] SUCCESS Emit",
+            "[2] STARTED Emit missing",
+            "[2] FAILURE Emit missing",
+            "[0] FAILURE Emit pings",
+        ])
+    );
+}
+
+#[test]
+fn event_nodes_are_registered() {
+    let app = run_events(r#"(root:("Idle", Debug(())))"#, vec![]);
+    let registry = app.world.resource::<BehaviorRegistry>();
+    assert!(registry.contains("WaitForPing"));
+    assert!(registry.contains("EmitPing"));
+}
+
+#[test]
+fn wait_for_event_steps_miss_earlier_events() {
+    // The wait starts in an extra step of the first frame, after the first ping was sent
+    let behavior = r#"
+    (
+        root:(
+            "Wait in order",
+            Sequencer(()),
+            [
+                ("First", Debug(())),
+                ("Wait for ping", WaitForPing((filter: (channel: Value("alpha"), store: "value")))),
+            ]
+        ),
+    )
+    "#;
+    let mut app = run_events_with(
+        behavior,
+        vec![(0, ping("alpha", 1)), (5, ping("alpha", 2))],
+        BehaviorExecution::RunToQuiescence { budget: 100 },
+    );
+    let trace = app.world.resource::<BehaviorTrace>().clone();
+    assert_eq!(
+        trace,
+        BehaviorTrace::from_list(&[
+            "[0] STARTED Wait in order",
+            "[1] STARTED First",
+            "[1] SUCCESS First",
+            "[2] STARTED Wait for ping",
+            "[2] SUCCESS Wait for ping",
+            "[0] SUCCESS Wait in order",
+        ])
+    );
+    assert_eq!(blackboard_int(&mut app, "value"), Some(2));
+}
@@ -0,0 +1,49 @@
+use simula_behavior::{test::*, BehaviorTrace};
+
+#[test]
+fn force_failure_success() {
+    let behavior = r#"
+    (
+        root:(
+            "Fail anyway",
+            ForceFailure(()),
+            [
+                ("Do action", Debug((message:"Hello, from DebugMessage0!"))),
+            ]
+        )
+    )
+    "#;
+    let trace = trace_behavior(behavior);
+    println!("{:#?}", trace);
+    let expected_trace = BehaviorTrace::from_list(&[
+        "[0] STARTED Fail anyway",
+        "[1] STARTED Do action",
+        "[1] SUCCESS Do action",
+        "[0] FAILURE Fail anyway",
+    ]);
+    assert_eq!(&trace, &expected_trace);
+}
+
+#[test]
+fn force_failure_failure() {
+    let behavior = r#"
+    (
+        root:(
+            "Fail anyway",
+            ForceFailure(()),
+            [
+                ("Do action", Debug((message:"Hello, from DebugMessage0!", fail:true))),
+            ]
+        )
+    )
+    "#;
+    let trace = trace_behavior(behavior);
+    println!("{:#?}", trace);
+    let expected_trace = BehaviorTrace::from_list(&[
+        "[0] STARTED Fail anyway",
+        "[1] STARTED Do action",
+        "[1] FAILURE Do action",
+        "[0] FAILURE Fail anyway",
+    ]);
+    assert_eq!(&trace, &expected_trace);
+}
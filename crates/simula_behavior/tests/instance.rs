@@ -0,0 +1,238 @@
+use bevy::{ecs::system::CommandQueue, prelude::*};
+use simula_behavior::{prelude::*, test::*};
+use std::sync::Arc;
+
+const LIMITED: &str = r#"
+(
+    root:(
+        "Once per life",
+        Limit((max: 1)),
+        [
+            ("Hello", Debug(())),
+        ]
+    ),
+    blackboard: {
+        "ready": Bool(true),
+    },
+)
+"#;
+
+fn definition(behavior: &str) -> Arc<BehaviorDefinition<TestBehavior>> {
+    BehaviorDefinition::from_document(ron::from_str(behavior).unwrap())
+}
+
+/// Apply commands to the app world
+fn with_commands<R>(app: &mut App, f: impl FnOnce(&mut Commands) -> R) -> R {
+    let mut command_queue = CommandQueue::default();
+    let mut commands = Commands::new(&mut command_queue, &app.world);
+    let result = f(&mut commands);
+    command_queue.apply(&mut app.world);
+    result
+}
+
+fn instance(app: &App, tree: Entity) -> &BehaviorInstance<TestBehavior> {
+    app.world.get::<BehaviorInstance<TestBehavior>>(tree).unwrap()
+}
+
+#[test]
+fn definition_flattens_document() {
+    let definition = definition(
+        r#"
+        (
+            root:(
+                "Root",
+                Sequencer(()),
+                [
+                    ("A", Debug(())),
+                    ("B", Selector(()), [("C", Debug(()))]),
+                    ("D", Debug(())),
+                ]
+            ),
+        )
+        "#,
+    );
+    let names = definition
+        .nodes()
+        .iter()
+        .map(|node| node.name.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(names, vec!["Root", "A", "B", "C", "D"]);
+    assert_eq!(definition.nodes()[0].children, vec![1, 2, 4]);
+    assert_eq!(definition.nodes()[2].children, vec![3]);
+    assert_eq!(definition.nodes()[3].parent, Some(2));
+    assert_eq!(definition.nodes()[0].parent, None);
+    assert_eq!(definition.nodes()[0].end, 5);
+    assert_eq!(definition.nodes()[2].end, 4);
+}
+
+#[test]
+fn instance_runs_on_compact_state() {
+    let mut app = App::new();
+    test_app(&mut app);
+    let definition = definition(
+        r#"
+        (
+            root:(
+                "Root",
+                Sequencer(()),
+                [
+                    ("Wait", Delay((duration: 0.1))),
+                    ("Done", SetBlackboard((key: "done", value: Bool(true)))),
+                ]
+            ),
+        )
+        "#,
+    );
+    let entities = app.world.entities().len();
+    let tree = with_commands(&mut app, |commands| {
+        BehaviorInstance::spawn(commands, &definition)
+    });
+    // One entity for the whole tree
+    assert_eq!(app.world.entities().len(), entities + 1);
+
+    update_frames(&mut app, 2);
+    assert_eq!(
+        instance(&app, tree).states(),
+        &[
+            BehaviorNodeState::Running,
+            BehaviorNodeState::Running,
+            BehaviorNodeState::Idle,
+        ]
+    );
+
+    update_frames(&mut app, 20);
+    assert_eq!(
+        instance(&app, tree).states(),
+        &[BehaviorNodeState::Success; 3]
+    );
+    let blackboard = app.world.get::<BehaviorBlackboard>(tree).unwrap();
+    assert_eq!(blackboard.get::<bool>("done"), Some(&true));
+    // Nothing was traced, there are no node entities
+    assert!(app.world.resource::<BehaviorTrace>().is_empty());
+}
+
+#[test]
+fn instance_repeats_once_per_frame() {
+    let mut app = App::new();
+    test_app(&mut app);
+    let definition = definition(
+        r#"
+        (
+            root:(
+                "Forever",
+                Repeater((repeat: Forever)),
+                [
+                    ("Hello", Debug(())),
+                ]
+            ),
+        )
+        "#,
+    );
+    let tree = with_commands(&mut app, |commands| {
+        BehaviorInstance::spawn(commands, &definition)
+    });
+    update_frames(&mut app, 5);
+    let instance = instance(&app, tree);
+    assert_eq!(instance.state(), BehaviorNodeState::Running);
+    assert_eq!(instance.locals()[0].count, 5);
+}
+
+#[test]
+fn instance_keeps_limit_count_across_runs() {
+    let mut app = App::new();
+    test_app(&mut app);
+    let definition = definition(
+        r#"
+        (
+            root:(
+                "Three times",
+                Repeater((repeat: Times(3))),
+                [
+                    ("Once", Limit((max: 1)), [("Hello", Debug(()))]),
+                ]
+            ),
+        )
+        "#,
+    );
+    let tree = with_commands(&mut app, |commands| {
+        BehaviorInstance::spawn(commands, &definition)
+    });
+    update_frames(&mut app, 10);
+    let instance = instance(&app, tree);
+    assert_eq!(instance.state(), BehaviorNodeState::Success);
+    // The limit ran its child once, and refused the other runs
+    assert_eq!(instance.locals()[1].count, 1);
+    assert_eq!(instance.states()[1], BehaviorNodeState::Failure);
+}
+
+#[test]
+fn pool_reuses_released_trees() {
+    let mut app = App::new();
+    test_app(&mut app);
+    let definition = definition(LIMITED);
+    let mut pool = BehaviorTreePool::default();
+    let agent = app.world.spawn().id();
+    let tree = with_commands(&mut app, |commands| {
+        let tree = pool.spawn(commands, &definition);
+        commands.entity(agent).push_children(&[tree]);
+        tree
+    });
+    update_frames(&mut app, 10);
+    assert_eq!(
+        instance(&app, tree).states(),
+        &[BehaviorNodeState::Success; 2]
+    );
+    assert_eq!(instance(&app, tree).locals()[0].count, 1);
+    app.world
+        .get_mut::<BehaviorBlackboard>(tree)
+        .unwrap()
+        .set("ready", false);
+
+    // The agent goes away, its tree stays in the pool
+    with_commands(&mut app, |commands| {
+        pool.release(commands, tree, definition.id(), Some(agent));
+        commands.entity(agent).despawn_recursive();
+    });
+    assert!(app.world.get_entity(tree).is_some());
+    assert_eq!(pool.available(definition.id()), 1);
+
+    // Reused as new, with the limit count and blackboard reset
+    let reused = with_commands(&mut app, |commands| pool.spawn(commands, &definition));
+    assert_eq!(reused, tree);
+    assert!(pool.is_empty());
+    assert_eq!(
+        instance(&app, tree).states(),
+        &[BehaviorNodeState::Idle; 2]
+    );
+    let blackboard = app.world.get::<BehaviorBlackboard>(tree).unwrap();
+    assert_eq!(blackboard.get::<bool>("ready"), Some(&true));
+    update_frames(&mut app, 10);
+    assert_eq!(
+        instance(&app, tree).states(),
+        &[BehaviorNodeState::Success; 2]
+    );
+    assert_eq!(instance(&app, tree).locals()[0].count, 1);
+}
+
+#[test]
+fn pool_despawns_over_capacity() {
+    let mut app = App::new();
+    test_app(&mut app);
+    let definition = definition(LIMITED);
+    let mut pool = BehaviorTreePool::new(1);
+    let trees = with_commands(&mut app, |commands| {
+        [
+            pool.spawn(commands, &definition),
+            pool.spawn(commands, &definition),
+        ]
+    });
+    update_frames(&mut app, 10);
+    for tree in trees {
+        with_commands(&mut app, |commands| {
+            pool.release(commands, tree, definition.id(), None)
+        });
+    }
+    assert_eq!(pool.len(), 1);
+    assert!(app.world.get_entity(trees[0]).is_some());
+    assert!(app.world.get_entity(trees[1]).is_none());
+}
@@ -0,0 +1,41 @@
+use simula_behavior::{test::*, BehaviorTrace};
+
+#[test]
+fn limit_runs() {
+    let behavior = r#"
+    (
+        root:(
+            "Do a few times",
+            Repeater((repeat:Times(3))),
+            [
+                (
+                    "At most twice",
+                    Limit((max: 2)),
+                    [
+                        ("Do action", Debug((message:"Hello, from DebugMessage0!"))),
+                    ]
+                )
+            ]
+        )
+    )
+    "#;
+    let trace = trace_behavior(behavior);
+    println!("{:#?}", trace);
+    let expected_trace = BehaviorTrace::from_list(&[
+        "[0] STARTED Do a few times",
+        "[1] STARTED At most twice",
+        "[2] STARTED Do action",
+        "[2] SUCCESS Do action",
+        "[1] SUCCESS At most twice",
+        "[0] STARTED Do a few times",
+        "[1] STARTED At most twice",
+        "[2] STARTED Do action",
+        "[2] SUCCESS Do action",
+        "[1] SUCCESS At most twice",
+        "[0] STARTED Do a few times",
+        "[1] STARTED At most twice",
+        "[1] FAILURE At most twice",
+        "[0] SUCCESS Do a few times",
+    ]);
+    assert_eq!(&trace, &expected_trace);
+}
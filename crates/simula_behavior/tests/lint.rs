@@ -0,0 +1,224 @@
+use simula_behavior::{lint::*, prelude::*, test::*};
+
+fn lint(document: &str) -> BehaviorLintReport {
+    BehaviorLinter::<TestBehavior>::new().lint_str("test.bht.ron", document)
+}
+
+fn rules(report: &BehaviorLintReport) -> Vec<&str> {
+    report.issues.iter().map(|issue| issue.rule).collect()
+}
+
+#[test]
+fn lint_accepts_valid_document() {
+    let report = lint(
+        r#"
+        (root:(
+            "Do a few times",
+            Repeater((repeat:Times(2))),
+            [(
+                "Do in order",
+                Sequencer(()),[
+                ("Say hi", Debug((message:"Hello"))),
+                ("Say hi again", Debug((message:"Hello again")))
+            ])]
+        ))
+        "#,
+    );
+    assert!(report.issues.is_empty(), "{:?}", report.issues);
+}
+
+#[test]
+fn lint_reports_decorator_children() {
+    let report = lint(
+        r#"
+        (root:("Sequence", Sequencer(()), [
+            ("No child", Inverter(())),
+            ("Two children", Succeeder(()), [
+                ("First", Debug(())),
+                ("Second", Debug(())),
+            ]),
+            ("Linked", Subtree((path:"behaviors/debug_sequence.bht.ron"))),
+        ]))
+        "#,
+    );
+    assert_eq!(
+        rules(&report),
+        vec!["decorator-children", "decorator-children"]
+    );
+    assert_eq!(report.issues[0].node, "Sequence/No child");
+    assert_eq!(report.issues[1].node, "Sequence/Two children");
+    assert_eq!(report.errors(), 2);
+}
+
+#[test]
+fn lint_reports_action_children() {
+    let report = lint(
+        r#"
+        (root:("Say hi", Debug(()), [("Child", Debug(()))]))
+        "#,
+    );
+    assert_eq!(rules(&report), vec!["action-children"]);
+    assert_eq!(report.issues[0].severity, BehaviorLintSeverity::Error);
+}
+
+#[test]
+fn lint_reports_unreachable_after_succeeder() {
+    let report = lint(
+        r#"
+        (root:("Choose", Selector(()), [
+            ("Try", Debug((fail:true))),
+            ("Always", Succeeder(()), [("Maybe", Debug((fail:true)))]),
+            ("Never", Debug(())),
+        ]))
+        "#,
+    );
+    assert_eq!(rules(&report), vec!["unreachable"]);
+    assert_eq!(report.issues[0].node, "Choose");
+    assert!(report.issues[0].message.contains("`Never`"));
+    assert_eq!(report.warnings(), 1);
+}
+
+#[test]
+fn lint_reports_repeat_forever_in_sequence() {
+    let report = lint(
+        r#"
+        (root:("Sequence", Sequencer(()), [
+            ("Loop", Repeater((repeat:Forever)), [("Tick", Debug(()))]),
+            ("After", Debug(())),
+        ]))
+        "#,
+    );
+    assert_eq!(rules(&report), vec!["repeat-forever"]);
+
+    // Found below decorators
+    let report = lint(
+        r#"
+        (root:("Sequence", Sequencer(()), [
+            ("Flip", Inverter(()), [
+                ("Again", Retry((attempts:3)), [
+                    ("Loop", Repeater((repeat:Forever)), [("Tick", Debug(()))]),
+                ]),
+            ]),
+            ("After", Debug(())),
+        ]))
+        "#,
+    );
+    assert_eq!(rules(&report), vec!["repeat-forever"]);
+    assert!(report.issues[0]
+        .message
+        .starts_with("`Loop` repeats forever"));
+
+    // A timeout between stops the loop
+    let report = lint(
+        r#"
+        (root:("Sequence", Sequencer(()), [
+            ("Bounded", Timeout((duration:1.0)), [
+                ("Loop", Repeater((repeat:Forever)), [("Tick", Debug(()))]),
+            ]),
+        ]))
+        "#,
+    );
+    assert!(report.issues.is_empty(), "{:?}", report.issues);
+
+    // A timeout above stops the loop
+    let report = lint(
+        r#"
+        (root:("Bounded", Timeout((duration:1.0)), [
+            ("Sequence", Sequencer(()), [
+                ("Loop", Repeater((repeat:Forever)), [("Tick", Debug(()))]),
+            ]),
+        ]))
+        "#,
+    );
+    assert!(report.issues.is_empty(), "{:?}", report.issues);
+}
+
+#[test]
+fn lint_reports_reactive_non_conditions() {
+    let report = lint(
+        r#"
+        (root:("Guarded", ReactiveSequence(()), [
+            ("Is ready", Condition((key:"ready", test:IsTrue))),
+            ("Get ready", Debug(())),
+            ("Work", Debug(())),
+        ]))
+        "#,
+    );
+    assert_eq!(rules(&report), vec!["reactive-condition"]);
+    assert_eq!(report.issues[0].node, "Guarded");
+    assert!(report.issues[0].message.contains("`Get ready`"));
+    assert_eq!(report.warnings(), 1);
+}
+
+#[test]
+fn lint_reports_unknown_nodes_and_allows_custom_ones() {
+    let document = r#"
+        (root:("Sequence", Sequencer(()), [
+            ("Rest", AgentRest(())),
+            ("Work", AgentWork((hours:8)), [("Child", Debug(()))]),
+        ]))
+        "#;
+    let report = lint(document);
+    assert_eq!(rules(&report), vec!["unknown-node", "unknown-node"]);
+
+    let mut linter = BehaviorLinter::<TestBehavior>::new();
+    linter
+        .allow("AgentRest", None)
+        .allow("AgentWork", Some(BehaviorType::Action));
+    let report = linter.lint_str("test.bht.ron", document);
+    assert_eq!(rules(&report), vec!["action-children"]);
+}
+
+#[test]
+fn lint_reads_nodes_from_registry() {
+    let document = r#"
+        (root:("Sequence", Sequencer(()), [
+            ("Forever", Repeater((repeat:Forever)), [("Say hi", Debug(()))]),
+        ]))
+        "#;
+    assert_eq!(rules(&lint(document)), vec!["repeat-forever"]);
+
+    // Nodes missing from the registry are unknown, and a sequence without its flow
+    // is not checked
+    let mut registry = BehaviorRegistry::default();
+    registry.register::<Repeater>();
+    registry.register::<Debug>();
+    let linter = BehaviorLinter::<TestBehavior>::with_registry(&registry);
+    assert!(!linter.is_known("Sequencer"));
+    let report = linter.lint_str("test.bht.ron", document);
+    assert_eq!(rules(&report), vec!["unknown-node"]);
+}
+
+#[test]
+fn lint_reports_duplicate_names() {
+    let report = lint(
+        r#"
+        (root:("Sequence", Sequencer(()), [
+            ("Say hi", Debug(())),
+            ("Say hi", Debug(())),
+        ]))
+        "#,
+    );
+    assert_eq!(rules(&report), vec!["duplicate-name"]);
+    assert!(report.issues[0].message.contains("`Say hi`"));
+}
+
+#[test]
+fn lint_reports_parse_errors() {
+    let report = lint("(root:(\"Broken\", Sequencer(())");
+    assert_eq!(rules(&report), vec!["parse"]);
+}
+
+#[test]
+fn lint_report_formats() {
+    let report = lint(r#"(root:("Say hi", Debug(()), [("Child", Debug(()))]))"#);
+    assert_eq!(
+        report.to_text(),
+        "test.bht.ron: error[action-children]: Say hi: Action `Debug` has 1 children, actions have none\n"
+    );
+    let json = serde_json::to_value(&report).unwrap();
+    assert_eq!(json["file"], "test.bht.ron");
+    assert_eq!(json["issues"][0]["severity"], "error");
+    assert_eq!(json["issues"][0]["rule"], "action-children");
+    assert_eq!(json["issues"][0]["node"], "Say hi");
+}
@@ -0,0 +1,133 @@
+use bevy::prelude::*;
+use simula_behavior::{
+    net::{setup_replication, snapshot_trees, update_proxy_trees, BehaviorNodeState},
+    prelude::*,
+    test::*,
+};
+use simula_net::Proxy;
+
+const BEHAVIOR: &str = r#"
+(
+    root:(
+        "In order",
+        Sequencer(()),
+        [
+            ("First", Debug(())),
+            ("Wait", Delay((duration: 10.0))),
+        ]
+    ),
+)
+"#;
+
+fn net_app() -> App {
+    let mut app = App::new();
+    test_app(&mut app);
+    app.add_system(setup_replication)
+        .add_system_to_stage(CoreStage::PostUpdate, update_proxy_trees)
+        .add_system_to_stage(CoreStage::Last, snapshot_trees);
+    app
+}
+
+fn spawn_tree(app: &mut App) -> Entity {
+    let tree = spawn_test_tree(app, BEHAVIOR);
+    app.world
+        .entity_mut(tree)
+        .insert(ReplicateBehaviorTree::default());
+    tree
+}
+
+/// Stand in for the network, copy the snapshot of a tree to its proxy
+fn sync(app: &mut App, tree: Entity, proxy: Entity) {
+    let snapshot = app.world.get::<BehaviorTreeSnapshot>(tree).unwrap().clone();
+    app.world.entity_mut(proxy).insert(snapshot);
+}
+
+#[test]
+fn snapshot_mirrors_tree() {
+    let mut app = net_app();
+    let tree = spawn_tree(&mut app);
+    update_frames(&mut app, 10);
+
+    let snapshot = app.world.get::<BehaviorTreeSnapshot>(tree).unwrap();
+    let names = snapshot
+        .nodes
+        .iter()
+        .map(|node| node.name.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(names, vec!["In order", "First", "Wait"]);
+    assert_eq!(snapshot.nodes[0].behavior, "Sequencer");
+    assert_eq!(snapshot.nodes[0].children, vec![1, 2]);
+    assert_eq!(snapshot.nodes[0].state, BehaviorNodeState::Running);
+    assert!(!snapshot.nodes[0].cursor);
+    assert_eq!(snapshot.nodes[1].state, BehaviorNodeState::Success);
+    assert_eq!(snapshot.nodes[2].state, BehaviorNodeState::Running);
+    assert!(snapshot.nodes[2].cursor);
+    assert!(!snapshot.paused);
+}
+
+#[test]
+fn proxy_tree_follows_snapshots() {
+    let mut app = net_app();
+    let tree = spawn_tree(&mut app);
+    let proxy = app.world.spawn().insert(Proxy::default()).id();
+    app.update();
+    sync(&mut app, tree, proxy);
+    app.update();
+
+    // Proxy nodes mirror the tree, without running
+    let root = app.world.get::<BehaviorTree>(proxy).unwrap().root.unwrap();
+    assert_eq!(app.world.get::<Name>(root).unwrap().as_str(), "In order");
+    assert!(app.world.get::<BehaviorPaused>(root).is_some());
+    let nodes = app.world.get::<BehaviorProxy>(proxy).unwrap().nodes.clone();
+    assert_eq!(nodes.len(), 3);
+    assert_eq!(
+        app.world.get::<BehaviorNode>(nodes[2]).unwrap().name,
+        "Delay"
+    );
+    assert_eq!(
+        app.world.get::<BehaviorNode>(nodes[2]).unwrap().tree,
+        Some(proxy)
+    );
+
+    for _ in 0..10 {
+        sync(&mut app, tree, proxy);
+        app.update();
+    }
+    assert!(app.world.get::<BehaviorSuccess>(nodes[1]).is_some());
+    assert!(app.world.get::<BehaviorRunning>(nodes[2]).is_some());
+    assert!(app.world.get::<BehaviorCursor>(nodes[2]).is_some());
+    assert!(app.world.get::<BehaviorCursor>(nodes[0]).is_none());
+    // Only the authoritative tree started its nodes
+    let trace = app.world.resource::<BehaviorTrace>();
+    let started = trace.iter().filter(|line| line.contains("STARTED")).count();
+    assert_eq!(started, 3);
+    let stats = app.world.resource::<BehaviorStats>();
+    assert_eq!(stats.node("Delay").unwrap().activations, 1);
+}
+
+#[test]
+fn proxy_tree_respawns_on_new_structure() {
+    let mut app = net_app();
+    let tree = spawn_tree(&mut app);
+    let proxy = app.world.spawn().insert(Proxy::default()).id();
+    app.update();
+    sync(&mut app, tree, proxy);
+    app.update();
+    let nodes = app.world.get::<BehaviorProxy>(proxy).unwrap().nodes.clone();
+
+    let mut snapshot = app.world.get::<BehaviorTreeSnapshot>(tree).unwrap().clone();
+    snapshot.nodes[0].children = vec![1];
+    snapshot.nodes.truncate(2);
+    app.world.entity_mut(proxy).insert(snapshot);
+    app.update();
+
+    let respawned = app.world.get::<BehaviorProxy>(proxy).unwrap().nodes.clone();
+    assert_eq!(respawned.len(), 2);
+    assert!(nodes
+        .iter()
+        .all(|node| app.world.get_entity(*node).is_none()));
+    assert_eq!(
+        app.world.get::<BehaviorTree>(proxy).unwrap().root,
+        Some(respawned[0])
+    );
+}
@@ -0,0 +1,150 @@
+use simula_behavior::{test::*, BehaviorTrace};
+
+#[test]
+fn condition_selects_fallback() {
+    let behavior = r#"
+    (
+        root:(
+            "Check",
+            Selector(()),
+            [
+                ("Is ready", Condition((key: "ready", test: IsTrue))),
+                ("Fallback", Debug((message:"Not ready yet"))),
+            ]
+        ),
+        blackboard: {
+            "ready": Bool(false),
+        },
+    )
+    "#;
+    let trace = trace_behavior(behavior);
+    println!("{:#?}", trace);
+    let expected_trace = BehaviorTrace::from_list(&[
+        "[0] STARTED Check",
+        "[1] STARTED Is ready",
+        "[1] FAILURE Is ready",
+        "[2] STARTED Fallback",
+        "[2] SUCCESS Fallback",
+        "[0] SUCCESS Check",
+    ]);
+    assert_eq!(&trace, &expected_trace);
+}
+
+#[test]
+fn observer_gate_fails() {
+    let behavior = r#"
+    (
+        root:(
+            "Only when ready",
+            Observer((key: "ready", test: IsTrue)),
+            [
+                ("Do an action", Debug((message:"Hello, from DebugMessage0!"))),
+            ]
+        ),
+        blackboard: {
+            "ready": Bool(false),
+        },
+    )
+    "#;
+    let trace = trace_behavior(behavior);
+    println!("{:#?}", trace);
+    let expected_trace =
+        BehaviorTrace::from_list(&["[0] STARTED Only when ready", "[0] FAILURE Only when ready"]);
+    assert_eq!(&trace, &expected_trace);
+}
+
+#[test]
+fn observer_abort_self() {
+    let behavior = r#"
+    (
+        root:(
+            "Only while ready",
+            Observer((key: "ready", test: IsTrue, abort: SelfOnly)),
+            [
+                (
+                    "Do a few things",
+                    Sequencer(()),
+                    [
+                        ("Say hello", Debug((message:"Hello!"))),
+                        ("Not ready anymore", SetBlackboard((key: "ready", value: Bool(false)))),
+                        ("Say goodbye", Debug((message:"Goodbye!"))),
+                    ]
+                ),
+            ]
+        ),
+        blackboard: {
+            "ready": Bool(true),
+        },
+    )
+    "#;
+    let trace = trace_behavior(behavior);
+    println!("{:#?}", trace);
+    let stopped = position(&trace, "[1] STOPPED Do a few things");
+    let failed = position(&trace, "[0] FAILURE Only while ready");
+    assert!(position(&trace, "[2] SUCCESS Say hello") < stopped);
+    assert!(stopped < failed);
+    assert_eq!(failed, trace.len() - 1);
+    assert!(!trace.iter().any(|traced| traced.contains("Say goodbye")));
+}
+
+#[test]
+fn observer_abort_lower_priority() {
+    let behavior = r#"
+    (
+        root:(
+            "React",
+            Selector(()),
+            [
+                (
+                    "On alarm",
+                    Observer((key: "alarm", test: IsTrue, abort: LowerPriority)),
+                    [
+                        ("Flee", Debug((message:"Run!"))),
+                    ]
+                ),
+                (
+                    "Patrol",
+                    Sequencer(()),
+                    [
+                        ("Walk", Debug((message:"Walking"))),
+                        ("Raise alarm", SetBlackboard((key: "alarm", value: Bool(true)))),
+                        (
+                            "Keep walking",
+                            Repeater((repeat: Forever)),
+                            [
+                                ("Walk more", Debug((message:"Walking"))),
+                            ]
+                        ),
+                    ]
+                ),
+            ]
+        ),
+        blackboard: {
+            "alarm": Bool(false),
+        },
+    )
+    "#;
+    let trace = trace_behavior(behavior);
+    println!("{:#?}", trace);
+    let expected_start = BehaviorTrace::from_list(&[
+        "[0] STARTED React",
+        "[1] STARTED On alarm",
+        "[1] FAILURE On alarm",
+        "[3] STARTED Patrol",
+        "[4] STARTED Walk",
+        "[4] SUCCESS Walk",
+        "[5] STARTED Raise alarm",
+        "[5] SUCCESS Raise alarm",
+    ]);
+    assert_eq!(&trace[..expected_start.len()], &expected_start[..]);
+    let stopped = position(&trace, "[3] STOPPED Patrol");
+    let fled = position(&trace, "[2] SUCCESS Flee");
+    assert!(stopped < fled);
+    assert_eq!(
+        &trace[trace.len() - 2..],
+        &[
+            "[1] SUCCESS On alarm".to_string(),
+            "[0] SUCCESS React".to_string()
+        ]
+    );
+}
@@ -0,0 +1,120 @@
+use simula_behavior::{test::*, BehaviorTrace};
+
+#[test]
+fn parallel_success_threshold() {
+    let behavior = r#"
+    (
+        root:
+        (
+            "Until three workers finish",
+            Parallel((success_threshold: Count(3))),
+            [
+                ("Worker 1", Debug(())),
+                ("Worker 2", Debug(())),
+                ("Worker 3", Debug(())),
+                ("Worker 4", Debug((duration: 1000.0))),
+                ("Worker 5", Debug((duration: 1000.0))),
+            ]
+        )
+    )
+    "#;
+    let trace = trace_behavior(behavior);
+    println!("{:#?}", trace);
+    let expected_trace = BehaviorTrace::from_list(&[
+        "[0] STARTED Until three workers finish",
+        "[1] STARTED Worker 1",
+        "[2] STARTED Worker 2",
+        "[3] STARTED Worker 3",
+        "[4] STARTED Worker 4",
+        "[5] STARTED Worker 5",
+        "[1] SUCCESS Worker 1",
+        "[2] SUCCESS Worker 2",
+        "[3] SUCCESS Worker 3",
+        "[4] STOPPED Worker 4",
+        "[5] STOPPED Worker 5",
+        "[0] SUCCESS Until three workers finish",
+    ]);
+    assert_eq!(&trace, &expected_trace);
+}
+
+#[test]
+fn parallel_require_one() {
+    let behavior = r#"
+    (
+        root:
+        (
+            "Until one finishes",
+            Parallel((success_threshold: RequireOne)),
+            [
+                ("Take long", Debug((duration: 1000.0))),
+                ("Take short", Debug(())),
+            ]
+        )
+    )
+    "#;
+    let trace = trace_behavior(behavior);
+    println!("{:#?}", trace);
+    let expected_trace = BehaviorTrace::from_list(&[
+        "[0] STARTED Until one finishes",
+        "[1] STARTED Take long",
+        "[2] STARTED Take short",
+        "[2] SUCCESS Take short",
+        "[1] STOPPED Take long",
+        "[0] SUCCESS Until one finishes",
+    ]);
+    assert_eq!(&trace, &expected_trace);
+}
+
+#[test]
+fn parallel_failure_threshold() {
+    let behavior = r#"
+    (
+        root:
+        (
+            "Until two fail",
+            Parallel((failure_threshold: Count(2))),
+            [
+                ("Fail first", Debug((fail: true))),
+                ("Succeed", Debug(())),
+                ("Fail second", Debug((fail: true))),
+                ("Take long", Debug((duration: 1000.0))),
+            ]
+        )
+    )
+    "#;
+    let trace = trace_behavior(behavior);
+    println!("{:#?}", trace);
+    let stopped = position(&trace, "[4] STOPPED Take long");
+    let failed = position(&trace, "[0] FAILURE Until two fail");
+    assert!(position(&trace, "[1] FAILURE Fail first") < stopped);
+    assert!(position(&trace, "[3] FAILURE Fail second") < stopped);
+    assert!(position(&trace, "[2] SUCCESS Succeed") < stopped);
+    assert!(stopped < failed);
+    assert_eq!(failed, trace.len() - 1);
+}
+
+#[test]
+fn parallel_success_unreachable() {
+    let behavior = r#"
+    (
+        root:
+        (
+            "Until three succeed",
+            Parallel((success_threshold: Count(3), failure_threshold: RequireAll)),
+            [
+                ("Fail first", Debug((fail: true))),
+                ("Fail second", Debug((fail: true))),
+                ("Take long", Debug((duration: 1000.0))),
+                ("Take longer", Debug((duration: 2000.0))),
+            ]
+        )
+    )
+    "#;
+    let trace = trace_behavior(behavior);
+    println!("{:#?}", trace);
+    // Only two children left, three can't succeed anymore
+    let failed = position(&trace, "[0] FAILURE Until three succeed");
+    assert!(position(&trace, "[3] STOPPED Take long") < failed);
+    assert!(position(&trace, "[4] STOPPED Take longer") < failed);
+    assert_eq!(failed, trace.len() - 1);
+}
@@ -0,0 +1,81 @@
+use simula_behavior::{test::*, BehaviorTrace};
+
+#[test]
+fn reactive_selector_fails() {
+    let behavior = r#"
+    (
+        root:(
+            "React",
+            ReactiveSelector(()),
+            [
+                ("Alarm raised", Condition((key: "alarm", test: IsTrue))),
+                ("Try to patrol", Debug((message:"Can't walk", fail: true))),
+            ]
+        ),
+        blackboard: {
+            "alarm": Bool(false),
+        },
+    )
+    "#;
+    let trace = trace_behavior(behavior);
+    println!("{:#?}", trace);
+    let expected_trace = BehaviorTrace::from_list(&[
+        "[0] STARTED React",
+        "[1] STARTED Alarm raised",
+        "[1] FAILURE Alarm raised",
+        "[2] STARTED Try to patrol",
+        "[2] FAILURE Try to patrol",
+        "[0] FAILURE React",
+    ]);
+    assert_eq!(&trace, &expected_trace);
+}
+
+#[test]
+fn reactive_selector_stops_running_child() {
+    let behavior = r#"
+    (
+        root:(
+            "React",
+            ReactiveSelector(()),
+            [
+                ("Alarm raised", Condition((key: "alarm", test: IsTrue))),
+                (
+                    "Patrol",
+                    Sequencer(()),
+                    [
+                        ("Walk", Debug((message:"Walking"))),
+                        ("Raise alarm", SetBlackboard((key: "alarm", value: Bool(true)))),
+                        (
+                            "Keep walking",
+                            Repeater((repeat: Forever)),
+                            [
+                                ("Walk more", Debug((message:"Walking"))),
+                            ]
+                        ),
+                    ]
+                ),
+            ]
+        ),
+        blackboard: {
+            "alarm": Bool(false),
+        },
+    )
+    "#;
+    let trace = trace_behavior(behavior);
+    println!("{:#?}", trace);
+    let expected_start = BehaviorTrace::from_list(&[
+        "[0] STARTED React",
+        "[1] STARTED Alarm raised",
+        "[1] FAILURE Alarm raised",
+        "[2] STARTED Patrol",
+        "[3] STARTED Walk",
+        "[3] SUCCESS Walk",
+        "[4] STARTED Raise alarm",
+        "[4] SUCCESS Raise alarm",
+    ]);
+    assert_eq!(&trace[..expected_start.len()], &expected_start[..]);
+    let stopped = position(&trace, "[2] STOPPED Patrol");
+    let succeeded = position(&trace, "[0] SUCCESS React");
+    assert!(stopped < succeeded);
+    assert_eq!(succeeded, trace.len() - 1);
+}
@@ -0,0 +1,93 @@
+use simula_behavior::{test::*, BehaviorTrace};
+
+#[test]
+fn reactive_sequence_succeeds() {
+    let behavior = r#"
+    (
+        root:(
+            "Guarded",
+            ReactiveSequence(()),
+            [
+                ("Is ready", Condition((key: "ready", test: IsTrue))),
+                ("Do an action", Debug((message:"Hello!"))),
+            ]
+        ),
+        blackboard: {
+            "ready": Bool(true),
+        },
+    )
+    "#;
+    let trace = trace_behavior(behavior);
+    println!("{:#?}", trace);
+    let expected_trace = BehaviorTrace::from_list(&[
+        "[0] STARTED Guarded",
+        "[1] STARTED Is ready",
+        "[1] SUCCESS Is ready",
+        "[2] STARTED Do an action",
+        "[2] SUCCESS Do an action",
+        "[0] SUCCESS Guarded",
+    ]);
+    assert_eq!(&trace, &expected_trace);
+}
+
+const GUARDED: &str = r#"
+(
+    root:(
+        "Guarded",
+        COMPOSITE(()),
+        [
+            ("Is ready", Condition((key: "ready", test: IsTrue))),
+            (
+                "Do a few things",
+                Sequencer(()),
+                [
+                    ("Say hello", Debug((message:"Hello!"))),
+                    ("Not ready anymore", SetBlackboard((key: "ready", value: Bool(false)))),
+                    (
+                        "Keep going",
+                        Repeater((repeat: Forever)),
+                        [
+                            ("Walk more", Debug((message:"Walking"))),
+                        ]
+                    ),
+                ]
+            ),
+        ]
+    ),
+    blackboard: {
+        "ready": Bool(true),
+    },
+)
+"#;
+
+#[test]
+fn reactive_sequence_stops_running_child() {
+    let trace = trace_behavior(&GUARDED.replace("COMPOSITE", "ReactiveSequence"));
+    println!("{:#?}", trace);
+    let expected_start = BehaviorTrace::from_list(&[
+        "[0] STARTED Guarded",
+        "[1] STARTED Is ready",
+        "[1] SUCCESS Is ready",
+        "[2] STARTED Do a few things",
+        "[3] STARTED Say hello",
+        "[3] SUCCESS Say hello",
+        "[4] STARTED Not ready anymore",
+        "[4] SUCCESS Not ready anymore",
+    ]);
+    assert_eq!(&trace[..expected_start.len()], &expected_start[..]);
+    let stopped = position(&trace, "[2] STOPPED Do a few things");
+    let failed = position(&trace, "[0] FAILURE Guarded");
+    assert!(stopped < failed);
+    assert_eq!(failed, trace.len() - 1);
+}
+
+#[test]
+fn sequencer_keeps_running_child() {
+    // Without reactivity, the condition is not checked again
+    let trace = trace_behavior(&GUARDED.replace("COMPOSITE", "Sequencer"));
+    assert!(!trace.iter().any(|traced| traced.contains("STOPPED")));
+    assert!(!trace
+        .iter()
+        .any(|traced| traced.contains("Guarded") && traced.contains("FAILURE")));
+    assert!(trace.iter().any(|traced| traced.contains("Walk more")));
+}
@@ -0,0 +1,162 @@
+use bevy::prelude::*;
+use simula_behavior::{
+    inspector::timeline::{timeline_rows, TimelineInterval},
+    prelude::*,
+    test::*,
+};
+
+const BEHAVIOR: &str = r#"
+(
+    root:(
+        "Sequencer of actions",
+        Sequencer(()),
+        [
+            ("Do action", Debug((message:"Hello, from DebugMessage0!"))),
+            ("Count", SetBlackboard((key: "count", value: Int(5)))),
+            ("Take long", Debug((duration: 1000.0))),
+        ]
+    ),
+    blackboard: {
+        "count": Int(0),
+    },
+)
+"#;
+
+fn event(node: u32, name: &str, transition: BehaviorTransition, time: f64) -> BehaviorEvent {
+    BehaviorEvent {
+        tree: None,
+        node: Entity::from_raw(node),
+        typ: BehaviorType::Action,
+        behavior: "Debug".to_string(),
+        name: name.to_string(),
+        transition,
+        frame: 0,
+        time,
+        blackboard: None,
+    }
+}
+
+#[test]
+fn recorder_events() {
+    let recorder = record_behavior(BEHAVIOR, false);
+    let transitions = recorder
+        .iter()
+        .map(|event| (event.name.as_str(), event.transition))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        transitions,
+        vec![
+            ("Sequencer of actions", BehaviorTransition::Started),
+            ("Do action", BehaviorTransition::Started),
+            ("Do action", BehaviorTransition::Success),
+            ("Count", BehaviorTransition::Started),
+            ("Count", BehaviorTransition::Success),
+            ("Take long", BehaviorTransition::Started),
+        ]
+    );
+    let last = recorder.iter().last().unwrap();
+    assert_eq!(last.typ, BehaviorType::Action);
+    assert_eq!(last.behavior, "Debug");
+    assert!(last.tree.is_some());
+    assert!(last.blackboard.is_none());
+    // Events are in order of frames and time
+    let events = recorder.iter().collect::<Vec<_>>();
+    assert!(events
+        .windows(2)
+        .all(|pair| pair[0].frame <= pair[1].frame && pair[0].time <= pair[1].time));
+}
+
+#[test]
+fn recorder_blackboard_snapshot() {
+    let recorder = record_behavior(BEHAVIOR, true);
+    let count = |name: &str, transition: BehaviorTransition| {
+        recorder
+            .iter()
+            .find(|event| event.name == name && event.transition == transition)
+            .and_then(|event| event.blackboard.as_ref())
+            .and_then(|blackboard| blackboard.get("count").cloned())
+    };
+    assert_eq!(
+        count("Count", BehaviorTransition::Started),
+        Some(BlackboardValue::Int(0))
+    );
+    assert_eq!(
+        count("Count", BehaviorTransition::Success),
+        Some(BlackboardValue::Int(5))
+    );
+}
+
+#[test]
+fn recorder_disabled_by_default() {
+    let mut app = App::new();
+    test_app(&mut app);
+    spawn_test_tree(&mut app, BEHAVIOR);
+    let trace = update_frames(&mut app, 10);
+    assert!(!trace.is_empty());
+    assert!(app.world.resource::<BehaviorRecorder>().is_empty());
+}
+
+#[test]
+fn recorder_ring_buffer() {
+    let mut recorder = BehaviorRecorder::new(3);
+    for node in 0..5 {
+        recorder.push(event(node, "Do action", BehaviorTransition::Started, 0.0));
+    }
+    assert_eq!(recorder.len(), 3);
+    let nodes = recorder
+        .iter()
+        .map(|event| event.node.id())
+        .collect::<Vec<_>>();
+    assert_eq!(nodes, vec![2, 3, 4]);
+}
+
+#[test]
+fn recorder_json_lines() {
+    let recorder = record_behavior(BEHAVIOR, false);
+    let lines = recorder.to_json_lines();
+    assert_eq!(lines.lines().count(), recorder.len());
+    let first: serde_json::Value = serde_json::from_str(lines.lines().next().unwrap()).unwrap();
+    assert_eq!(first["name"], "Sequencer of actions");
+    assert_eq!(first["transition"], "Started");
+    assert_eq!(first["typ"], "Composite");
+    assert_eq!(first["behavior"], "Sequencer");
+    assert!(first.get("blackboard").is_none());
+}
+
+#[test]
+fn recorder_timeline() {
+    let events = vec![
+        event(0, "Sequencer", BehaviorTransition::Started, 0.0),
+        event(1, "Do action", BehaviorTransition::Started, 0.5),
+        event(1, "Do action", BehaviorTransition::Failure, 1.0),
+        event(1, "Do action", BehaviorTransition::Started, 2.0),
+        event(2, "Before recording", BehaviorTransition::Success, 2.5),
+    ];
+    let rows = timeline_rows(events.iter());
+    assert_eq!(rows.len(), 3);
+    assert_eq!(
+        rows[0].intervals,
+        vec![TimelineInterval {
+            start: 0.0,
+            end: None,
+            transition: None,
+        }]
+    );
+    assert_eq!(
+        rows[1].intervals,
+        vec![
+            TimelineInterval {
+                start: 0.5,
+                end: Some(1.0),
+                transition: Some(BehaviorTransition::Failure),
+            },
+            TimelineInterval {
+                start: 2.0,
+                end: None,
+                transition: None,
+            },
+        ]
+    );
+    assert_eq!(rows[2].name, "Before recording");
+    assert_eq!(rows[2].intervals[0].end, Some(2.5));
+}
@@ -0,0 +1,122 @@
+use bevy::{ecs::system::CommandQueue, prelude::*};
+use simula_behavior::{prelude::*, test::*};
+
+fn registry() -> BehaviorRegistry {
+    let mut app = App::new();
+    TestBehavior::register(&mut app);
+    app.world
+        .remove_resource::<BehaviorRegistry>()
+        .expect("Registry should be created by registering behaviors")
+}
+
+#[test]
+fn registry_lists_builtins() {
+    let registry = registry();
+    for name in [
+        "Debug",
+        "Delay",
+        "Sequencer",
+        "Selector",
+        "Inverter",
+        "Timeout",
+    ] {
+        assert!(registry.contains(name), "Missing behavior: {}", name);
+    }
+    let names = registry.iter().map(|r| r.name).collect::<Vec<_>>();
+    let mut sorted = names.clone();
+    sorted.sort();
+    assert_eq!(names, sorted);
+}
+
+#[test]
+fn registry_of_enum_matches_app_registry() {
+    let registry = registry();
+    let of = BehaviorRegistry::of::<TestBehavior>();
+    assert_eq!(of.len(), registry.len());
+    assert!(of.iter().zip(registry.iter()).all(|(a, b)| a.name == b.name));
+}
+
+#[test]
+fn registry_describes_nodes() {
+    let registry = registry();
+
+    let sequencer = registry.get("Sequencer").unwrap();
+    assert_eq!(sequencer.category, BehaviorType::Composite);
+    assert_eq!(sequencer.children, BehaviorChildCount::Any);
+    assert_eq!(sequencer.desc, Sequencer::DESC);
+
+    assert!(sequencer.flow.sequences);
+    assert!(!sequencer.flow.aborts);
+    assert!(registry.get("Timeout").unwrap().flow.aborts);
+
+    let inverter = registry.get("Inverter").unwrap();
+    assert_eq!(inverter.children, BehaviorChildCount::One);
+    assert!(inverter.children.allows(1));
+    assert!(!inverter.children.allows(2));
+
+    let debug = registry.get("Debug").unwrap();
+    assert_eq!(debug.category, BehaviorType::Action);
+    assert_eq!(registry.allows_children("Debug", 1), Some(false));
+    assert_eq!(registry.allows_children("Missing", 0), None);
+    let fields = debug
+        .fields
+        .iter()
+        .map(|field| field.name.as_str())
+        .collect::<Vec<_>>();
+    assert!(fields.contains(&"message"));
+    assert!(fields.contains(&"fail"));
+
+    let by_type = registry
+        .get_by_type_name(std::any::type_name::<Debug>())
+        .unwrap();
+    assert_eq!(by_type.name, "Debug");
+    assert!(registry
+        .category(BehaviorType::Decorator)
+        .all(|registration| registration.children == BehaviorChildCount::One));
+}
+
+#[test]
+fn registry_spawns_and_reads_nodes() {
+    let registry = registry();
+    let mut world = World::new();
+    let mut command_queue = CommandQueue::default();
+    let mut commands = Commands::new(&mut command_queue, &world);
+
+    let plain = commands.spawn().id();
+    assert!(registry.spawn("Delay", &mut commands.entity(plain), None));
+    let data = Debug {
+        message: "From the registry".to_string(),
+        ..default()
+    };
+    let custom = commands.spawn().id();
+    assert!(registry.spawn("Debug", &mut commands.entity(custom), Some(&data)));
+    assert!(!registry.spawn("Missing", &mut commands.entity(custom), None));
+    let mismatched = commands.spawn().id();
+    assert!(!registry.spawn("Delay", &mut commands.entity(mismatched), Some(&data)));
+    command_queue.apply(&mut world);
+
+    assert!(world.get::<Delay>(plain).is_some());
+    assert!(world.get::<BehaviorNode>(plain).is_some());
+    assert!(world.get::<Delay>(mismatched).is_none());
+    assert!(world.get::<BehaviorNode>(mismatched).is_none());
+    assert_eq!(
+        world.get::<Debug>(custom).unwrap().message,
+        "From the registry"
+    );
+
+    let (registration, behavior) = registry.from_entity(&world, custom).unwrap();
+    assert_eq!(registration.name, "Debug");
+    let mut debug = Debug::default();
+    debug.apply(behavior.as_ref());
+    assert_eq!(debug.message, "From the registry");
+}
+
+#[test]
+fn registration_spawns_behavior_of_enum() {
+    let registry = registry();
+    let behavior = registry
+        .get("Inverter")
+        .unwrap()
+        .behavior::<TestBehavior>();
+    assert!(matches!(behavior, Some(TestBehavior::Inverter(_))));
+}
@@ -0,0 +1,90 @@
+use simula_behavior::{prelude::*, test::*, BehaviorTrace};
+
+const BEFORE: &str = r#"
+(
+    root:(
+        "Greet",
+        Sequencer(()),
+        [
+            ("Say hello", Debug((message:"Hello"))),
+            ("Count", SetBlackboard((key:"count", value:Int(5)))),
+            ("Wait", Delay((duration:1000.0))),
+        ]
+    ),
+    blackboard: {
+        "count": Int(1),
+    },
+)
+"#;
+
+const AFTER: &str = r#"
+(
+    root:(
+        "Greet",
+        Sequencer(()),
+        [
+            ("Say hello", Debug((message:"Hello"))),
+            ("Wait", Delay((duration:0.0))),
+            ("Say goodbye", Debug((message:"Goodbye"))),
+        ]
+    ),
+    blackboard: {
+        "count": Int(1),
+        "mood": String("happy"),
+    },
+)
+"#;
+
+fn count(trace: &BehaviorTrace, event: &str) -> usize {
+    trace.0.iter().filter(|line| line.ends_with(event)).count()
+}
+
+#[test]
+fn reload_restart() {
+    let (trace, blackboard) = trace_behavior_reload(BEFORE, AFTER, BehaviorReload::Restart);
+    println!("{:#?}", trace);
+    // Started over from the root with the new document
+    assert_eq!(count(&trace, "STARTED Greet"), 2);
+    assert_eq!(count(&trace, "SUCCESS Say hello"), 2);
+    assert_eq!(count(&trace, "SUCCESS Say goodbye"), 1);
+    assert_eq!(count(&trace, "SUCCESS Greet"), 1);
+    assert_eq!(count(&trace, "SUCCESS Wait"), 1);
+    // Blackboard starts over from the new document values
+    assert_eq!(blackboard.get("count"), Some(&BlackboardValue::Int(1)));
+    assert_eq!(
+        blackboard.get("mood"),
+        Some(&BlackboardValue::String("happy".to_string()))
+    );
+}
+
+#[test]
+fn reload_preserve() {
+    let (trace, blackboard) = trace_behavior_reload(BEFORE, AFTER, BehaviorReload::Preserve);
+    println!("{:#?}", trace);
+    // Carried on from the waiting node with the new document
+    assert_eq!(count(&trace, "STARTED Greet"), 1);
+    assert_eq!(count(&trace, "SUCCESS Say hello"), 1);
+    assert_eq!(count(&trace, "STARTED Wait"), 1);
+    assert_eq!(count(&trace, "SUCCESS Wait"), 1);
+    assert_eq!(count(&trace, "SUCCESS Say goodbye"), 1);
+    assert_eq!(count(&trace, "SUCCESS Greet"), 1);
+    // Blackboard keeps its values, and gets new defaults
+    assert_eq!(blackboard.get("count"), Some(&BlackboardValue::Int(5)));
+    assert_eq!(
+        blackboard.get("mood"),
+        Some(&BlackboardValue::String("happy".to_string()))
+    );
+}
+
+#[test]
+fn reload_never() {
+    let (trace, blackboard) = trace_behavior_reload(BEFORE, AFTER, BehaviorReload::Never);
+    println!("{:#?}", trace);
+    // Still waiting in the old document
+    assert_eq!(count(&trace, "STARTED Greet"), 1);
+    assert_eq!(count(&trace, "STARTED Wait"), 1);
+    assert_eq!(count(&trace, "SUCCESS Wait"), 0);
+    assert_eq!(count(&trace, "STARTED Say goodbye"), 0);
+    assert_eq!(blackboard.get("count"), Some(&BlackboardValue::Int(5)));
+    assert_eq!(blackboard.get("mood"), None);
+}
@@ -0,0 +1,53 @@
+use simula_behavior::{test::*, BehaviorTrace};
+
+#[test]
+fn retry_until_out_of_attempts() {
+    let behavior = r#"
+    (
+        root:(
+            "Try a few times",
+            Retry((attempts: 2)),
+            [
+                ("Do action", Debug((message:"Hello, from DebugMessage0!", fail:true))),
+            ]
+        )
+    )
+    "#;
+    let trace = trace_behavior(behavior);
+    println!("{:#?}", trace);
+    let expected_trace = BehaviorTrace::from_list(&[
+        "[0] STARTED Try a few times",
+        "[1] STARTED Do action",
+        "[1] FAILURE Do action",
+        "[1] STARTED Do action",
+        "[1] FAILURE Do action",
+        "[1] STARTED Do action",
+        "[1] FAILURE Do action",
+        "[0] FAILURE Try a few times",
+    ]);
+    assert_eq!(&trace, &expected_trace);
+}
+
+#[test]
+fn retry_success() {
+    let behavior = r#"
+    (
+        root:(
+            "Try a few times",
+            Retry((attempts: 2)),
+            [
+                ("Do action", Debug((message:"Hello, from DebugMessage0!"))),
+            ]
+        )
+    )
+    "#;
+    let trace = trace_behavior(behavior);
+    println!("{:#?}", trace);
+    let expected_trace = BehaviorTrace::from_list(&[
+        "[0] STARTED Try a few times",
+        "[1] STARTED Do action",
+        "[1] SUCCESS Do action",
+        "[0] SUCCESS Try a few times",
+    ]);
+    assert_eq!(&trace, &expected_trace);
+}
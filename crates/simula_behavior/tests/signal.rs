@@ -0,0 +1,117 @@
+use bevy::prelude::*;
+use simula_behavior::{prelude::*, test::*};
+use simula_core::signal::{SignalController, SignalFunction, SignalGenerator};
+
+/// A process that moves by the controller output, like a velocity command
+fn plant(mut blackboards: Query<&mut BehaviorBlackboard>) {
+    for mut blackboard in &mut blackboards {
+        let output = blackboard.get::<f64>("output").copied().unwrap_or_default();
+        if let Some(pv) = blackboard.get_mut::<f64>("pv") {
+            *pv += output * FIXED_STEP;
+        }
+    }
+}
+
+fn controller(kp: f32) -> SignalController<f32> {
+    SignalController { kp, ..default() }
+}
+
+const DRIVE: &str = r#"
+(
+    root:("Drive", DriveSignal((
+        setpoint: Value(1.0),
+        pv: Blackboard("pv"),
+        tolerance: 0.01,
+        timeout: 5.0,
+        output: "output",
+    ))),
+    blackboard: {
+        "pv": Float(0.0),
+    },
+)
+"#;
+
+#[test]
+fn drive_signal_reaches_setpoint() {
+    let mut app = App::new();
+    test_app(&mut app).add_system(plant);
+    let tree = spawn_test_tree(&mut app, DRIVE);
+    // Controller on the agent owning the tree
+    let agent = app.world.spawn().insert(controller(2.0)).id();
+    app.world.entity_mut(agent).push_children(&[tree]);
+
+    assert_eq!(
+        update_frames(&mut app, 300),
+        BehaviorTrace::from_list(&["[0] STARTED Drive", "[0] SUCCESS Drive"])
+    );
+    let blackboard = app.world.get::<BehaviorBlackboard>(tree).unwrap();
+    let pv = *blackboard.get::<f64>("pv").unwrap();
+    assert!((pv - 1.0).abs() <= 0.011, "pv {}", pv);
+}
+
+#[test]
+fn drive_signal_times_out() {
+    let mut app = App::new();
+    test_app(&mut app).add_system(plant);
+    let tree = spawn_test_tree(&mut app, DRIVE);
+    // No gain, the process never moves
+    app.world.entity_mut(tree).insert(controller(0.0));
+
+    assert_eq!(
+        update_frames(&mut app, 240),
+        BehaviorTrace::from_list(&["[0] STARTED Drive"])
+    );
+    assert_eq!(
+        update_frames(&mut app, 120),
+        BehaviorTrace::from_list(&["[0] STARTED Drive", "[0] FAILURE Drive"])
+    );
+}
+
+#[test]
+fn drive_signal_fails_without_controller() {
+    let mut app = App::new();
+    test_app(&mut app);
+    spawn_test_tree(&mut app, DRIVE);
+
+    assert_eq!(
+        update_frames(&mut app, 10),
+        BehaviorTrace::from_list(&["[0] STARTED Drive", "[0] FAILURE Drive"])
+    );
+}
+
+const SAMPLE: &str = r#"
+(
+    root:("Sample", SampleSignal((key: "sample"))),
+)
+"#;
+
+#[test]
+fn sample_signal_writes_blackboard() {
+    let mut app = App::new();
+    test_app(&mut app);
+    let tree = spawn_test_tree(&mut app, SAMPLE);
+    app.world.entity_mut(tree).insert(SignalGenerator {
+        func: SignalFunction::Sine,
+        offset: 2.5,
+        ..default()
+    });
+
+    assert_eq!(
+        update_frames(&mut app, 10),
+        BehaviorTrace::from_list(&["[0] STARTED Sample", "[0] SUCCESS Sample"])
+    );
+    let blackboard = app.world.get::<BehaviorBlackboard>(tree).unwrap();
+    assert_eq!(blackboard.get::<f64>("sample"), Some(&2.5));
+}
+
+#[test]
+fn sample_signal_fails_without_generator() {
+    let mut app = App::new();
+    test_app(&mut app);
+    spawn_test_tree(&mut app, SAMPLE);
+
+    assert_eq!(
+        update_frames(&mut app, 10),
+        BehaviorTrace::from_list(&["[0] STARTED Sample", "[0] FAILURE Sample"])
+    );
+}
@@ -0,0 +1,84 @@
+use bevy::{
+    ecs::system::CommandQueue,
+    prelude::*,
+    reflect::{TypeRegistryArc, TypeUuid},
+};
+use serde::{Deserialize, Serialize};
+use simula_behavior::prelude::*;
+
+mod custom {
+    use super::*;
+
+    #[derive(Debug, Default, Component, Reflect, Clone, Serialize, Deserialize, Inspectable)]
+    pub struct Custom {
+        #[serde(default)]
+        pub value: i64,
+    }
+
+    impl BehaviorInfo for Custom {
+        const TYPE: BehaviorType = BehaviorType::Action;
+        const NAME: &'static str = "Custom";
+        const DESC: &'static str = "A custom action";
+    }
+
+    pub fn run(mut commands: Commands, customs: Query<Entity, (With<Custom>, BehaviorRunQuery)>) {
+        for entity in &customs {
+            commands.entity(entity).insert(BehaviorSuccess);
+        }
+    }
+}
+
+#[behavior_builtins]
+#[derive(Debug, Serialize, Deserialize, TypeUuid, BehaviorSpawner)]
+#[uuid = "0d1a5c9e-8f4b-4d55-9d2c-6c8a3c1f2b7e"]
+enum CustomBehavior {
+    #[behavior(run = "custom::run")]
+    Custom(custom::Custom),
+}
+
+#[test]
+fn spawner_inserts_custom_and_builtin_nodes() {
+    let behavior = r#"
+    (
+        root:(
+            "Sequencer of custom",
+            Sequencer(()),
+            [
+                ("Custom action", Custom((value: 7))),
+            ]
+        )
+    )
+    "#;
+    let document = ron::from_str::<BehaviorDocument<CustomBehavior>>(behavior).unwrap();
+    let mut world = World::new();
+    let mut command_queue = CommandQueue::default();
+    let mut commands = Commands::new(&mut command_queue, &world);
+    let root = BehaviorTree::spawn_tree(None, &mut commands, &document.root);
+    command_queue.apply(&mut world);
+
+    assert!(world.get::<Sequencer>(root).is_some());
+    let children = world.get::<BehaviorChildren>(root).unwrap();
+    assert_eq!(children.len(), 1);
+    let child = children[0];
+    assert_eq!(world.get::<custom::Custom>(child).unwrap().value, 7);
+    assert_eq!(world.get::<BehaviorNode>(child).unwrap().name, "Custom");
+}
+
+#[test]
+fn spawner_default_is_debug() {
+    assert!(matches!(
+        CustomBehavior::default(),
+        CustomBehavior::Debug(_)
+    ));
+}
+
+#[test]
+fn spawner_registers_types() {
+    let mut app = App::new();
+    CustomBehavior::register(&mut app);
+    let registry = app.world.resource::<TypeRegistryArc>().read();
+    assert!(registry
+        .get(std::any::TypeId::of::<custom::Custom>())
+        .is_some());
+    assert!(registry.get(std::any::TypeId::of::<Sequencer>()).is_some());
+}
@@ -0,0 +1,130 @@
+use bevy::{diagnostic::Diagnostics, prelude::*};
+use simula_behavior::{prelude::*, test::*};
+
+const BEHAVIOR: &str = r#"
+(
+    root:(
+        "Root",
+        Selector(()),
+        [
+            (
+                "In order",
+                Sequencer(()),
+                [
+                    ("Ok", Debug(())),
+                    ("Nope", Debug((fail: true))),
+                ]
+            ),
+            ("Wait a second", Delay((duration: 1.0))),
+        ]
+    ),
+)
+"#;
+
+/// Run the behavior for a number of frames, returns the app to look at its stats
+fn run_stats(frames: usize) -> App {
+    let mut app = App::new();
+    test_app(&mut app);
+    app.init_resource::<Diagnostics>();
+    app.world.resource::<BehaviorStats>().set_profiling(true);
+    spawn_test_tree(&mut app, BEHAVIOR);
+    update_frames(&mut app, frames);
+    app
+}
+
+#[test]
+fn stats_count_node_types() {
+    let app = run_stats(120);
+    let stats = app.world.resource::<BehaviorStats>();
+
+    let debug = stats.node("Debug").unwrap();
+    assert_eq!(debug.activations, 2);
+    assert_eq!(debug.successes, 1);
+    assert_eq!(debug.failures, 1);
+    assert_eq!(debug.success_ratio(), Some(0.5));
+
+    let sequencer = stats.node("Sequencer").unwrap();
+    assert_eq!(sequencer.activations, 1);
+    assert_eq!(sequencer.success_ratio(), Some(0.0));
+
+    let delay = stats.node("Delay").unwrap();
+    assert_eq!(delay.successes, 1);
+    let duration = delay.mean_duration().unwrap();
+    assert!((duration - 1.0).abs() < 0.05, "Delay ran {}s", duration);
+
+    assert_eq!(stats.node("Selector").unwrap().successes, 1);
+    assert!(stats.node("Inverter").is_none());
+}
+
+#[test]
+fn stats_time_node_systems() {
+    let app = run_stats(30);
+    let stats = app.world.resource::<BehaviorStats>();
+
+    let sequencer = stats.system("sequencer::run").unwrap();
+    assert_eq!(sequencer.runs, 30);
+    assert!(sequencer.total >= sequencer.mean());
+    assert!(sequencer.max_frame >= sequencer.last_frame);
+    assert!(stats.system("delay::run").is_some());
+    assert!(stats.systems().count() >= 20);
+}
+
+#[test]
+fn stats_report_diagnostics() {
+    let app = run_stats(10);
+    let diagnostics = app.world.resource::<Diagnostics>();
+    let activations = diagnostics.get(BehaviorStats::ACTIVATIONS).unwrap();
+    assert!(activations.value().is_some());
+    assert!(diagnostics
+        .iter()
+        .any(|diagnostic| diagnostic.name == "bt sequencer::run"));
+}
+
+#[test]
+fn stats_to_csv() {
+    let mut app = run_stats(120);
+    let mut stats = app.world.resource_mut::<BehaviorStats>();
+    let nodes = stats.nodes_csv();
+    let mut lines = nodes.lines();
+    assert_eq!(
+        lines.next(),
+        Some("behavior,activations,successes,failures,stops,success_ratio,mean_duration")
+    );
+    assert!(lines.any(|line| line.starts_with("Debug,2,1,1,0,0.5,")));
+    assert!(stats
+        .systems_csv()
+        .lines()
+        .any(|line| line.starts_with("\"sequencer::run\",120,")));
+
+    stats.clear();
+    assert_eq!(stats.nodes().count(), 0);
+    assert_eq!(stats.system("sequencer::run").unwrap().runs, 0);
+}
+
+#[test]
+fn stats_skip_system_timings_without_profiling() {
+    let mut app = App::new();
+    test_app(&mut app);
+    spawn_test_tree(&mut app, BEHAVIOR);
+    update_frames(&mut app, 10);
+    let stats = app.world.resource::<BehaviorStats>();
+    assert!(!stats.profiling());
+    assert_eq!(stats.system("sequencer::run").unwrap().runs, 0);
+    // Nodes are still counted
+    assert_eq!(stats.node("Debug").unwrap().activations, 2);
+}
+
+#[test]
+fn stats_forget_despawned_nodes() {
+    let mut app = App::new();
+    test_app(&mut app);
+    let tree = spawn_test_tree(&mut app, BEHAVIOR);
+    update_frames(&mut app, 10);
+    // The delay is still running
+    assert!(app.world.resource::<BehaviorStats>().running() > 0);
+
+    let root = app.world.get::<BehaviorTree>(tree).unwrap().root.unwrap();
+    app.world.entity_mut(root).despawn_recursive();
+    update_frames(&mut app, 1);
+    assert_eq!(app.world.resource::<BehaviorStats>().running(), 0);
+}
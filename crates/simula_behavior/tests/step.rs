@@ -0,0 +1,78 @@
+use simula_behavior::{test::*, BehaviorTrace};
+
+const SEQUENCE: &str = r#"
+(
+    root:(
+        "Do all in one go",
+        Sequencer(()),
+        [
+            ("Do action", Debug((message:"Hello, from DebugMessage0!"))),
+            ("Do another action", Debug((message:"Hello, from DebugMessage1!"))),
+            ("Do last action", Debug((message:"Hello, from DebugMessage2!"))),
+        ]
+    )
+)
+"#;
+
+#[test]
+fn step_per_frame() {
+    let trace = trace_behavior_steps(SEQUENCE, 1, 0);
+    println!("{:#?}", trace);
+    let expected_trace = BehaviorTrace::from_list(&["[0] STARTED Do all in one go"]);
+    assert_eq!(&trace, &expected_trace);
+}
+
+#[test]
+fn step_to_quiescence() {
+    let trace = trace_behavior_steps(SEQUENCE, 1, 100);
+    println!("{:#?}", trace);
+    let expected_trace = BehaviorTrace::from_list(&[
+        "[0] STARTED Do all in one go",
+        "[1] STARTED Do action",
+        "[1] SUCCESS Do action",
+        "[2] STARTED Do another action",
+        "[2] SUCCESS Do another action",
+        "[3] STARTED Do last action",
+        "[3] SUCCESS Do last action",
+        "[0] SUCCESS Do all in one go",
+    ]);
+    assert_eq!(&trace, &expected_trace);
+}
+
+#[test]
+fn step_budget() {
+    let trace = trace_behavior_steps(SEQUENCE, 1, 2);
+    println!("{:#?}", trace);
+    let expected_trace = BehaviorTrace::from_list(&[
+        "[0] STARTED Do all in one go",
+        "[1] STARTED Do action",
+        "[1] SUCCESS Do action",
+    ]);
+    assert_eq!(&trace, &expected_trace);
+}
+
+#[test]
+fn step_until_running_leaf() {
+    let behavior = r#"
+    (
+        root:(
+            "Do until busy",
+            Sequencer(()),
+            [
+                ("Do action", Debug((message:"Hello, from DebugMessage0!"))),
+                ("Take long", Debug((duration: 1000.0))),
+                ("Do last action", Debug((message:"Hello, from DebugMessage2!"))),
+            ]
+        )
+    )
+    "#;
+    let trace = trace_behavior_steps(behavior, 1, 100);
+    println!("{:#?}", trace);
+    let expected_trace = BehaviorTrace::from_list(&[
+        "[0] STARTED Do until busy",
+        "[1] STARTED Do action",
+        "[1] SUCCESS Do action",
+        "[2] STARTED Take long",
+    ]);
+    assert_eq!(&trace, &expected_trace);
+}
@@ -0,0 +1,136 @@
+use simula_behavior::{test::*, BehaviorTrace};
+
+#[test]
+fn subtree_success() {
+    let behavior = r#"
+    (
+        root:("Run greeting", Subtree((path: "behaviors/subtree_greet.bht.ron")))
+    )
+    "#;
+    let trace = trace_behavior_with_assets(behavior);
+    println!("{:#?}", trace);
+    let expected_trace = BehaviorTrace::from_list(&[
+        "[0] STARTED Run greeting",
+        "[2] STARTED Greet",
+        "[3] STARTED Say hello",
+        "[3] SUCCESS Say hello",
+        "[4] STARTED Say goodbye",
+        "[4] SUCCESS Say goodbye",
+        "[2] SUCCESS Greet",
+        "[0] SUCCESS Run greeting",
+    ]);
+    assert_eq!(&trace, &expected_trace);
+}
+
+#[test]
+fn subtree_in_sequence() {
+    let behavior = r#"
+    (
+        root:(
+            "Greet twice",
+            Sequencer(()),
+            [
+                ("First greeting", Subtree((path: "behaviors/subtree_greet.bht.ron"))),
+                ("Second greeting", Subtree((path: "behaviors/subtree_greet.bht.ron"))),
+            ]
+        )
+    )
+    "#;
+    let trace = trace_behavior_with_assets(behavior);
+    println!("{:#?}", trace);
+    let expected_trace = BehaviorTrace::from_list(&[
+        "[0] STARTED Greet twice",
+        "[1] STARTED First greeting",
+        "[4] STARTED Greet",
+        "[5] STARTED Say hello",
+        "[5] SUCCESS Say hello",
+        "[6] STARTED Say goodbye",
+        "[6] SUCCESS Say goodbye",
+        "[4] SUCCESS Greet",
+        "[1] SUCCESS First greeting",
+        "[2] STARTED Second greeting",
+        "[7] STARTED Greet",
+        "[8] STARTED Say hello",
+        "[8] SUCCESS Say hello",
+        "[9] STARTED Say goodbye",
+        "[9] SUCCESS Say goodbye",
+        "[7] SUCCESS Greet",
+        "[2] SUCCESS Second greeting",
+        "[0] SUCCESS Greet twice",
+    ]);
+    assert_eq!(&trace, &expected_trace);
+}
+
+#[test]
+fn subtree_recursive_failure() {
+    let behavior = r#"
+    (
+        root:("Run cycle", Subtree((path: "behaviors/subtree_cycle.bht.ron")))
+    )
+    "#;
+    let trace = trace_behavior_with_assets(behavior);
+    println!("{:#?}", trace);
+    let expected_trace = BehaviorTrace::from_list(&[
+        "[0] STARTED Run cycle",
+        "[2] STARTED Include myself",
+        "[2] FAILURE Include myself",
+        "[0] FAILURE Run cycle",
+    ]);
+    assert_eq!(&trace, &expected_trace);
+}
+
+#[test]
+fn subtree_recursive_failure_with_relative_path() {
+    let behavior = r#"
+    (
+        root:("Run self", Subtree((path: "behaviors/subtree_self.bht.ron")))
+    )
+    "#;
+    let trace = trace_behavior_with_assets(behavior);
+    let expected_trace = BehaviorTrace::from_list(&[
+        "[0] STARTED Run self",
+        "[2] STARTED Include myself again",
+        "[2] FAILURE Include myself again",
+        "[0] FAILURE Run self",
+    ]);
+    assert_eq!(&trace, &expected_trace);
+}
+
+#[test]
+fn subtree_recursive_failure_from_root_asset() {
+    // The root was loaded from the asset its subtree includes
+    let trace = trace_behavior_asset("behaviors/subtree_cycle.bht.ron");
+    let started = trace
+        .iter()
+        .filter(|line| line.contains("STARTED"))
+        .count();
+    assert_eq!(started, 1);
+    assert!(trace.last().unwrap().ends_with("FAILURE Include myself"));
+}
+
+#[test]
+fn subtree_broken_failure() {
+    let behavior = r#"
+    (
+        root:(
+            "Greet or wave",
+            Selector(()),
+            [
+                ("Run broken", Subtree((path: "behaviors/subtree_broken.bht.ron"))),
+                ("Run missing", Subtree((path: "behaviors/subtree_missing.bht.ron"))),
+            ]
+        )
+    )
+    "#;
+    let trace = trace_behavior_with_assets(behavior);
+    println!("{:#?}", trace);
+    let expected_trace = BehaviorTrace::from_list(&[
+        "[0] STARTED Greet or wave",
+        "[1] STARTED Run broken",
+        "[1] FAILURE Run broken",
+        "[2] STARTED Run missing",
+        "[2] FAILURE Run missing",
+        "[0] FAILURE Greet or wave",
+    ]);
+    assert_eq!(&trace, &expected_trace);
+}
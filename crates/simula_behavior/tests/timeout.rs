@@ -0,0 +1,49 @@
+use simula_behavior::{test::*, BehaviorTrace};
+
+#[test]
+fn timeout_success() {
+    let behavior = r#"
+    (
+        root:(
+            "Do it in time",
+            Timeout((duration: 1000.0)),
+            [
+                ("Do action", Debug((message:"Hello, from DebugMessage0!"))),
+            ]
+        )
+    )
+    "#;
+    let trace = trace_behavior(behavior);
+    println!("{:#?}", trace);
+    let expected_trace = BehaviorTrace::from_list(&[
+        "[0] STARTED Do it in time",
+        "[1] STARTED Do action",
+        "[1] SUCCESS Do action",
+        "[0] SUCCESS Do it in time",
+    ]);
+    assert_eq!(&trace, &expected_trace);
+}
+
+#[test]
+fn timeout_stops_child() {
+    let behavior = r#"
+    (
+        root:(
+            "Give up right away",
+            Timeout((duration: 0.0)),
+            [
+                ("Take long", Debug((duration: 1000.0))),
+            ]
+        )
+    )
+    "#;
+    let trace = trace_behavior(behavior);
+    println!("{:#?}", trace);
+    let expected_trace = BehaviorTrace::from_list(&[
+        "[0] STARTED Give up right away",
+        "[1] STARTED Take long",
+        "[1] STOPPED Take long",
+        "[0] FAILURE Give up right away",
+    ]);
+    assert_eq!(&trace, &expected_trace);
+}
@@ -0,0 +1,128 @@
+use simula_behavior::{test::*, BehaviorTrace};
+
+#[test]
+fn utility_picks_highest_score() {
+    let behavior = r#"
+    (
+        root:
+        (
+            "Do what matters most",
+            UtilitySelector((
+                scorers: [
+                    Fixed(0.2),
+                    Blackboard("hunger"),
+                    Curve((key: "fatigue", from: (0.0, 10.0), to: (0.0, 1.0))),
+                ],
+            )),
+            [
+                ("Wander", Debug(())),
+                ("Eat", Debug(())),
+                ("Rest", Debug(())),
+            ]
+        ),
+        blackboard: {
+            "hunger": Float(0.5),
+            "fatigue": Int(8),
+        },
+    )
+    "#;
+    let trace = trace_behavior(behavior);
+    println!("{:#?}", trace);
+    let expected_trace = BehaviorTrace::from_list(&[
+        "[0] STARTED Do what matters most",
+        "[3] STARTED Rest",
+        "[3] SUCCESS Rest",
+        "[0] SUCCESS Do what matters most",
+    ]);
+    assert_eq!(&trace, &expected_trace);
+}
+
+#[test]
+fn utility_falls_back_on_failure() {
+    let behavior = r#"
+    (
+        root:
+        (
+            "Do what matters most",
+            UtilitySelector((scorers: [Fixed(0.2), Fixed(0.8), Fixed(0.5)])),
+            [
+                ("Wander", Debug(())),
+                ("Eat", Debug((fail: true))),
+                ("Rest", Debug((fail: true))),
+            ]
+        )
+    )
+    "#;
+    let trace = trace_behavior(behavior);
+    println!("{:#?}", trace);
+    let expected_trace = BehaviorTrace::from_list(&[
+        "[0] STARTED Do what matters most",
+        "[2] STARTED Eat",
+        "[2] FAILURE Eat",
+        "[3] STARTED Rest",
+        "[3] FAILURE Rest",
+        "[1] STARTED Wander",
+        "[1] SUCCESS Wander",
+        "[0] SUCCESS Do what matters most",
+    ]);
+    assert_eq!(&trace, &expected_trace);
+}
+
+const SWITCH: &str = r#"
+(
+    root:
+    (
+        "Live",
+        Parallel(()),
+        [
+            (
+                "Do what matters most",
+                UtilitySelector((
+                    scorers: [Blackboard("work"), Blackboard("eat")],
+                    interval: 0.000001,
+                    hysteresis: HYSTERESIS,
+                )),
+                [
+                    ("Work", Debug((duration: 1000.0))),
+                    ("Eat", Debug(())),
+                ]
+            ),
+            (
+                "Get hungry",
+                Sequencer(()),
+                [
+                    ("Wait a bit", Debug(())),
+                    ("Feel hungry", SetBlackboard((key: "eat", value: Float(1.0)))),
+                ]
+            ),
+        ]
+    ),
+    blackboard: {
+        "work": Float(0.6),
+        "eat": Float(0.5),
+    },
+)
+"#;
+
+#[test]
+fn utility_switches_to_better_child() {
+    let trace = trace_behavior(&SWITCH.replace("HYSTERESIS", "0.1"));
+    println!("{:#?}", trace);
+    let stopped = position(&trace, "[2] STOPPED Work");
+    assert!(position(&trace, "[2] STARTED Work") < stopped);
+    assert!(position(&trace, "[6] SUCCESS Feel hungry") < stopped);
+    assert!(stopped < position(&trace, "[3] STARTED Eat"));
+    assert!(
+        position(&trace, "[3] SUCCESS Eat") < position(&trace, "[1] SUCCESS Do what matters most")
+    );
+}
+
+#[test]
+fn utility_hysteresis_keeps_child() {
+    let trace = trace_behavior(&SWITCH.replace("HYSTERESIS", "0.5"));
+    println!("{:#?}", trace);
+    position(&trace, "[2] STARTED Work");
+    position(&trace, "[6] SUCCESS Feel hungry");
+    assert!(!trace.contains(&"[2] STOPPED Work".to_string()));
+    assert!(!trace.contains(&"[3] STARTED Eat".to_string()));
+}
@@ -0,0 +1,133 @@
+use simula_behavior::{prelude::*, test::*};
+
+fn count(trace: &BehaviorTrace, line: &str) -> usize {
+    trace.iter().filter(|traced| *traced == line).count()
+}
+
+#[test]
+fn weighted_selector_zero_weight_goes_last() {
+    let mut selector = WeightedSelector {
+        weights: vec![0.0, 1.0, 0.0],
+        ..Default::default()
+    };
+    for _ in 0..10 {
+        selector.seed += 1;
+        assert_eq!(selector.shuffle(3), vec![1, 0, 2]);
+    }
+}
+
+#[test]
+fn weighted_selector_is_reproducible() {
+    let mut a = WeightedSelector {
+        weights: vec![1.0, 2.0, 3.0, 4.0],
+        seed: 42,
+        ..Default::default()
+    };
+    let mut b = a.clone();
+    let order = a.shuffle(4);
+    assert_eq!(order, b.shuffle(4));
+    // Same order every run without reseeding
+    assert_eq!(order, a.shuffle(4));
+    assert_eq!(a.seed, 42);
+
+    // Reseeding moves on to a new seed, the same way every time
+    a.reseed = true;
+    b.reseed = true;
+    a.shuffle(4);
+    b.shuffle(4);
+    assert_ne!(a.seed, 42);
+    assert_eq!(a.seed, b.seed);
+}
+
+#[test]
+fn weighted_selector_prefers_heavy_children() {
+    let mut selector = WeightedSelector {
+        weights: vec![1.0, 9.0],
+        reseed: true,
+        seed: 1,
+        ..Default::default()
+    };
+    let heavy_first = (0..1000).filter(|_| selector.shuffle(2)[0] == 1).count();
+    assert!(heavy_first > 800, "Heavy child first {} times", heavy_first);
+}
+
+#[test]
+fn weighted_selector_picks_by_weight() {
+    let behavior = r#"
+    (
+        root:(
+            "Pick",
+            WeightedSelector((weights: [0.0, 1.0], seed: 7)),
+            [
+                ("Light", Debug(())),
+                ("Heavy", Debug(())),
+            ]
+        ),
+    )
+    "#;
+    let trace = trace_behavior(behavior);
+    println!("{:#?}", trace);
+    let expected_trace = BehaviorTrace::from_list(&[
+        "[0] STARTED Pick",
+        "[2] STARTED Heavy",
+        "[2] SUCCESS Heavy",
+        "[0] SUCCESS Pick",
+    ]);
+    assert_eq!(&trace, &expected_trace);
+}
+
+#[test]
+fn weighted_selector_falls_back_on_failure() {
+    let behavior = r#"
+    (
+        root:(
+            "Pick",
+            WeightedSelector((weights: [0.0, 1.0], seed: 7)),
+            [
+                ("Light", Debug(())),
+                ("Heavy", Debug((fail: true))),
+            ]
+        ),
+    )
+    "#;
+    let trace = trace_behavior(behavior);
+    println!("{:#?}", trace);
+    let expected_trace = BehaviorTrace::from_list(&[
+        "[0] STARTED Pick",
+        "[2] STARTED Heavy",
+        "[2] FAILURE Heavy",
+        "[1] STARTED Light",
+        "[1] SUCCESS Light",
+        "[0] SUCCESS Pick",
+    ]);
+    assert_eq!(&trace, &expected_trace);
+}
+
+#[test]
+fn weighted_selector_shuffle_bag_tries_all_children() {
+    let behavior = r#"
+    (
+        root:(
+            "Three times",
+            Repeater((repeat: Times(3))),
+            [
+                (
+                    "Pick",
+                    WeightedSelector((weights: [1.0, 5.0, 1.0], seed: 3, shuffle_bag: true)),
+                    [
+                        ("A", Debug(())),
+                        ("B", Debug(())),
+                        ("C", Debug(())),
+                    ]
+                ),
+            ]
+        ),
+    )
+    "#;
+    let trace = trace_behavior(behavior);
+    println!("{:#?}", trace);
+    assert_eq!(count(&trace, "[1] SUCCESS Pick"), 3);
+    assert_eq!(count(&trace, "[2] STARTED A"), 1);
+    assert_eq!(count(&trace, "[3] STARTED B"), 1);
+    assert_eq!(count(&trace, "[4] STARTED C"), 1);
+}
@@ -1,11 +1,12 @@
 use bevy::prelude::Reflect;
+use bevy_inspector_egui::Inspectable;
 use enum_iterator::Sequence;
 use serde::{Deserialize, Serialize};
 use std::fmt::Display;
 
 #[allow(missing_docs)]
 #[derive(
-    Reflect, Debug, Copy, Clone, PartialEq, Serialize, Deserialize, Sequence, Display,
+    Reflect, Debug, Copy, Clone, PartialEq, Serialize, Deserialize, Sequence, Display, Inspectable,
 )]
 pub enum EaseFunction {
     Linear,
@@ -1,7 +1,16 @@
 use bevy::prelude::*;
 use std::time::Duration;
 
-#[derive(Reflect, Component, Default)]
+/// Whether the controller computes its output, or follows one set by hand
+#[derive(Reflect, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SignalControllerMode {
+    #[default]
+    Auto,
+    Manual,
+}
+
+/// PID controller, with output limits, anti-windup and a filtered derivative
+#[derive(Reflect, Component)]
 #[reflect(Component)]
 pub struct SignalController<Sample: Default + Reflect + PartialEq + Clone> {
     /// proportional gain
@@ -10,16 +19,67 @@ pub struct SignalController<Sample: Default + Reflect + PartialEq + Clone> {
     pub ki: Sample,
     /// derivative gain
     pub kd: Sample,
+    /// setpoint weight of the proportional term, 1 acts on the error, 0 on the measurement only
+    pub setpoint_weight: Sample,
+    /// setpoint weight of the derivative term, 0 acts on the measurement only, without a kick
+    /// on setpoint changes
+    pub derivative_weight: Sample,
+    /// time constant of the derivative low-pass filter in seconds, 0 to not filter
+    pub derivative_filter: f32,
+    /// lowest output
+    pub output_min: Sample,
+    /// highest output
+    pub output_max: Sample,
+    /// lowest integral term
+    pub integral_min: Sample,
+    /// highest integral term
+    pub integral_max: Sample,
+    /// back-calculation gain, unwinds the integral term while the output is clamped
+    pub anti_windup: Sample,
+    pub mode: SignalControllerMode,
     /// setpoint
     pub setpoint: Sample,
-    /// error to determine pi gain
+    /// weighted derivative error of the last step
     pub last_error: Sample,
-    /// accumulated error
+    /// integral term
     pub integral: Sample,
+    /// filtered derivative term
+    pub derivative: Sample,
     /// manipulated variable
     pub output: Sample,
-    /// process variable
+    /// process variable of the last step
     pub pv: Sample,
+    /// whether `last_error` is set, no derivative is taken before
+    pub primed: bool,
+}
+
+impl<Sample> Default for SignalController<Sample>
+where
+    Sample: Copy + Default + Reflect + PartialEq + AsSample<Sample>,
+{
+    fn default() -> Self {
+        Self {
+            kp: Sample::default(),
+            ki: Sample::default(),
+            kd: Sample::default(),
+            setpoint_weight: Sample::from(1.0),
+            derivative_weight: Sample::default(),
+            derivative_filter: 0.0,
+            output_min: Sample::from(f32::NEG_INFINITY),
+            output_max: Sample::from(f32::INFINITY),
+            integral_min: Sample::from(f32::NEG_INFINITY),
+            integral_max: Sample::from(f32::INFINITY),
+            anti_windup: Sample::default(),
+            mode: SignalControllerMode::default(),
+            setpoint: Sample::default(),
+            last_error: Sample::default(),
+            integral: Sample::default(),
+            derivative: Sample::default(),
+            output: Sample::default(),
+            pv: Sample::default(),
+            primed: false,
+        }
+    }
 }
 
 impl<Sample> SignalController<Sample>
@@ -34,40 +94,144 @@ where
         + std::ops::Div<Output = Sample>
         + AsSample<Sample>,
 {
+    /// Step the controller with a measured process variable, returns the output. In manual
+    /// mode the output set by hand is returned, and the integral term tracks it so that
+    /// switching back to auto doesn't bump the output.
     pub fn control(&mut self, setpoint: Sample, pv: Sample, dt: Duration) -> Sample {
         let dt = dt.as_secs_f32();
-        if dt > 0.0 {
-            let dt: Sample = Sample::from(dt);
-            self.setpoint = setpoint;
-            let error = setpoint - pv;
-            let p = error;
-            self.integral = self.integral + error * dt;
-            let i = self.integral;
-            let d = (error - self.last_error) / dt;
-            self.last_error = error;
-            self.output = self.kp * p + self.ki * i + self.kd * d;
-            self.pv = pv + self.output * dt;
-            self.output
+        if dt <= 0.0 {
+            return self.output;
+        }
+        self.setpoint = setpoint;
+        self.pv = pv;
+        let p = self.proportional();
+
+        // Derivative on the weighted error, low-pass filtered
+        let error_d = self.derivative_weight * setpoint - pv;
+        let raw_d = if self.primed {
+            self.kd * (error_d - self.last_error) / Sample::from(dt)
         } else {
             Sample::from(0.0)
+        };
+        self.last_error = error_d;
+        self.primed = true;
+        let alpha = if self.derivative_filter > 0.0 {
+            dt / (self.derivative_filter + dt)
+        } else {
+            1.0
+        };
+        self.derivative = self.derivative + Sample::from(alpha) * (raw_d - self.derivative);
+
+        if self.mode == SignalControllerMode::Manual {
+            self.output = Sample::clamp(self.output, self.output_min, self.output_max);
+            self.track_output(p);
+            return self.output;
+        }
+
+        let error = setpoint - pv;
+        self.integral = self.clamp_integral(self.integral + self.ki * error * Sample::from(dt));
+        let unclamped = p + self.integral + self.derivative;
+        self.output = Sample::clamp(unclamped, self.output_min, self.output_max);
+        // Back-calculation, unwind the integral by how much the output was clamped
+        self.integral = self.clamp_integral(
+            self.integral + self.anti_windup * (self.output - unclamped) * Sample::from(dt),
+        );
+        self.output
+    }
+
+    /// Switch between auto and manual. The output is kept as is, auto carries on from it.
+    pub fn set_mode(&mut self, mode: SignalControllerMode) {
+        if self.mode == SignalControllerMode::Manual && mode == SignalControllerMode::Auto {
+            let p = self.proportional();
+            self.track_output(p);
         }
+        self.mode = mode;
+    }
+
+    /// Set the output by hand, used in manual mode
+    pub fn set_output(&mut self, output: Sample) {
+        self.output = Sample::clamp(output, self.output_min, self.output_max);
+    }
+
+    /// Clear the integral and derivative terms and the output, gains, limits and mode stay
+    pub fn reset(&mut self) {
+        self.last_error = Sample::default();
+        self.integral = Sample::default();
+        self.derivative = Sample::default();
+        self.output = Sample::default();
+        self.primed = false;
+    }
+
+    fn proportional(&self) -> Sample {
+        self.kp * (self.setpoint_weight * self.setpoint - self.pv)
+    }
+
+    /// Make the integral term account for the current output
+    fn track_output(&mut self, p: Sample) {
+        self.integral = self.clamp_integral(self.output - p - self.derivative);
+    }
+
+    fn clamp_integral(&self, integral: Sample) -> Sample {
+        Sample::clamp(integral, self.integral_min, self.integral_max)
     }
 }
 
 pub trait AsSample<Sample> {
     fn from(src: f32) -> Sample;
+
+    /// Limit a sample between two others, per component
+    fn clamp(value: Sample, min: Sample, max: Sample) -> Sample;
 }
 
 impl AsSample<f32> for f32 {
     fn from(src: f32) -> f32 {
         src
     }
+
+    fn clamp(value: f32, min: f32, max: f32) -> f32 {
+        value.max(min).min(max)
+    }
 }
 
 impl AsSample<Vec3> for Vec3 {
     fn from(src: f32) -> Vec3 {
         Vec3::new(src, src, src)
     }
+
+    fn clamp(value: Vec3, min: Vec3, max: Vec3) -> Vec3 {
+        value.max(min).min(max)
+    }
+}
+
+#[cfg(test)]
+const DT: Duration = Duration::from_millis(10);
+
+/// Step response of a process that integrates the output, returns the measurements
+#[cfg(test)]
+fn step_response<Sample>(
+    controller: &mut SignalController<Sample>,
+    setpoint: Sample,
+    steps: usize,
+) -> Vec<Sample>
+where
+    Sample: Copy
+        + Default
+        + Reflect
+        + PartialEq
+        + std::ops::Add<Output = Sample>
+        + std::ops::Sub<Output = Sample>
+        + std::ops::Mul<Output = Sample>
+        + std::ops::Div<Output = Sample>
+        + AsSample<Sample>,
+{
+    let mut pv = Sample::default();
+    (0..steps)
+        .map(|_| {
+            let output = controller.control(setpoint, pv, DT);
+            pv = pv + output * Sample::from(DT.as_secs_f32());
+            pv
+        })
+        .collect()
 }
 
 #[test]
@@ -78,3 +242,178 @@ fn test_types_compile() {
     let _c = s_f32.control(0.0, 0.0, dt);
     let _c = s_vec3.control(Vec3::ZERO, Vec3::ZERO, dt);
 }
+
+#[test]
+fn step_response_f32() {
+    let mut controller = SignalController::<f32> {
+        kp: 4.0,
+        ki: 1.0,
+        ..default()
+    };
+    let response = step_response(&mut controller, 1.0, 2000);
+    assert!((response[1999] - 1.0).abs() < 0.01, "{}", response[1999]);
+    // The measurement is ours, the controller doesn't integrate it
+    assert_eq!(controller.pv, response[1998]);
+}
+
+#[test]
+fn step_response_vec3() {
+    let mut controller = SignalController::<Vec3> {
+        kp: Vec3::splat(4.0),
+        ki: Vec3::splat(1.0),
+        output_min: Vec3::splat(-2.0),
+        output_max: Vec3::splat(2.0),
+        ..default()
+    };
+    let setpoint = Vec3::new(1.0, -2.0, 0.5);
+    let response = step_response(&mut controller, setpoint, 2000);
+    assert!(
+        response[1999].abs_diff_eq(setpoint, 0.01),
+        "{}",
+        response[1999]
+    );
+}
+
+#[test]
+fn output_limits_f32() {
+    let mut controller = SignalController::<f32> {
+        kp: 100.0,
+        output_min: -0.5,
+        output_max: 0.5,
+        ..default()
+    };
+    assert_eq!(controller.control(1.0, 0.0, DT), 0.5);
+    assert_eq!(controller.control(-1.0, 0.0, DT), -0.5);
+}
+
+#[test]
+fn output_limits_vec3() {
+    let mut controller = SignalController::<Vec3> {
+        kp: Vec3::splat(100.0),
+        output_min: Vec3::splat(-0.5),
+        output_max: Vec3::splat(0.5),
+        ..default()
+    };
+    let output = controller.control(Vec3::new(1.0, -1.0, 0.001), Vec3::ZERO, DT);
+    assert!(
+        output.abs_diff_eq(Vec3::new(0.5, -0.5, 0.1), 1e-5),
+        "{}",
+        output
+    );
+}
+
+#[test]
+fn anti_windup_limits_overshoot() {
+    let overshoot = |controller: SignalController<f32>| {
+        let mut controller = controller;
+        let response = step_response(&mut controller, 1.0, 2000);
+        response.into_iter().fold(f32::MIN, f32::max) - 1.0
+    };
+    let saturated = || SignalController::<f32> {
+        kp: 1.0,
+        ki: 2.0,
+        output_min: -0.2,
+        output_max: 0.2,
+        ..default()
+    };
+    let windup = overshoot(saturated());
+    let back_calculation = overshoot(SignalController {
+        anti_windup: 10.0,
+        ..saturated()
+    });
+    let clamped = overshoot(SignalController {
+        integral_min: -0.2,
+        integral_max: 0.2,
+        ..saturated()
+    });
+    assert!(windup > 0.5, "{}", windup);
+    assert!(back_calculation < windup / 4.0, "{}", back_calculation);
+    assert!(clamped < windup / 4.0, "{}", clamped);
+}
+
+#[test]
+fn derivative_on_measurement() {
+    // No kick on a setpoint step, only measurement changes count
+    let mut controller = SignalController::<f32> {
+        kd: 1.0,
+        ..default()
+    };
+    controller.control(0.0, 0.0, DT);
+    assert_eq!(controller.control(1.0, 0.0, DT), 0.0);
+    assert!(controller.control(1.0, 0.1, DT) < 0.0);
+
+    // Unless the derivative acts on the error
+    let mut controller = SignalController::<f32> {
+        kd: 1.0,
+        derivative_weight: 1.0,
+        ..default()
+    };
+    controller.control(0.0, 0.0, DT);
+    assert!((controller.control(1.0, 0.0, DT) - 100.0).abs() < 1e-3);
+}
+
+#[test]
+fn derivative_filter() {
+    let mut raw = SignalController::<f32> {
+        kd: 1.0,
+        ..default()
+    };
+    let mut filtered = SignalController::<f32> {
+        kd: 1.0,
+        derivative_filter: 0.09,
+        ..default()
+    };
+    raw.control(0.0, 0.0, DT);
+    filtered.control(0.0, 0.0, DT);
+    let raw = raw.control(0.0, 1.0, DT);
+    let filtered = filtered.control(0.0, 1.0, DT);
+    assert!((raw + 100.0).abs() < 1e-3);
+    // A tenth of the way, with dt / (filter + dt)
+    assert!((filtered + 10.0).abs() < 1e-3, "{}", filtered);
+}
+
+#[test]
+fn setpoint_weight() {
+    let mut controller = SignalController::<f32> {
+        kp: 2.0,
+        setpoint_weight: 0.5,
+        ..default()
+    };
+    assert_eq!(controller.control(1.0, 0.0, DT), 1.0);
+}
+
+#[test]
+fn bumpless_mode_switch() {
+    let mut controller = SignalController::<f32> {
+        kp: 2.0,
+        ki: 1.0,
+        ..default()
+    };
+    controller.set_mode(SignalControllerMode::Manual);
+    controller.set_output(0.7);
+    for _ in 0..10 {
+        assert_eq!(controller.control(1.0, 0.2, DT), 0.7);
+    }
+
+    // Auto carries on from the manual output
+    controller.set_mode(SignalControllerMode::Auto);
+    let output = controller.control(1.0, 0.2, DT);
+    assert!((output - 0.7).abs() < 0.01, "{}", output);
+}
+
+#[test]
+fn reset_clears_state() {
+    let mut controller = SignalController::<f32> {
+        kp: 1.0,
+        ki: 1.0,
+        kd: 1.0,
+        ..default()
+    };
+    step_response(&mut controller, 1.0, 10);
+    controller.reset();
+    assert_eq!(controller.integral, 0.0);
+    assert_eq!(controller.derivative, 0.0);
+    assert_eq!(controller.output, 0.0);
+    assert!(!controller.primed);
+    assert_eq!(controller.kp, 1.0);
+}
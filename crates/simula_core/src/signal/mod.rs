@@ -1,5 +1,5 @@
 pub mod controller;
 pub mod generator;
 
-pub use controller::SignalController;
+pub use controller::{SignalController, SignalControllerMode};
 pub use generator::{SignalFunction, SignalGenerator};
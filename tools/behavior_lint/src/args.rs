@@ -0,0 +1,27 @@
+use clap::{ArgEnum, Parser};
+use std::path::PathBuf;
+
+#[derive(Parser, Debug)]
+#[clap(name = "behavior_lint", rename_all = "kebab-case")]
+pub struct Args {
+    /// Behavior documents, or folders searched for `.bht.ron` files
+    #[clap(required = true)]
+    pub paths: Vec<PathBuf>,
+
+    #[clap(long, arg_enum, default_value = "human")]
+    pub format: Format,
+
+    /// Accept a custom node, like `AgentRest:action`, as `Name[:action|decorator|composite]`
+    #[clap(long)]
+    pub allow: Vec<String>,
+
+    /// Fail on warnings too
+    #[clap(long)]
+    pub deny_warnings: bool,
+}
+
+#[derive(ArgEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Format {
+    Human,
+    Json,
+}
@@ -0,0 +1,124 @@
+use bevy::reflect::TypeUuid;
+use clap::Parser;
+use serde::{Deserialize, Serialize};
+use simula_behavior::{
+    lint::{BehaviorLintIssue, BehaviorLintReport, BehaviorLintSeverity, BehaviorLinter},
+    prelude::*,
+};
+use std::{
+    path::{Path, PathBuf},
+    process::ExitCode,
+};
+
+pub use args::{Args, Format};
+
+mod args;
+
+/// Nodes documents are checked against, custom nodes come from `--allow`
+#[behavior_builtins]
+#[derive(Debug, Clone, Serialize, Deserialize, TypeUuid, BehaviorSpawner)]
+#[uuid = "8b0e4f3a-2d61-4c7e-9f15-3a7c2e9d4b68"]
+pub enum LintBehavior {}
+
+#[derive(Serialize)]
+struct Summary<'a> {
+    files: &'a [BehaviorLintReport],
+    errors: usize,
+    warnings: usize,
+}
+
+fn main() -> ExitCode {
+    let args = Args::parse();
+
+    let mut linter = BehaviorLinter::<LintBehavior>::new();
+    for allow in args.allow.iter() {
+        let (name, typ) = match allow.split_once(':') {
+            Some((name, "action")) => (name, Some(BehaviorType::Action)),
+            Some((name, "decorator")) => (name, Some(BehaviorType::Decorator)),
+            Some((name, "composite")) => (name, Some(BehaviorType::Composite)),
+            Some((_, typ)) => {
+                eprintln!("Unknown node type `{}` in `--allow {}`", typ, allow);
+                return ExitCode::from(2);
+            }
+            None => (allow.as_str(), None),
+        };
+        linter.allow(name, typ);
+    }
+
+    let mut files = vec![];
+    let mut reports = vec![];
+    for path in args.paths.iter() {
+        collect_documents(path, &mut files, &mut reports);
+    }
+    files.sort();
+
+    reports.extend(files.iter().map(|file| linter.lint_file(file)));
+    let errors = reports.iter().map(|report| report.errors()).sum();
+    let warnings = reports.iter().map(|report| report.warnings()).sum();
+
+    match args.format {
+        Format::Human => {
+            for report in reports.iter() {
+                print!("{}", report.to_text());
+            }
+            println!(
+                "{} files checked, {} errors, {} warnings",
+                reports.len(),
+                errors,
+                warnings
+            );
+        }
+        Format::Json => {
+            let summary = Summary {
+                files: &reports,
+                errors,
+                warnings,
+            };
+            match serde_json::to_string_pretty(&summary) {
+                Ok(json) => println!("{}", json),
+                Err(err) => {
+                    eprintln!("Failed to write report: {}", err);
+                    return ExitCode::from(2);
+                }
+            }
+        }
+    }
+
+    if errors > 0 || (args.deny_warnings && warnings > 0) {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+/// Files are taken as is, folders are searched for behavior documents. Folders that can't
+/// be read are reported as errors.
+fn collect_documents(path: &Path, files: &mut Vec<PathBuf>, reports: &mut Vec<BehaviorLintReport>) {
+    if !path.is_dir() {
+        files.push(path.to_path_buf());
+        return;
+    }
+    let entries = match std::fs::read_dir(path) {
+        Ok(entries) => entries,
+        Err(err) => {
+            reports.push(BehaviorLintReport {
+                file: path.display().to_string(),
+                issues: vec![BehaviorLintIssue {
+                    severity: BehaviorLintSeverity::Error,
+                    rule: "read",
+                    node: String::new(),
+                    message: err.to_string(),
+                }],
+            });
+            return;
+        }
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            collect_documents(&path, files, reports);
+        } else if path.to_string_lossy().ends_with(".bht.ron") {
+            files.push(path);
+        }
+    }
+}
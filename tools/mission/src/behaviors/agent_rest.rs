@@ -3,7 +3,11 @@ use serde::{Deserialize, Serialize};
 use simula_behavior::prelude::*;
 
 #[derive(Debug, Default, Component, Reflect, Clone, Serialize, Deserialize, Inspectable)]
-pub struct AgentRest;
+pub struct AgentRest {
+    /// Work target to rest from, usually bound to the key written by `AgentWork`
+    #[serde(default)]
+    pub target: BehaviorProp<i64>,
+}
 
 impl BehaviorInfo for AgentRest {
     const TYPE: BehaviorType = BehaviorType::Action;
@@ -11,8 +15,16 @@ impl BehaviorInfo for AgentRest {
     const DESC: &'static str = "Take a break for a bit";
 }
 
-pub fn run(mut commands: Commands, agents: Query<(Entity, &mut AgentRest), BehaviorRunQuery>) {
-    for (agent, _rest) in &agents {
+pub fn run(
+    mut commands: Commands,
+    agents: Query<(Entity, &AgentRest, &BehaviorNode), BehaviorRunQuery>,
+    blackboards: Query<&BehaviorBlackboard>,
+) {
+    for (agent, rest, node) in &agents {
+        let blackboard = node.tree.and_then(|tree| blackboards.get(tree).ok());
+        if let Some(target) = rest.target.get(blackboard) {
+            debug!("[{}] RESTING from target {}", agent.id(), target);
+        }
         commands.entity(agent).insert(BehaviorSuccess);
     }
 }
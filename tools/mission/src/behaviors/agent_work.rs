@@ -3,7 +3,11 @@ use serde::{Deserialize, Serialize};
 use simula_behavior::prelude::*;
 
 #[derive(Debug, Default, Component, Reflect, Clone, Serialize, Deserialize, Inspectable)]
-pub struct AgentWork;
+pub struct AgentWork {
+    /// Work target picked by the agent, usually bound to a blackboard key
+    #[serde(default)]
+    pub target: BehaviorProp<i64>,
+}
 
 impl BehaviorInfo for AgentWork {
     const TYPE: BehaviorType = BehaviorType::Action;
@@ -11,8 +15,16 @@ impl BehaviorInfo for AgentWork {
     const DESC: &'static str = "Do some work";
 }
 
-pub fn run(mut commands: Commands, agents: Query<(Entity, &mut AgentWork), BehaviorRunQuery>) {
-    for (agent, _work) in &agents {
+pub fn run(
+    mut commands: Commands,
+    mut agents: Query<(Entity, &mut AgentWork, &BehaviorNode), BehaviorRunQuery>,
+    mut blackboards: Query<&mut BehaviorBlackboard>,
+) {
+    for (agent, mut work, node) in &mut agents {
+        let mut blackboard = node.tree.and_then(|tree| blackboards.get_mut(tree).ok());
+        let target = work.target.get(blackboard.as_deref()).unwrap_or_default() + 1;
+        work.target.set(blackboard.as_deref_mut(), target);
+        debug!("[{}] WORKING on target {}", agent.id(), target);
         commands.entity(agent).insert(BehaviorSuccess);
     }
 }
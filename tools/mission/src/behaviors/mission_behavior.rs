@@ -1,5 +1,5 @@
-use crate::behaviors;
-use bevy::{ecs::system::EntityCommands, prelude::*, reflect::TypeUuid};
+use crate::behaviors::{self, token_transfer::TokenTransfer};
+use bevy::{prelude::*, reflect::TypeUuid};
 use serde::{Deserialize, Serialize};
 use simula_behavior::prelude::*;
 
@@ -8,50 +8,22 @@ pub struct MissionBehaviorPlugin;
 impl Plugin for MissionBehaviorPlugin {
     fn build(&self, app: &mut App) {
         app.add_startup_system(setup)
-            .add_system(behavior_loader::<MissionBehavior>)
-            .add_system(behaviors::agent_rest::run)
-            .add_system(behaviors::agent_work::run);
+            .add_plugin(BehaviorSpawnerPlugin::<MissionBehavior>::default())
+            .add_plugin(BehaviorEventPlugin::<TokenTransfer>::default())
+            .add_plugin(BehaviorEditorPlugin::<MissionBehavior>::default());
     }
 }
 
-#[derive(Debug, Serialize, Deserialize, TypeUuid)]
+#[behavior_builtins]
+#[derive(Debug, Clone, Serialize, Deserialize, TypeUuid, BehaviorSpawner)]
 #[uuid = "5c3fbd4c-5359-11ed-9c5d-02a179e5df2b"]
 pub enum MissionBehavior {
-    Debug(Debug),
-    Selector(Selector),
-    Sequencer(Sequencer),
-    All(All),
-    Any(Any),
-    Repeater(Repeater),
-    Inverter(Inverter),
-    Succeeder(Succeeder),
-    Delay(Delay),
+    #[behavior(run = "behaviors::agent_rest::run")]
     AgentRest(behaviors::agent_rest::AgentRest),
+    #[behavior(run = "behaviors::agent_work::run")]
     AgentWork(behaviors::agent_work::AgentWork),
-}
-
-impl Default for MissionBehavior {
-    fn default() -> Self {
-        Self::Debug(Debug::default())
-    }
-}
-
-impl BehaviorSpawner for MissionBehavior {
-    fn insert(&self, commands: &mut EntityCommands) {
-        match self {
-            MissionBehavior::Debug(data) => BehaviorInfo::insert_with(commands, data),
-            MissionBehavior::Selector(data) => BehaviorInfo::insert_with(commands, data),
-            MissionBehavior::Sequencer(data) => BehaviorInfo::insert_with(commands, data),
-            MissionBehavior::All(data) => BehaviorInfo::insert_with(commands, data),
-            MissionBehavior::Any(data) => BehaviorInfo::insert_with(commands, data),
-            MissionBehavior::Repeater(data) => BehaviorInfo::insert_with(commands, data),
-            MissionBehavior::Inverter(data) => BehaviorInfo::insert_with(commands, data),
-            MissionBehavior::Succeeder(data) => BehaviorInfo::insert_with(commands, data),
-            MissionBehavior::Delay(data) => BehaviorInfo::insert_with(commands, data),
-            MissionBehavior::AgentRest(data) => BehaviorInfo::insert_with(commands, data),
-            MissionBehavior::AgentWork(data) => BehaviorInfo::insert_with(commands, data),
-        }
-    }
+    WaitForTransfer(WaitForEvent<TokenTransfer>),
+    EmitTransfer(EmitEvent<TokenTransfer>),
 }
 
 fn setup() {}
@@ -87,6 +59,7 @@ pub fn create_from_data(parent: Option<Entity>, commands: &mut Commands) -> Beha
                 ],
             )],
         ),
+        ..default()
     };
     BehaviorTree::from_document(parent, commands, &document)
 }
@@ -1,3 +1,4 @@
 pub mod agent_rest;
 pub mod agent_work;
 pub mod mission_behavior;
+pub mod token_transfer;
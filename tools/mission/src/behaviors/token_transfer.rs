@@ -0,0 +1,60 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use simula_behavior::prelude::*;
+
+/// Sent when tokens are moved to another account, with the token kind like `ENERGY`
+#[derive(Debug, Clone, PartialEq)]
+pub struct TokenTransfer {
+    pub token: String,
+    pub amount: i128,
+}
+
+#[derive(Debug, Default, Reflect, Clone, Serialize, Deserialize, Inspectable)]
+pub struct TransferFilter {
+    /// Token kind to wait for, any token if empty
+    #[serde(default)]
+    pub token: BehaviorProp<String>,
+    /// Smallest amount to wait for
+    #[serde(default)]
+    pub min_amount: BehaviorProp<i64>,
+    /// Blackboard key to store the transferred amount to, if not empty
+    #[serde(default)]
+    pub store: String,
+}
+
+#[derive(Debug, Default, Reflect, Clone, Serialize, Deserialize, Inspectable)]
+pub struct TransferPayload {
+    #[serde(default)]
+    pub token: BehaviorProp<String>,
+    #[serde(default)]
+    pub amount: BehaviorProp<i64>,
+}
+
+impl BehaviorEventInfo for TokenTransfer {
+    const WAIT_NAME: &'static str = "Wait for transfer";
+    const EMIT_NAME: &'static str = "Emit transfer";
+    type Filter = TransferFilter;
+    type Payload = TransferPayload;
+
+    fn matches(&self, filter: &TransferFilter, blackboard: Option<&BehaviorBlackboard>) -> bool {
+        let token = filter.token.get(blackboard).unwrap_or_default();
+        let min_amount = filter.min_amount.get(blackboard).unwrap_or_default();
+        (token.is_empty() || token == self.token) && self.amount >= min_amount as i128
+    }
+
+    fn from_payload(
+        payload: &TransferPayload,
+        blackboard: Option<&BehaviorBlackboard>,
+    ) -> Option<Self> {
+        Some(TokenTransfer {
+            token: payload.token.get(blackboard)?,
+            amount: payload.amount.get(blackboard)? as i128,
+        })
+    }
+
+    fn store(&self, filter: &TransferFilter, blackboard: &mut BehaviorBlackboard) {
+        if !filter.store.is_empty() {
+            blackboard.set(filter.store.clone(), self.amount as i64);
+        }
+    }
+}
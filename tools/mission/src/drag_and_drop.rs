@@ -1,4 +1,4 @@
-use bevy::prelude::{App, BuildChildren, Children, Commands, Plugin, Query, ResMut,Res};
+use bevy::prelude::{App, BuildChildren, Children, Commands, EventWriter, Plugin, Query, ResMut,Res};
 use bevy_egui::{egui::*, EguiContext};
 use simula_mission::{
     account::Account,
@@ -6,7 +6,7 @@ use simula_mission::{
     wallet::Wallet,
 };
 
-use crate::{MissionToken, token_ui::AssetInfo};
+use crate::{MissionToken, token_ui::AssetInfo, behaviors::token_transfer::TokenTransfer};
 
 pub struct DragAndDropPlugin;
 
@@ -113,6 +113,7 @@ pub fn drag_and_drop(
     mut assets: Query<&mut MissionToken>,
     mut commands: Commands,
     image_texture_ids: Res<ImageTextureIds>,
+    mut transfers: EventWriter<TokenTransfer>,
 ) {
     egui::Window::new("Transfer assets")
         .open(&mut true)
@@ -286,6 +287,14 @@ pub fn drag_and_drop(
                                 }
                             }
                         }
+
+                        if !mission_tuple.0.is_empty() {
+                            // let behaviors waiting for transfers know
+                            transfers.send(TokenTransfer {
+                                token: mission_tuple.0.clone(),
+                                amount: mission_tuple.1,
+                            });
+                        }
                     }
                 }
             }
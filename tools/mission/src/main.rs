@@ -144,11 +144,6 @@ fn setup(
 
     let agent_behavior_graph = commands
         .spawn()
-        // .insert(BehaviorEditorState {
-        //     show: true,
-        //     ..default()
-        // })
-        // .insert(BehaviorGraphState::default())
         .with_children(|_parent| {
             // parent.spawn_bundle(BehaviorBundle::<AgentRest>::default());
             // parent.spawn_bundle(BehaviorBundle::<AgentWork>::default());
@@ -244,7 +239,8 @@ fn setup(
         .insert(Name::new("Agent: 001"));
 
     // Build Agent 002
-    let document: Handle<BehaviorAsset> = asset_server.load("behaviors/debug_any.bht.ron");
+    let document: Handle<BehaviorAsset> =
+        asset_server.load("behaviors/agent_wait_transfer.bht.ron");
     let behavior = BehaviorTree::from_asset::<mission_behavior::MissionBehavior>(
         None,
         &mut commands,
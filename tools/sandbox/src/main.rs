@@ -18,7 +18,7 @@ use simula_camera::{flycam::*, orbitcam::*};
 use simula_core::{
     ease::EaseFunction,
     force_graph::{NodeData, NodeIndex, SimulationParameters},
-    signal::{SignalController, SignalFunction, SignalGenerator},
+    signal::{SignalController, SignalControllerMode, SignalFunction, SignalGenerator},
 };
 use simula_net::{NetId, NetPlugin, Replicate};
 #[cfg(feature = "gif")]
@@ -51,6 +51,7 @@ fn main() {
     app.register_type::<SignalGenerator>()
         .register_type::<SignalFunction>()
         .register_type::<SignalController<f32>>()
+        .register_type::<SignalControllerMode>()
         .register_type::<ForceGraph<SandboxNodeData, SandboxEdgeData>>()
         .register_type::<SimulationParameters>()
         .register_type::<SandboxNode>()